@@ -3,11 +3,21 @@ import type {
   FindHttpResponsesResponse,
   GetHttpRequestByIdRequest,
   GetHttpRequestByIdResponse,
+  GetHttpResponseBodyChunkRequest,
+  GetHttpResponseBodyChunkResponse,
+  GetHttpResponseBodyHexdumpRequest,
+  GetHttpResponseBodyHexdumpResponse,
+  GetHttpResponseBodyLinesRequest,
+  GetHttpResponseBodyLinesResponse,
+  GetHttpResponseBodyRequest,
+  GetHttpResponseBodyResponse,
   OpenWindowRequest,
   PromptTextRequest,
   PromptTextResponse,
   RenderHttpRequestRequest,
   RenderHttpRequestResponse,
+  SearchHttpResponseBodyRequest,
+  SearchHttpResponseBodyResponse,
   SendHttpRequestRequest,
   SendHttpRequestResponse,
   ShowToastRequest,
@@ -45,6 +55,19 @@ export interface Context {
   };
   httpResponse: {
     find(args: FindHttpResponsesRequest): Promise<FindHttpResponsesResponse['httpResponses']>;
+    getBody(args: GetHttpResponseBodyRequest): Promise<GetHttpResponseBodyResponse['body']>;
+    getBodyChunk(
+      args: GetHttpResponseBodyChunkRequest,
+    ): Promise<GetHttpResponseBodyChunkResponse['chunk']>;
+    getBodyLines(
+      args: GetHttpResponseBodyLinesRequest,
+    ): Promise<GetHttpResponseBodyLinesResponse['lines']>;
+    searchBody(
+      args: SearchHttpResponseBodyRequest,
+    ): Promise<SearchHttpResponseBodyResponse['search']>;
+    getBodyHexdump(
+      args: GetHttpResponseBodyHexdumpRequest,
+    ): Promise<GetHttpResponseBodyHexdumpResponse['rows']>;
   };
   templates: {
     render(args: TemplateRenderRequest): Promise<TemplateRenderResponse['data']>;
@@ -5,6 +5,11 @@ export type FilterPluginResponse = { filtered: string };
 export type FilterPlugin = {
   name: string;
   description?: string;
+  /**
+   * MIME types to filter responses of, like `application/json` or `application/*+json`. Responses
+   * of other types are filtered by Yaak's built-in JSONPath, jq, and XPath filters.
+   */
+  mimeTypes?: string[];
   onFilter(
     ctx: Context,
     args: { payload: string; filter: string; mimeType: string },
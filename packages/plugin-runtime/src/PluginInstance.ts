@@ -5,6 +5,10 @@ import type {
   FindHttpResponsesResponse,
   FormInput,
   GetHttpRequestByIdResponse,
+  GetHttpResponseBodyChunkResponse,
+  GetHttpResponseBodyHexdumpResponse,
+  GetHttpResponseBodyLinesResponse,
+  GetHttpResponseBodyResponse,
   GetKeyValueResponse,
   HttpAuthenticationAction,
   HttpRequestAction,
@@ -14,6 +18,7 @@ import type {
   PluginDefinition,
   PromptTextResponse,
   RenderHttpRequestResponse,
+  SearchHttpResponseBodyResponse,
   SendHttpRequestResponse,
   TemplateFunction,
   TemplateRenderResponse,
@@ -122,11 +127,22 @@ export class PluginInstance {
         }
       }
 
+      if (payload.type === 'get_filter_summary_request' && this.#mod?.filter?.mimeTypes?.length) {
+        const { name, mimeTypes } = this.#mod.filter;
+        const replyPayload: InternalEventPayload = {
+          type: 'get_filter_summary_response',
+          name,
+          mimeTypes,
+        };
+        this.#sendPayload(windowContext, replyPayload, replyId);
+        return;
+      }
+
       if (payload.type === 'filter_request' && typeof this.#mod?.filter?.onFilter === 'function') {
         const reply = await this.#mod.filter.onFilter(ctx, {
           filter: payload.filter,
           payload: payload.content,
-          mimeType: payload.type,
+          mimeType: payload.mimeType,
         });
         const replyPayload: InternalEventPayload = {
           type: 'filter_response',
@@ -459,6 +475,61 @@ export class PluginInstance {
           );
           return httpResponses;
         },
+        getBody: async (args) => {
+          const payload = {
+            type: 'get_http_response_body_request',
+            ...args,
+          } as const;
+          const { body } = await this.#sendAndWaitForReply<GetHttpResponseBodyResponse>(
+            event.windowContext,
+            payload,
+          );
+          return body;
+        },
+        getBodyChunk: async (args) => {
+          const payload = {
+            type: 'get_http_response_body_chunk_request',
+            ...args,
+          } as const;
+          const { chunk } = await this.#sendAndWaitForReply<GetHttpResponseBodyChunkResponse>(
+            event.windowContext,
+            payload,
+          );
+          return chunk;
+        },
+        getBodyLines: async (args) => {
+          const payload = {
+            type: 'get_http_response_body_lines_request',
+            ...args,
+          } as const;
+          const { lines } = await this.#sendAndWaitForReply<GetHttpResponseBodyLinesResponse>(
+            event.windowContext,
+            payload,
+          );
+          return lines;
+        },
+        searchBody: async (args) => {
+          const payload = {
+            type: 'search_http_response_body_request',
+            ...args,
+          } as const;
+          const { search } = await this.#sendAndWaitForReply<SearchHttpResponseBodyResponse>(
+            event.windowContext,
+            payload,
+          );
+          return search;
+        },
+        getBodyHexdump: async (args) => {
+          const payload = {
+            type: 'get_http_response_body_hexdump_request',
+            ...args,
+          } as const;
+          const { rows } = await this.#sendAndWaitForReply<GetHttpResponseBodyHexdumpResponse>(
+            event.windowContext,
+            payload,
+          );
+          return rows;
+        },
       },
       httpRequest: {
         getById: async (args) => {
@@ -540,7 +611,7 @@ export class PluginInstance {
   }
 }
 
-function genId(len = 5): string {
+export function genId(len = 5): string {
   const alphabet = '01234567890abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ';
   let id = '';
   for (let i = 0; i < len; i++) {
@@ -0,0 +1,125 @@
+import type {
+  GetHttpResponseBodyResponse,
+  InternalEvent,
+  InternalEventPayload,
+  TemplateRenderResponse,
+  WindowContext,
+} from '@yaakapp/api';
+import { EventChannel } from './EventChannel';
+import { genId } from './PluginInstance';
+import { runPostResponseScript, runPreRequestScript, ScriptHost } from './scripts';
+
+/** Events for scripts are addressed to this rather than a plugin. Must match the app's. */
+export const SCRIPT_RUNNER_REF_ID = '__scripts__';
+
+/**
+ * Runs pre-request and post-response scripts. These come from workspace data, which may have
+ * been synced or imported, so unlike plugins they don't get Node or a plugin context, only the
+ * sandbox in ./scripts.
+ */
+export class ScriptRunner {
+  #pluginToAppEvents: EventChannel;
+  #appToScriptEvents = new EventChannel();
+
+  constructor(pluginToAppEvents: EventChannel) {
+    this.#pluginToAppEvents = pluginToAppEvents;
+    this.#appToScriptEvents.listen(async (event) => {
+      await this.#onMessage(event);
+    });
+  }
+
+  postMessage(event: InternalEvent) {
+    this.#appToScriptEvents.emit(event);
+  }
+
+  async #onMessage(event: InternalEvent) {
+    const { windowContext, payload, id: replyId } = event;
+    const host = this.#newHost(windowContext);
+    try {
+      if (payload.type === 'call_pre_request_script_request') {
+        const reply = await runPreRequestScript(host, payload);
+        this.#sendPayload(
+          windowContext,
+          { type: 'call_pre_request_script_response', ...reply },
+          replyId,
+        );
+        return;
+      }
+
+      if (payload.type === 'call_post_response_script_request') {
+        const reply = await runPostResponseScript(host, payload);
+        this.#sendPayload(
+          windowContext,
+          { type: 'call_post_response_script_response', ...reply },
+          replyId,
+        );
+        return;
+      }
+    } catch (err) {
+      this.#sendPayload(windowContext, { type: 'error_response', error: `${err}` }, replyId);
+      return;
+    }
+
+    // Replies to our own requests are picked up by #sendAndWaitForReply
+    if (event.replyId == null) {
+      this.#sendPayload(windowContext, { type: 'empty_response' }, replyId);
+    }
+  }
+
+  #newHost(windowContext: WindowContext): ScriptHost {
+    return {
+      render: async (data) => {
+        const payload = { type: 'template_render_request', data, purpose: 'send' } as const;
+        const result = await this.#sendAndWaitForReply<TemplateRenderResponse>(
+          windowContext,
+          payload,
+        );
+        return result.data;
+      },
+      getResponseBody: async (responseId) => {
+        const payload = { type: 'get_http_response_body_request', responseId } as const;
+        const { body } = await this.#sendAndWaitForReply<GetHttpResponseBodyResponse>(
+          windowContext,
+          payload,
+        );
+        return body;
+      },
+    };
+  }
+
+  #sendPayload(
+    windowContext: WindowContext,
+    payload: InternalEventPayload,
+    replyId: string | null,
+  ): string {
+    const event: InternalEvent = {
+      pluginRefId: SCRIPT_RUNNER_REF_ID,
+      pluginName: 'scripts',
+      id: genId(),
+      replyId,
+      payload,
+      windowContext,
+    };
+    this.#pluginToAppEvents.emit(event);
+    return event.id;
+  }
+
+  #sendAndWaitForReply<T extends Omit<InternalEventPayload, 'type'>>(
+    windowContext: WindowContext,
+    payload: InternalEventPayload,
+  ): Promise<T> {
+    return new Promise<T>((resolve) => {
+      let id: string | null = null;
+      const cb = (event: InternalEvent) => {
+        if (id != null && event.replyId === id) {
+          this.#appToScriptEvents.unlisten(cb); // Unlisten, now that we're done
+          const { type: _, ...payload } = event.payload;
+          resolve(payload as T);
+        }
+      };
+      // Listen before sending, so the reply can't be missed
+      this.#appToScriptEvents.listen(cb);
+      id = this.#sendPayload(windowContext, payload, null);
+    });
+  }
+}
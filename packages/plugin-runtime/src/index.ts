@@ -1,6 +1,7 @@
 import type { InternalEvent } from '@yaakapp/api';
 import { EventChannel } from './EventChannel';
 import { PluginHandle } from './PluginHandle';
+import { SCRIPT_RUNNER_REF_ID, ScriptRunner } from './ScriptRunner';
 import WebSocket from 'ws';
 
 const port = process.env.PORT;
@@ -10,6 +11,7 @@ if (!port) {
 
 const pluginToAppEvents = new EventChannel();
 const plugins: Record<string, PluginHandle> = {};
+const scripts = new ScriptRunner(pluginToAppEvents);
 
 const ws = new WebSocket(`ws://localhost:${port}`);
 
@@ -32,6 +34,11 @@ pluginToAppEvents.listen((e) => {
 
 async function handleIncoming(msg: string) {
   const pluginEvent: InternalEvent = JSON.parse(msg);
+  if (pluginEvent.pluginRefId === SCRIPT_RUNNER_REF_ID) {
+    scripts.postMessage(pluginEvent);
+    return;
+  }
+
   // Handle special event to bootstrap plugin
   if (pluginEvent.payload.type === 'boot_request') {
     const plugin = new PluginHandle(pluginEvent.pluginRefId, pluginEvent.payload, pluginToAppEvents);
@@ -0,0 +1,139 @@
+import type {
+  CallPostResponseScriptRequest,
+  CallPostResponseScriptResponse,
+  CallPreRequestScriptRequest,
+  CallPreRequestScriptResponse,
+  HttpAssertionResult,
+  HttpRequest,
+  HttpResponse,
+} from '@yaakapp/api';
+import vm from 'node:vm';
+
+/** What the app does for scripts, on their behalf */
+export interface ScriptHost {
+  render(data: unknown): Promise<unknown>;
+  getResponseBody(responseId: string): Promise<string | null>;
+}
+
+interface ScriptInput {
+  script: string;
+  request: HttpRequest;
+  response: HttpResponse | null;
+  variables: { [name: string]: string | undefined };
+}
+
+interface ScriptOutput {
+  request: HttpRequest;
+  variables: { [name: string]: string };
+  assertionResults: HttpAssertionResult[];
+}
+
+/**
+ * Sets up the `yaak` global and runs the script, inside the script's own context. Nothing from
+ * this runtime is handed to the script: data comes in and out as JSON, and calls to the host go
+ * through `bridge`, which the script can't reach. Otherwise it could climb the prototypes of what
+ * it was given to get at `Function`, and from there `process` and `require`.
+ */
+const SANDBOX_MAIN = `(async function (bridge, input) {
+  // Strict mode, so script functions called from here can't see their callers
+  'use strict';
+  const { script, request, response, variables } = JSON.parse(input);
+
+  const call = async (name, args) => {
+    let result;
+    try {
+      result = await bridge(name, JSON.stringify(args ?? null));
+    } catch (err) {
+      throw new Error(String(err));
+    }
+    return JSON.parse(result);
+  };
+
+  const setVariables = {};
+  const assertionResults = [];
+  const pendingTests = [];
+  const yaak = {
+    request,
+    variables: {
+      get: (name) => setVariables[name] ?? variables[name],
+      set: (name, value) => {
+        setVariables[name] = String(value);
+      },
+    },
+    test(name, fn) {
+      const check = async () => {
+        let passed = false;
+        let error = null;
+        try {
+          // Tests fail by throwing or returning false
+          passed = (await fn()) !== false;
+        } catch (err) {
+          error = String(err);
+        }
+        assertionResults.push({
+          assertion: { enabled: true, assertionType: 'script', target: String(name), value: '' },
+          passed,
+          actual: null,
+          error,
+        });
+      };
+      pendingTests.push(check());
+    },
+    render: (data) => call('render', data),
+  };
+
+  if (response != null) {
+    const text = () => call('getResponseBody', response.id);
+    yaak.response = { ...response, text, json: async () => JSON.parse((await text()) ?? 'null') };
+  }
+
+  // Scripts can use await at the top level, so they're run as the body of an async function
+  const AsyncFunction = (async function () {}).constructor;
+  await new AsyncFunction('yaak', script)(yaak);
+
+  // Async tests may still be going if the script didn't await them
+  await Promise.all(pendingTests);
+  return JSON.stringify({ request: yaak.request, variables: setVariables, assertionResults });
+})`;
+
+export async function runPreRequestScript(
+  host: ScriptHost,
+  { script, httpRequest, variables }: CallPreRequestScriptRequest,
+): Promise<CallPreRequestScriptResponse> {
+  const output = await run(host, { script, request: httpRequest, response: null, variables });
+  return { httpRequest: output.request, variables: output.variables };
+}
+
+export async function runPostResponseScript(
+  host: ScriptHost,
+  { script, httpRequest, httpResponse, variables }: CallPostResponseScriptRequest,
+): Promise<CallPostResponseScriptResponse> {
+  const output = await run(host, {
+    script,
+    request: httpRequest,
+    response: httpResponse,
+    variables,
+  });
+  return { variables: output.variables, assertionResults: output.assertionResults };
+}
+
+async function run(host: ScriptHost, input: ScriptInput): Promise<ScriptOutput> {
+  // A fresh context for every script, with nothing in it but the JavaScript built-ins
+  const context = vm.createContext();
+  const main = vm.runInContext(SANDBOX_MAIN, context);
+
+  const bridge = async (name: string, args: string) => {
+    const parsed = JSON.parse(args);
+    switch (name) {
+      case 'render':
+        return JSON.stringify(await host.render(parsed));
+      case 'getResponseBody':
+        return JSON.stringify(await host.getResponseBody(`${parsed}`));
+      default:
+        throw new Error(`Unknown script call ${name}`);
+    }
+  };
+
+  const output: string = await main(bridge, JSON.stringify(input));
+  return JSON.parse(output);
+}
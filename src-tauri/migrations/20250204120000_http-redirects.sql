@@ -0,0 +1,5 @@
+ALTER TABLE workspaces
+    ADD COLUMN setting_max_redirects INTEGER DEFAULT 10 NOT NULL;
+
+ALTER TABLE http_responses
+    ADD COLUMN redirects TEXT DEFAULT '[]' NOT NULL;
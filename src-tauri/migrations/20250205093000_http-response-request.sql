@@ -0,0 +1,11 @@
+ALTER TABLE http_responses
+    ADD COLUMN request_url TEXT DEFAULT '' NOT NULL;
+
+ALTER TABLE http_responses
+    ADD COLUMN request_headers TEXT DEFAULT '[]' NOT NULL;
+
+ALTER TABLE http_responses
+    ADD COLUMN request_body_path TEXT;
+
+ALTER TABLE http_responses
+    ADD COLUMN request_content_length INTEGER;
@@ -0,0 +1,5 @@
+ALTER TABLE http_requests
+    ADD COLUMN disabled_cookies TEXT DEFAULT '[]' NOT NULL;
+
+ALTER TABLE http_responses
+    ADD COLUMN request_cookies TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1,6 @@
+ALTER TABLE workspaces
+    ADD COLUMN setting_client_certificates TEXT DEFAULT '[]' NOT NULL;
+
+-- Passphrases are kept by the file they decrypt on workspace meta, which doesn't get synced
+ALTER TABLE workspace_metas
+    ADD COLUMN setting_client_certificate_passphrases TEXT DEFAULT '{}' NOT NULL;
@@ -0,0 +1,5 @@
+ALTER TABLE workspaces
+    ADD COLUMN setting_ca_certificates TEXT DEFAULT '[]' NOT NULL;
+
+ALTER TABLE workspaces
+    ADD COLUMN setting_certificate_pins TEXT DEFAULT '[]' NOT NULL;
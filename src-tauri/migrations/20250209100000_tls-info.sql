@@ -0,0 +1,8 @@
+ALTER TABLE http_responses
+    ADD COLUMN tls TEXT;
+
+ALTER TABLE grpc_connections
+    ADD COLUMN tls TEXT;
+
+ALTER TABLE websocket_connections
+    ADD COLUMN tls TEXT;
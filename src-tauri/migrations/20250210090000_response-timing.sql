@@ -0,0 +1,2 @@
+ALTER TABLE http_responses
+    ADD COLUMN timing TEXT;
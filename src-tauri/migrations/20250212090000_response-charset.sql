@@ -0,0 +1,2 @@
+ALTER TABLE http_requests
+    ADD COLUMN response_charset TEXT;
@@ -0,0 +1,13 @@
+ALTER TABLE workspaces
+    ADD COLUMN setting_history_max_count INTEGER DEFAULT 20 NOT NULL;
+ALTER TABLE workspaces
+    ADD COLUMN setting_history_max_age_days INTEGER DEFAULT 0 NOT NULL;
+ALTER TABLE workspaces
+    ADD COLUMN setting_history_max_bytes INTEGER DEFAULT 0 NOT NULL;
+
+ALTER TABLE http_requests
+    ADD COLUMN history_max_count INTEGER;
+ALTER TABLE http_requests
+    ADD COLUMN history_max_age_days INTEGER;
+ALTER TABLE http_requests
+    ADD COLUMN history_max_bytes INTEGER;
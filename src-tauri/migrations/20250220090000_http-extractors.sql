@@ -0,0 +1,5 @@
+ALTER TABLE http_requests
+    ADD COLUMN extractors TEXT DEFAULT '[]' NOT NULL;
+
+ALTER TABLE http_responses
+    ADD COLUMN extracted_values TEXT DEFAULT '[]' NOT NULL;
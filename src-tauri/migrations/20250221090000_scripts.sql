@@ -0,0 +1,18 @@
+ALTER TABLE workspaces
+    ADD COLUMN pre_request_script TEXT DEFAULT '' NOT NULL;
+ALTER TABLE workspaces
+    ADD COLUMN post_response_script TEXT DEFAULT '' NOT NULL;
+
+ALTER TABLE folders
+    ADD COLUMN pre_request_script TEXT DEFAULT '' NOT NULL;
+ALTER TABLE folders
+    ADD COLUMN post_response_script TEXT DEFAULT '' NOT NULL;
+
+ALTER TABLE http_requests
+    ADD COLUMN pre_request_script TEXT DEFAULT '' NOT NULL;
+ALTER TABLE http_requests
+    ADD COLUMN post_response_script TEXT DEFAULT '' NOT NULL;
+
+-- Kept on workspace meta, which doesn't get synced, so every machine decides for itself
+ALTER TABLE workspace_metas
+    ADD COLUMN setting_trust_scripts BOOLEAN DEFAULT FALSE NOT NULL;
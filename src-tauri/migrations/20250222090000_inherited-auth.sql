@@ -0,0 +1,14 @@
+ALTER TABLE workspaces
+    ADD COLUMN headers TEXT DEFAULT '[]' NOT NULL;
+ALTER TABLE workspaces
+    ADD COLUMN authentication TEXT DEFAULT '{}' NOT NULL;
+ALTER TABLE workspaces
+    ADD COLUMN authentication_type TEXT NULL;
+
+-- Existing folders pass along whatever authentication their parent has
+ALTER TABLE folders
+    ADD COLUMN headers TEXT DEFAULT '[]' NOT NULL;
+ALTER TABLE folders
+    ADD COLUMN authentication TEXT DEFAULT '{}' NOT NULL;
+ALTER TABLE folders
+    ADD COLUMN authentication_type TEXT DEFAULT 'inherit' NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE folders
+    ADD COLUMN variables TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1,254 @@
+//! Headless runner for CI. It links the app library, so building it needs Tauri's system
+//! dependencies (GTK and WebKitGTK on Linux) even though no window is opened. See DEVELOPMENT.md.
+
+use clap::Parser;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri_app_lib::headless::{
+    environment_with_overrides, send_http_request_headless, HeadlessWorkspace,
+};
+use tokio::sync::Mutex;
+use yaak_http::runner::{
+    describe_assertion, format_report, read_data_file, response_passed, run_http_requests,
+    ReportFormat, RunOptions,
+};
+use yaak_models::models::{HttpRequest, HttpResponse, HttpRunReport};
+
+/// Send the requests of a Yaak workspace without launching the app
+#[derive(Parser, Debug)]
+#[command(name = "yaak", version)]
+struct Cli {
+    /// Path to a Yaak database file (db.sqlite in the app data directory)
+    #[arg(
+        long,
+        conflicts_with = "sync_dir",
+        required_unless_present = "sync_dir"
+    )]
+    db: Option<PathBuf>,
+
+    /// Path to a workspace directory written by Yaak's filesystem sync
+    #[arg(long)]
+    sync_dir: Option<PathBuf>,
+
+    /// Workspace ID or name (required with --db)
+    #[arg(long, short)]
+    workspace: Option<String>,
+
+    /// Environment ID or name to render requests with
+    #[arg(long, short)]
+    environment: Option<String>,
+
+    /// Only send requests under this folder (ID or name)
+    #[arg(long, short)]
+    folder: Option<String>,
+
+    /// Only send these requests (ID or name). Can be repeated.
+    #[arg(long, short)]
+    request: Vec<String>,
+
+    /// Set a variable, overriding the environment. Can be repeated.
+    #[arg(long = "var", value_name = "NAME=VALUE", value_parser = parse_var)]
+    vars: Vec<(String, String)>,
+
+    /// Cookie jar ID or name to send and store cookies with (only with --db)
+    #[arg(long, short)]
+    cookie_jar: Option<String>,
+
+    /// Skip the remaining requests once one fails
+    #[arg(long)]
+    stop_on_failure: bool,
+
+    /// Milliseconds to wait between requests
+    #[arg(long, value_name = "MS", default_value_t = 0)]
+    delay: u64,
+
+    /// CSV or JSON file of rows to send every request with, once per row. Columns (or
+    /// properties) are set as variables, overriding the environment.
+    #[arg(long, value_name = "PATH")]
+    data: Option<PathBuf>,
+
+    /// Write a report of the run to this file
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+
+    /// Format of the report: junit or json. Defaults to json for .json files and junit otherwise.
+    #[arg(long, value_name = "FORMAT", requires = "report")]
+    report_format: Option<ReportFormat>,
+
+    /// Print response headers and bodies
+    #[arg(long, short)]
+    verbose: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match tauri::async_runtime::block_on(run(cli)) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns whether all requests succeeded
+async fn run(cli: Cli) -> Result<bool, String> {
+    let ws = match (&cli.db, &cli.sync_dir) {
+        (Some(db), _) => {
+            let workspace = cli.workspace.as_deref().ok_or("--workspace is required with --db")?;
+            HeadlessWorkspace::from_db(db, workspace)?
+        }
+        (None, Some(dir)) => HeadlessWorkspace::from_sync_dir(dir)?,
+        (None, None) => return Err("One of --db or --sync-dir is required".to_string()),
+    };
+
+    let base_environment = ws.base_environment();
+    let environment = match &cli.environment {
+        Some(e) => Some(ws.find_environment(e).ok_or(format!("Environment not found: {e}"))?),
+        None => None,
+    };
+    let environment = environment_with_overrides(environment, &cli.vars);
+
+    let folder_id = match &cli.folder {
+        Some(f) => Some(ws.find_folder(f).ok_or(format!("Folder not found: {f}"))?.id.clone()),
+        None => None,
+    };
+
+    let requests = match cli.request.is_empty() {
+        true => ws.http_requests_in_order(folder_id.as_deref()),
+        false => {
+            let mut requests = Vec::new();
+            for r in &cli.request {
+                requests.push(ws.find_http_request(r).ok_or(format!("Request not found: {r}"))?);
+            }
+            requests.into_iter().cloned().collect()
+        }
+    };
+    // Otherwise these would be sent without the authentication they expect
+    ws.check_authentication(&requests)?;
+
+    let cookie_jar = match &cli.cookie_jar {
+        Some(c) => Some(ws.find_cookie_jar(c).ok_or(format!("Cookie jar not found: {c}"))?.clone()),
+        None => None,
+    };
+    // Shared so cookies set by one request are sent with the next
+    let cookie_jar = Arc::new(Mutex::new(cookie_jar));
+
+    let responses_dir = std::env::temp_dir().join("yaak-cli");
+    let options = RunOptions {
+        stop_on_failure: cli.stop_on_failure,
+        delay: Duration::from_millis(cli.delay),
+        data: match &cli.data {
+            Some(path) => read_data_file(path)?,
+            None => Vec::new(),
+        },
+    };
+
+    let report = run_http_requests(requests, &options, |request, variables| {
+        let (ws, base_environment, environment) = (&ws, &base_environment, &environment);
+        let (cookie_jar, responses_dir, verbose) =
+            (cookie_jar.clone(), &responses_dir, cli.verbose);
+        async move {
+            let response = send_http_request_headless(
+                &ws.workspace,
+                &request,
+                &ws.inherited(request.folder_id.as_deref()),
+                base_environment,
+                environment.as_ref(),
+                variables,
+                cookie_jar.lock().await.as_mut(),
+                responses_dir,
+            )
+            .await?;
+            print_response(&request, &response, verbose);
+            Ok(response)
+        }
+    })
+    .await;
+
+    let all_ok = report.results.iter().all(|r| r.passed);
+    for r in report.results.iter().filter(|r| r.response.is_none()) {
+        println!("- {}: skipped", r.name);
+    }
+
+    if let Some(path) = &cli.report {
+        let format = cli.report_format.unwrap_or(
+            match path.extension().is_some_and(|e| e.eq_ignore_ascii_case("json")) {
+                true => ReportFormat::Json,
+                false => ReportFormat::JUnit,
+            },
+        );
+        let folder = folder_id.as_deref().and_then(|id| ws.find_folder(id));
+        let report = HttpRunReport {
+            name: folder.map(|f| f.name.clone()).unwrap_or(ws.workspace.name.clone()),
+            workspace_id: ws.workspace.id.clone(),
+            folder_id,
+            environment_id: environment.map(|e| e.id).filter(|id| !id.is_empty()),
+            ..report
+        };
+        let contents = format_report(&report, format)?;
+        std::fs::write(path, contents)
+            .map_err(|e| format!("Failed to write report {path:?}: {e}"))?;
+    }
+
+    Ok(all_ok)
+}
+
+fn print_response(request: &HttpRequest, response: &HttpResponse, verbose: bool) {
+    let body = match &response.body_path {
+        Some(p) => {
+            let body = std::fs::read(p).unwrap_or_default();
+            let _ = std::fs::remove_file(p);
+            body
+        }
+        None => Vec::new(),
+    };
+    if let Some(p) = &response.request_body_path {
+        let _ = std::fs::remove_file(p);
+    }
+
+    let ok = response_passed(response);
+    let name = if request.name.is_empty() { &request.url } else { &request.name };
+    match &response.error {
+        Some(e) => eprintln!("✗ {name}: {e}"),
+        None => println!(
+            "{} {name}: {} {} ({} ms)",
+            if ok { "✓" } else { "✗" },
+            response.status,
+            response.status_reason.as_deref().unwrap_or_default(),
+            response.elapsed,
+        ),
+    }
+    for r in &response.assertion_results {
+        let detail = match (&r.error, &r.actual) {
+            (Some(e), _) => format!(" ({e})"),
+            (None, Some(a)) if !r.passed => format!(" (got {a})"),
+            _ => String::new(),
+        };
+        let mark = if r.passed { "✓" } else { "✗" };
+        println!("  {mark} {}{detail}", describe_assertion(&r.assertion));
+    }
+    for v in &response.extracted_values {
+        match (&v.value, &v.error) {
+            (Some(value), _) => println!("  → {} = {value}", v.extractor.variable),
+            (None, e) => println!("  → {}: {}", v.extractor.variable, e.as_deref().unwrap_or("")),
+        }
+    }
+
+    if verbose {
+        for h in &response.headers {
+            println!("  {}: {}", h.name, h.value);
+        }
+        println!("{}", String::from_utf8_lossy(&body));
+    }
+}
+
+fn parse_var(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("Expected NAME=VALUE but got \"{s}\"")),
+    }
+}
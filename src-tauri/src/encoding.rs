@@ -1,16 +1,23 @@
-use encoding_rs::SHIFT_JIS;
-use tokio::fs;
+use tauri::{Manager, Runtime};
+use yaak_http::error::Result;
+use yaak_http::response_body::DecodedBody;
 use yaak_models::models::HttpResponse;
+use yaak_models::queries::get_http_request;
 
-pub async fn read_response_body<'a>(
-    response: HttpResponse,
-) -> Option<String> {
-    let body_path = match response.body_path {
-        None => return None,
-        Some(p) => p,
-    };
+/// The charset a response's request overrides its body's with, if any
+pub async fn response_charset<R: Runtime>(
+    mgr: &impl Manager<R>,
+    response: &HttpResponse,
+) -> Result<Option<String>> {
+    let request = get_http_request(mgr, &response.request_id).await?;
+    Ok(request.and_then(|r| r.response_charset))
+}
 
-    let body = fs::read(body_path).await.unwrap();
-    let (s, _, _) = SHIFT_JIS.decode(body.as_slice());
-    Some(s.to_string())
+/// Read and decode a response body, using the charset its request overrides it with, if any
+pub async fn read_response_body<R: Runtime>(
+    mgr: &impl Manager<R>,
+    response: &HttpResponse,
+) -> Result<Option<DecodedBody>> {
+    let charset = response_charset(mgr, response).await?;
+    yaak_http::response_body::read_response_body(response, charset.as_deref()).await
 }
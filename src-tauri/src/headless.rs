@@ -0,0 +1,446 @@
+use crate::render::render_http_request_with_layers;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use rusqlite::{Connection, OpenFlags, Row};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use yaak_http::client::HttpConnectionOptions;
+use yaak_http::error::Error::GenericError;
+use yaak_http::runner::http_requests_in_order;
+use yaak_http::sender::{send_http_request, HttpSendCallback};
+use yaak_models::models::{
+    CookieJar, Environment, EnvironmentVariable, Folder, HttpRequest, HttpResponse,
+    HttpResponseEvent, HttpResponseHeader, Workspace, WorkspaceMeta,
+};
+use yaak_models::render::Inherited;
+use yaak_sync::models::SyncModel;
+use yaak_templates::TemplateCallback;
+
+/// Everything needed to send the requests of a workspace without a running app
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessWorkspace {
+    pub workspace: Workspace,
+    pub environments: Vec<Environment>,
+    pub folders: Vec<Folder>,
+    pub http_requests: Vec<HttpRequest>,
+    // Only available from a database, since cookie jars aren't synced
+    pub cookie_jars: Vec<CookieJar>,
+}
+
+impl HeadlessWorkspace {
+    /// Load a workspace (matched by ID or name) from an app database file. The database
+    /// is opened read-only so this is safe to run while the app is open.
+    pub fn from_db(db_path: &Path, workspace_id_or_name: &str) -> Result<Self, String> {
+        let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .map_err(|e| format!("Failed to open database {db_path:?}: {e}"))?;
+
+        let workspaces: Vec<Workspace> = query_all(&conn, "SELECT * FROM workspaces", [])?;
+        let mut workspace = find_by_id_or_name(&workspaces, workspace_id_or_name, |w| {
+            (w.id.as_str(), w.name.as_str())
+        })
+        .ok_or(format!("Workspace not found: {workspace_id_or_name}"))?
+        .to_owned();
+
+        let params = [workspace.id.as_str()];
+
+        // Client certificate passphrases are kept in the workspace meta, since it isn't synced
+        let metas: Vec<WorkspaceMeta> =
+            query_all(&conn, "SELECT * FROM workspace_metas WHERE workspace_id = ?1", params)?;
+        if let Some(meta) = metas.first() {
+            workspace.setting_client_certificates =
+                meta.client_certificates(&workspace.setting_client_certificates);
+        }
+
+        Ok(Self {
+            environments: query_all(
+                &conn,
+                "SELECT * FROM environments WHERE workspace_id = ?1",
+                params,
+            )?,
+            folders: query_all(&conn, "SELECT * FROM folders WHERE workspace_id = ?1", params)?,
+            http_requests: query_all(
+                &conn,
+                "SELECT * FROM http_requests WHERE workspace_id = ?1",
+                params,
+            )?,
+            cookie_jars: query_all(
+                &conn,
+                "SELECT * FROM cookie_jars WHERE workspace_id = ?1",
+                params,
+            )?,
+            workspace,
+        })
+    }
+
+    /// Load a workspace from a directory of files written by `yaak-sync`, including any in
+    /// nested directories
+    pub fn from_sync_dir(dir: &Path) -> Result<Self, String> {
+        let mut paths = Vec::new();
+        collect_sync_files(dir, &mut paths)?;
+
+        let mut workspace = None;
+        let mut environments = Vec::new();
+        let mut folders = Vec::new();
+        let mut http_requests = Vec::new();
+        for path in paths {
+            match SyncModel::from_file(&path).map_err(|e| format!("{path:?}: {e}"))? {
+                Some((SyncModel::Workspace(m), _)) => workspace = Some(m),
+                Some((SyncModel::Environment(m), _)) => environments.push(m),
+                Some((SyncModel::Folder(m), _)) => folders.push(m),
+                Some((SyncModel::HttpRequest(m), _)) => http_requests.push(m),
+                Some((SyncModel::GrpcRequest(_), _)) => {}
+                Some((SyncModel::WebsocketRequest(_), _)) => {}
+                None => {}
+            }
+        }
+
+        Ok(Self {
+            workspace: workspace.ok_or(format!("No workspace file found in {dir:?}"))?,
+            environments,
+            folders,
+            http_requests,
+            cookie_jars: Vec::new(),
+        })
+    }
+
+    pub fn base_environment(&self) -> Environment {
+        match self.environments.iter().find(|e| e.environment_id.is_none()) {
+            Some(e) => e.to_owned(),
+            None => Environment {
+                model: "environment".to_string(),
+                workspace_id: self.workspace.id.clone(),
+                name: "Global Variables".to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn find_environment(&self, id_or_name: &str) -> Option<&Environment> {
+        let sub_environments =
+            self.environments.iter().filter(|e| e.environment_id.is_some()).collect::<Vec<_>>();
+        find_by_id_or_name(&sub_environments, id_or_name, |e| (e.id.as_str(), e.name.as_str()))
+            .copied()
+    }
+
+    pub fn find_folder(&self, id_or_name: &str) -> Option<&Folder> {
+        find_by_id_or_name(&self.folders, id_or_name, |f| (f.id.as_str(), f.name.as_str()))
+    }
+
+    /// Headers and authentication that requests in the given folder inherit
+    pub fn inherited(&self, folder_id: Option<&str>) -> Inherited {
+        let mut folders = Vec::new();
+        let mut next_id = folder_id;
+        while let Some(f) = next_id.and_then(|id| self.folders.iter().find(|f| f.id == id)) {
+            next_id = f.folder_id.as_deref();
+            folders.insert(0, f.clone());
+        }
+        Inherited::new(&self.workspace, &folders)
+    }
+
+    pub fn find_http_request(&self, id_or_name: &str) -> Option<&HttpRequest> {
+        find_by_id_or_name(&self.http_requests, id_or_name, |r| (r.id.as_str(), r.name.as_str()))
+    }
+
+    pub fn find_cookie_jar(&self, id_or_name: &str) -> Option<&CookieJar> {
+        find_by_id_or_name(&self.cookie_jars, id_or_name, |c| (c.id.as_str(), c.name.as_str()))
+    }
+
+    /// Fail if any of the requests use authentication that can't be applied without the
+    /// plugin runtime, rather than letting them run and fail one by one
+    pub fn check_authentication(&self, requests: &[HttpRequest]) -> Result<(), String> {
+        for request in requests {
+            let (authentication_type, authentication) = self
+                .inherited(request.folder_id.as_deref())
+                .authentication(&request.authentication_type, &request.authentication);
+            if let Err(e) = authorization(authentication_type.as_deref(), &authentication) {
+                let name = if request.name.is_empty() { &request.url } else { &request.name };
+                return Err(format!("{name}: {e}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// All HTTP requests under the given folder (or the whole workspace), in the same
+    /// order they appear in the sidebar.
+    pub fn http_requests_in_order(&self, folder_id: Option<&str>) -> Vec<HttpRequest> {
+        http_requests_in_order(&self.folders, &self.http_requests, folder_id)
+    }
+}
+
+/// Template callback for when there is no plugin runtime to call into
+pub struct HeadlessTemplateCallback;
+
+impl TemplateCallback for HeadlessTemplateCallback {
+    async fn run(&self, fn_name: &str, _args: HashMap<String, String>) -> Result<String, String> {
+        Err(format!("Template function {fn_name}() is not available in headless mode"))
+    }
+}
+
+/// Add variables on top of an environment, overriding any that share the same name
+pub fn environment_with_overrides(
+    environment: Option<&Environment>,
+    overrides: &Vec<(String, String)>,
+) -> Option<Environment> {
+    if overrides.is_empty() {
+        return environment.cloned();
+    }
+
+    let mut environment = environment.cloned().unwrap_or_default();
+    for (name, value) in overrides {
+        environment.variables.retain(|v| &v.name != name);
+        environment.variables.push(EnvironmentVariable {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            id: None,
+        });
+    }
+
+    Some(environment)
+}
+
+/// Render and send a single request, with `variables` overriding the environment's. Response
+/// bodies are written to `responses_dir`, and cookies the response sets are added to
+/// `cookie_jar` in memory.
+pub async fn send_http_request_headless(
+    workspace: &Workspace,
+    unrendered_request: &HttpRequest,
+    inherited: &Inherited,
+    base_environment: &Environment,
+    environment: Option<&Environment>,
+    variables: Vec<EnvironmentVariable>,
+    cookie_jar: Option<&mut CookieJar>,
+    responses_dir: &Path,
+) -> Result<HttpResponse, String> {
+    let response = HttpResponse {
+        workspace_id: unrendered_request.workspace_id.clone(),
+        request_id: unrendered_request.id.clone(),
+        ..HttpResponse::new()
+    };
+
+    let mut layers = inherited.variable_layers(base_environment, environment);
+    layers.push("Run", variables);
+    let request = render_http_request_with_layers(
+        unrendered_request,
+        inherited,
+        &layers,
+        &HeadlessTemplateCallback,
+    )
+    .await;
+
+    let options = HttpConnectionOptions::new(workspace, None);
+    let cb = HeadlessSendCallback {
+        responses_dir: responses_dir.to_path_buf(),
+        cookie_jar: Default::default(),
+    };
+
+    // Nothing cancels a headless request, but the sender must stay alive until it's done
+    let (_cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+    let jar = cookie_jar.as_deref().cloned();
+    let response = send_http_request(&cb, &request, &response, &options, jar, &mut cancel_rx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if let (Some(cookie_jar), Some(updated)) = (cookie_jar, cb.cookie_jar.lock().unwrap().take()) {
+        *cookie_jar = updated;
+    }
+
+    Ok(response)
+}
+
+/// Send callback that doesn't persist anything
+#[derive(Clone)]
+struct HeadlessSendCallback {
+    responses_dir: PathBuf,
+    // The cookie jar as the response left it, for the caller to carry over to the next request
+    cookie_jar: Arc<Mutex<Option<CookieJar>>>,
+}
+
+impl HttpSendCallback for HeadlessSendCallback {
+    async fn update_response(
+        &self,
+        response: &HttpResponse,
+    ) -> yaak_http::error::Result<HttpResponse> {
+        Ok(response.clone())
+    }
+
+    fn responses_dir(&self) -> PathBuf {
+        self.responses_dir.clone()
+    }
+
+    async fn authenticate(
+        &self,
+        request: &HttpRequest,
+        _method: &str,
+        _url: &str,
+        _headers: Vec<HttpResponseHeader>,
+    ) -> yaak_http::error::Result<Vec<HttpResponseHeader>> {
+        let authentication_type = request.authentication_type.as_deref();
+        let value = match authorization(authentication_type, &request.authentication) {
+            Ok(Some(value)) => value,
+            Ok(None) => return Ok(Vec::new()),
+            Err(e) => return Err(GenericError(e)),
+        };
+
+        Ok(vec![HttpResponseHeader {
+            name: "Authorization".to_string(),
+            value,
+        }])
+    }
+
+    async fn update_cookie_jar(&self, cookie_jar: &CookieJar) -> yaak_http::error::Result<()> {
+        *self.cookie_jar.lock().unwrap() = Some(cookie_jar.clone());
+        Ok(())
+    }
+
+    async fn add_response_event(&self, _event: &HttpResponseEvent) -> yaak_http::error::Result<()> {
+        Ok(())
+    }
+}
+
+/// The Authorization header for a request's authentication. Only the first-party Basic and
+/// Bearer plugins are supported, since there is no plugin runtime to call out to.
+fn authorization(
+    authentication_type: Option<&str>,
+    authentication: &BTreeMap<String, Value>,
+) -> Result<Option<String>, String> {
+    if authentication.get("disabled").and_then(|v| v.as_bool()).unwrap_or_default() {
+        return Ok(None);
+    }
+
+    let get = |key: &str| authentication.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+    match authentication_type {
+        None => Ok(None),
+        Some("basic") => {
+            let credentials = format!("{}:{}", get("username"), get("password"));
+            Ok(Some(format!("Basic {}", BASE64_STANDARD.encode(credentials))))
+        }
+        Some("bearer") => Ok(Some(format!("Bearer {}", get("token")))),
+        Some(name) => Err(format!("Authentication {name} is not supported in headless mode")),
+    }
+}
+
+/// Every file under `dir`, skipping hidden directories like `.git`
+fn collect_sync_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read sync dir {dir:?}: {e}"))?;
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_file() {
+            paths.push(path);
+        } else if path.is_dir()
+            && !path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.'))
+        {
+            collect_sync_files(&path, paths)?;
+        }
+    }
+    Ok(())
+}
+
+fn query_all<T, P>(conn: &Connection, sql: &str, params: P) -> Result<Vec<T>, String>
+where
+    T: for<'a, 'b> TryFrom<&'a Row<'b>, Error = rusqlite::Error>,
+    P: rusqlite::Params,
+{
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let items = stmt.query_map(params, |row| T::try_from(row)).map_err(|e| e.to_string())?;
+    items.collect::<rusqlite::Result<Vec<T>>>().map_err(|e| e.to_string())
+}
+
+fn find_by_id_or_name<'a, T>(
+    items: &'a [T],
+    id_or_name: &str,
+    keys: impl Fn(&T) -> (&str, &str),
+) -> Option<&'a T> {
+    items
+        .iter()
+        .find(|i| keys(i).0 == id_or_name)
+        .or_else(|| items.iter().find(|i| keys(i).1 == id_or_name))
+}
+
+#[cfg(test)]
+mod headless_tests {
+    use crate::headless::HeadlessWorkspace;
+    use std::path::Path;
+    use yaak_models::models::{Folder, HttpRequest, Workspace, AUTHENTICATION_TYPE_INHERIT};
+    use yaak_models::queries::generate_id;
+    use yaak_sync::models::SyncModel;
+
+    fn write_model(path: &Path, model: SyncModel) {
+        let (contents, _) = model.to_file_contents(path).unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn loads_nested_sync_dirs() {
+        let dir = std::env::temp_dir().join(format!("yaak-headless-{}", generate_id()));
+        let workspace = Workspace {
+            id: "wk_1".to_string(),
+            name: "Workspace".to_string(),
+            ..Default::default()
+        };
+        let folder = Folder {
+            id: "fl_1".to_string(),
+            workspace_id: workspace.id.clone(),
+            name: "Folder".to_string(),
+            ..Default::default()
+        };
+        let request = |id: &str, folder_id: Option<&str>| HttpRequest {
+            id: id.to_string(),
+            workspace_id: workspace.id.clone(),
+            folder_id: folder_id.map(|id| id.to_string()),
+            ..Default::default()
+        };
+        write_model(&dir.join("yaak.wk_1.yaml"), SyncModel::Workspace(workspace.clone()));
+        write_model(&dir.join("Folder/yaak.fl_1.yaml"), SyncModel::Folder(folder.clone()));
+        write_model(
+            &dir.join("Folder/Nested/yaak.rq_2.yaml"),
+            SyncModel::HttpRequest(request("rq_2", Some("fl_1"))),
+        );
+        write_model(&dir.join("yaak.rq_1.yaml"), SyncModel::HttpRequest(request("rq_1", None)));
+        // Hidden directories, like a git checkout's, aren't part of the workspace
+        write_model(
+            &dir.join(".git/yaak.rq_3.yaml"),
+            SyncModel::HttpRequest(request("rq_3", None)),
+        );
+
+        let ws = HeadlessWorkspace::from_sync_dir(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(ws.workspace.id, "wk_1");
+        assert_eq!(ws.folders.len(), 1);
+        let mut ids: Vec<_> = ws.http_requests.iter().map(|r| r.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["rq_1", "rq_2"]);
+    }
+
+    #[test]
+    fn rejects_unsupported_authentication() {
+        let ws = HeadlessWorkspace {
+            workspace: Workspace {
+                authentication_type: Some("oauth2".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let request = |authentication_type: Option<&str>| HttpRequest {
+            name: "Request".to_string(),
+            authentication_type: authentication_type.map(|t| t.to_string()),
+            ..Default::default()
+        };
+
+        assert!(ws.check_authentication(&[request(None), request(Some("bearer"))]).is_ok());
+        assert_eq!(
+            ws.check_authentication(&[request(Some("bearer")), request(Some("apikey"))]),
+            Err("Request: Authentication apikey is not supported in headless mode".to_string())
+        );
+        // Inherited from the workspace
+        assert_eq!(
+            ws.check_authentication(&[request(Some(AUTHENTICATION_TYPE_INHERIT))]),
+            Err("Request: Authentication oauth2 is not supported in headless mode".to_string())
+        );
+    }
+}
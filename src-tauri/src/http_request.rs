@@ -1,56 +1,55 @@
-use crate::render::render_http_request;
-use crate::response_err;
-use http::header::{ACCEPT, USER_AGENT};
-use http::{HeaderMap, HeaderName, HeaderValue, Uri};
-use log::{debug, error, warn};
-use mime_guess::Mime;
-use reqwest::redirect::Policy;
-use reqwest::{multipart, Proxy, Url};
-use reqwest::{Method, Response};
-use rustls::crypto::ring;
-use rustls::ClientConfig;
-use rustls_platform_verifier::BuilderVerifierExt;
-use serde_json::Value;
-use std::collections::BTreeMap;
+use crate::render::render_http_request_with_layers;
+use crate::scripts::{
+    ensure_scripts_trusted, run_post_response_scripts, run_pre_request_scripts, RequestScripts,
+};
+use log::warn;
 use std::path::PathBuf;
-use std::str::FromStr;
-use std::sync::Arc;
-use std::time::Duration;
 use tauri::{Manager, Runtime, WebviewWindow};
-use tokio::fs;
-use tokio::fs::{create_dir_all, File};
-use tokio::io::AsyncWriteExt;
 use tokio::sync::watch::Receiver;
-use tokio::sync::{oneshot, Mutex};
+use yaak_http::client::HttpConnectionOptions;
+use yaak_http::error::Error::GenericError;
+use yaak_http::sender::HttpSendCallback;
 use yaak_models::models::{
-    Cookie, CookieJar, Environment, HttpRequest, HttpResponse, HttpResponseHeader,
-    HttpResponseState, ProxySetting, ProxySettingAuth,
+    CookieJar, Environment, EnvironmentVariable, HttpRequest, HttpResponse, HttpResponseEvent,
+    HttpResponseHeader, HttpResponseState,
 };
 use yaak_models::queries::{
-    get_base_environment, get_http_response, get_or_create_settings, get_workspace,
-    update_response_if_id, upsert_cookie_jar, UpdateSource,
+    get_base_environment, get_client_certificates, get_environment, get_or_create_settings,
+    get_workspace, list_folder_ancestors, update_response_if_id, upsert_cookie_jar,
+    upsert_environment, upsert_http_response_event, UpdateSource,
 };
+use yaak_models::render::Inherited;
 use yaak_plugins::events::{
     CallHttpAuthenticationRequest, HttpHeader, RenderPurpose, WindowContext,
 };
 use yaak_plugins::manager::PluginManager;
 use yaak_plugins::template_callback::PluginTemplateCallback;
 
+/// Where response and request bodies are stored
+pub fn responses_dir<R: Runtime>(mgr: &impl Manager<R>) -> PathBuf {
+    mgr.path().app_data_dir().unwrap().join("responses")
+}
+
 pub async fn send_http_request<R: Runtime>(
     window: &WebviewWindow<R>,
     unrendered_request: &HttpRequest,
     og_response: &HttpResponse,
     environment: Option<Environment>,
+    variables: Vec<EnvironmentVariable>,
     cookie_jar: Option<CookieJar>,
     cancelled_rx: &mut Receiver<bool>,
 ) -> Result<HttpResponse, String> {
-    let plugin_manager = window.state::<PluginManager>();
     let workspace = get_workspace(window, &unrendered_request.workspace_id)
         .await
         .expect("Failed to get Workspace");
     let base_environment = get_base_environment(window, &unrendered_request.workspace_id)
         .await
         .expect("Failed to get base environment");
+    let folders = list_folder_ancestors(window, unrendered_request.folder_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    let scripts = RequestScripts::new(&workspace, &folders, unrendered_request);
+    let inherited = Inherited::new(&workspace, &folders);
     let settings = get_or_create_settings(window).await;
     let cb = PluginTemplateCallback::new(
         window.app_handle(),
@@ -58,591 +57,212 @@ pub async fn send_http_request<R: Runtime>(
         RenderPurpose::Send,
     );
 
-    let response_id = og_response.id.clone();
-    let response = Arc::new(Mutex::new(og_response.clone()));
-
-    let request =
-        render_http_request(&unrendered_request, &base_environment, environment.as_ref(), &cb)
-            .await;
-
-    let mut url_string = request.url;
-
-    url_string = ensure_proto(&url_string);
-    if !url_string.starts_with("http://") && !url_string.starts_with("https://") {
-        url_string = format!("http://{}", url_string);
-    }
-    debug!("Sending request to {} {url_string}", request.method);
-
-    let mut client_builder = reqwest::Client::builder()
-        .redirect(match workspace.setting_follow_redirects {
-            true => Policy::limited(10), // TODO: Handle redirects natively
-            false => Policy::none(),
-        })
-        .connection_verbose(true)
-        .gzip(true)
-        .brotli(true)
-        .deflate(true)
-        .referer(false)
-        .tls_info(true);
-
-    if workspace.setting_validate_certificates {
-        // Use platform-native verifier to validate certificates
-        let arc_crypto_provider = Arc::new(ring::default_provider());
-        let config = ClientConfig::builder_with_provider(arc_crypto_provider)
-            .with_safe_default_protocol_versions()
-            .unwrap()
-            .with_platform_verifier()
-            .with_no_client_auth();
-        client_builder = client_builder.use_preconfigured_tls(config)
-    } else {
-        // Use rustls to skip validation because rustls_platform_verifier does not have this ability
-        client_builder = client_builder
-            .use_rustls_tls()
-            .danger_accept_invalid_hostnames(true)
-            .danger_accept_invalid_certs(true);
-    }
-
-    match settings.proxy {
-        Some(ProxySetting::Disabled) => client_builder = client_builder.no_proxy(),
-        Some(ProxySetting::Enabled { http, https, auth }) => {
-            debug!("Using proxy http={http} https={https}");
-            let mut proxy = Proxy::custom(move |url| {
-                let http = if http.is_empty() { None } else { Some(http.to_owned()) };
-                let https = if https.is_empty() { None } else { Some(https.to_owned()) };
-                let proxy_url = match (url.scheme(), http, https) {
-                    ("http", Some(proxy_url), _) => Some(proxy_url),
-                    ("https", _, Some(proxy_url)) => Some(proxy_url),
-                    _ => None,
-                };
-                proxy_url
-            });
-
-            if let Some(ProxySettingAuth { user, password }) = auth {
-                debug!("Using proxy auth");
-                proxy = proxy.basic_auth(user.as_str(), password.as_str());
-            }
-
-            client_builder = client_builder.proxy(proxy);
+    // Extra variables override the environments and folders, so a run can send the same request
+    // with each row of a data file
+    let mut layers = inherited.variable_layers(&base_environment, environment.as_ref());
+    layers.push("Run", variables);
+
+    // Variables set by scripts are saved to the active environment, or the base one without it
+    let script_environment_id = environment.as_ref().unwrap_or(&base_environment).id.clone();
+
+    if !scripts.is_empty() {
+        if let Err(e) = ensure_scripts_trusted(window, &workspace).await {
+            let mut response = og_response.clone();
+            response.state = HttpResponseState::Closed;
+            response.error = Some(e);
+            return update_response_if_id(window, &response, &UpdateSource::Window)
+                .await
+                .map_err(|e| e.to_string());
         }
-        None => {} // Nothing to do for this one, as it is the default
     }
 
-    // Add cookie store if specified
-    let maybe_cookie_manager = match cookie_jar.clone() {
-        Some(cj) => {
-            // HACK: Can't construct Cookie without serde, so we have to do this
-            let cookies = cj
-                .cookies
-                .iter()
-                .map(|cookie| {
-                    let json_cookie = serde_json::to_value(cookie).unwrap();
-                    serde_json::from_value(json_cookie).expect("Failed to deserialize cookie")
-                })
-                .map(|c| Ok(c))
-                .collect::<Vec<Result<_, ()>>>();
-
-            let store = reqwest_cookie_store::CookieStore::from_cookies(cookies, true)
-                .expect("Failed to create cookie store");
-            let cookie_store = reqwest_cookie_store::CookieStoreMutex::new(store);
-            let cookie_store = Arc::new(cookie_store);
-            client_builder = client_builder.cookie_provider(Arc::clone(&cookie_store));
-
-            Some((cookie_store, cj))
-        }
-        None => None,
-    };
-
-    if workspace.setting_request_timeout > 0 {
-        client_builder = client_builder.timeout(Duration::from_millis(
-            workspace.setting_request_timeout.unsigned_abs() as u64,
-        ));
-    }
-
-    let client = client_builder.build().expect("Failed to build client");
-
-    // Render query parameters
-    let mut query_params = Vec::new();
-    for p in request.url_parameters.clone() {
-        if !p.enabled || p.name.is_empty() {
-            continue;
-        }
-        query_params.push((p.name, p.value));
-    }
-
-    let uri = match Uri::from_str(url_string.as_str()) {
-        Ok(u) => u,
-        Err(e) => {
-            return Ok(response_err(
-                &*response.lock().await,
-                format!("Failed to parse URL \"{}\": {}", url_string, e.to_string()),
-                window,
-            )
-            .await);
-        }
-    };
-    // Yes, we're parsing both URI and URL because they could return different errors
-    let url = match Url::from_str(uri.to_string().as_str()) {
-        Ok(u) => u,
+    // Pre-request scripts run before rendering, so the variables they set are used to render it
+    let (unrendered_request, script_variables) = match run_pre_request_scripts(
+        window,
+        &scripts.pre_request,
+        unrendered_request,
+        layers.to_hashmap(),
+    )
+    .await
+    {
+        Ok(r) => r,
         Err(e) => {
-            return Ok(response_err(
-                &*response.lock().await,
-                format!("Failed to parse URL \"{}\": {}", url_string, e.to_string()),
-                window,
-            )
-            .await);
+            let mut response = og_response.clone();
+            response.state = HttpResponseState::Closed;
+            response.error = Some(format!("Pre-request script failed: {e}"));
+            return update_response_if_id(window, &response, &UpdateSource::Window)
+                .await
+                .map_err(|e| e.to_string());
         }
     };
+    save_environment_variables(window, &script_environment_id, script_variables.clone()).await;
+    layers.push("Pre-request scripts", script_variables);
 
-    let m = Method::from_bytes(request.method.to_uppercase().as_bytes())
-        .expect("Failed to create method");
-    let mut request_builder = client.request(m, url).query(&query_params);
-
-    let mut headers = HeaderMap::new();
-    headers.insert(USER_AGENT, HeaderValue::from_static("yaak"));
-    headers.insert(ACCEPT, HeaderValue::from_static("*/*"));
+    let request =
+        render_http_request_with_layers(&unrendered_request, &inherited, &layers, &cb).await;
 
-    // TODO: Set cookie header ourselves once we also handle redirects. We need to do this
-    //  because reqwest doesn't give us a way to inspect the headers it sent (we have to do
-    //  everything manually to know that).
-    // if let Some(cookie_store) = maybe_cookie_store.clone() {
-    //     let values1 = cookie_store.get_request_values(&url);
-    //     let raw_value = cookie_store.get_request_values(&url)
-    //         .map(|(name, value)| format!("{}={}", name, value))
-    //         .collect::<Vec<_>>()
-    //         .join("; ");
-    //     headers.insert(
-    //         COOKIE,
-    //         HeaderValue::from_str(&raw_value).expect("Failed to create cookie header"),
-    //     );
-    // }
+    let mut options = HttpConnectionOptions::new(&workspace, settings.proxy);
+    options.client_certificates =
+        get_client_certificates(window, &workspace).await.map_err(|e| e.to_string())?;
+    let send_cb = WindowSendCallback {
+        window: window.clone(),
+    };
 
-    for h in request.headers.clone() {
-        if h.name.is_empty() && h.value.is_empty() {
-            continue;
-        }
+    let response = yaak_http::sender::send_http_request(
+        &send_cb,
+        &request,
+        og_response,
+        &options,
+        cookie_jar,
+        cancelled_rx,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    save_extracted_values(window, &response).await;
+
+    // Post-response scripts only run for responses that finished
+    if scripts.post_response.is_empty() || response.error.is_some() || *cancelled_rx.borrow() {
+        return Ok(response);
+    }
+    let (script_variables, results) = run_post_response_scripts(
+        window,
+        &scripts.post_response,
+        &request,
+        &response,
+        layers.to_hashmap(),
+    )
+    .await;
+    save_environment_variables(window, &script_environment_id, script_variables).await;
+
+    let mut response = response;
+    response.assertion_results.extend(results);
+    update_response_if_id(window, &response, &UpdateSource::Window).await.map_err(|e| e.to_string())
+}
 
-        if !h.enabled {
+/// Write the values extracted from a response into the environments their extractors point
+/// to. Values without an environment are only used by the run that sent the request.
+async fn save_extracted_values<R: Runtime>(window: &WebviewWindow<R>, response: &HttpResponse) {
+    for extracted in &response.extracted_values {
+        let (Some(environment_id), Some(value)) =
+            (&extracted.extractor.environment_id, &extracted.value)
+        else {
             continue;
-        }
-
-        let header_name = match HeaderName::from_str(&h.name) {
-            Ok(n) => n,
-            Err(e) => {
-                error!("Failed to create header name: {}", e);
-                continue;
-            }
         };
-        let header_value = match HeaderValue::from_str(&h.value) {
-            Ok(n) => n,
-            Err(e) => {
-                error!("Failed to create header value: {}", e);
-                continue;
-            }
+        let variable = EnvironmentVariable {
+            enabled: true,
+            name: extracted.extractor.variable.to_string(),
+            value: value.to_string(),
+            id: None,
         };
-
-        headers.insert(header_name, header_value);
+        save_environment_variables(window, environment_id, vec![variable]).await;
     }
+}
 
-    let request_body = request.body.clone();
-    if let Some(body_type) = &request.body_type {
-        if body_type == "graphql" {
-            let query = get_str_h(&request_body, "query");
-            let variables = get_str_h(&request_body, "variables");
-            let body = if variables.trim().is_empty() {
-                format!(r#"{{"query":{}}}"#, serde_json::to_string(query).unwrap_or_default())
-            } else {
-                format!(
-                    r#"{{"query":{},"variables":{variables}}}"#,
-                    serde_json::to_string(query).unwrap_or_default()
-                )
-            };
-            request_builder = request_builder.body(body.to_owned());
-        } else if body_type == "application/x-www-form-urlencoded"
-            && request_body.contains_key("form")
-        {
-            let mut form_params = Vec::new();
-            let form = request_body.get("form");
-            if let Some(f) = form {
-                match f.as_array() {
-                    None => {}
-                    Some(a) => {
-                        for p in a {
-                            let enabled = get_bool(p, "enabled", true);
-                            let name = get_str(p, "name");
-                            if !enabled || name.is_empty() {
-                                continue;
-                            }
-                            let value = get_str(p, "value");
-                            form_params.push((name, value));
-                        }
-                    }
-                }
-            }
-            request_builder = request_builder.form(&form_params);
-        } else if body_type == "binary" && request_body.contains_key("filePath") {
-            let file_path = request_body
-                .get("filePath")
-                .ok_or("filePath not set")?
-                .as_str()
-                .unwrap_or_default();
-
-            match fs::read(file_path).await.map_err(|e| e.to_string()) {
-                Ok(f) => {
-                    request_builder = request_builder.body(f);
-                }
-                Err(e) => {
-                    return Ok(response_err(&*response.lock().await, e, window).await);
-                }
-            }
-        } else if body_type == "multipart/form-data" && request_body.contains_key("form") {
-            let mut multipart_form = multipart::Form::new();
-            if let Some(form_definition) = request_body.get("form") {
-                match form_definition.as_array() {
-                    None => {}
-                    Some(fd) => {
-                        for p in fd {
-                            let enabled = get_bool(p, "enabled", true);
-                            let name = get_str(p, "name").to_string();
-
-                            if !enabled || name.is_empty() {
-                                continue;
-                            }
-
-                            let file_path = get_str(p, "file").to_owned();
-                            let value = get_str(p, "value").to_owned();
-
-                            let mut part = if file_path.is_empty() {
-                                multipart::Part::text(value.clone())
-                            } else {
-                                match fs::read(file_path.clone()).await {
-                                    Ok(f) => multipart::Part::bytes(f),
-                                    Err(e) => {
-                                        return Ok(response_err(
-                                            &*response.lock().await,
-                                            e.to_string(),
-                                            window,
-                                        )
-                                        .await);
-                                    }
-                                }
-                            };
-
-                            let content_type = get_str(p, "contentType");
-
-                            // Set or guess mimetype
-                            if !content_type.is_empty() {
-                                part = match part.mime_str(content_type) {
-                                    Ok(p) => p,
-                                    Err(e) => {
-                                        return Ok(response_err(
-                                            &*response.lock().await,
-                                            format!("Invalid mime for multi-part entry {e:?}"),
-                                            window,
-                                        )
-                                        .await);
-                                    }
-                                };
-                            } else if !file_path.is_empty() {
-                                let default_mime =
-                                    Mime::from_str("application/octet-stream").unwrap();
-                                let mime =
-                                    mime_guess::from_path(file_path.clone()).first_or(default_mime);
-                                part = match part.mime_str(mime.essence_str()) {
-                                    Ok(p) => p,
-                                    Err(e) => {
-                                        return Ok(response_err(
-                                            &*response.lock().await,
-                                            format!("Invalid mime for multi-part entry {e:?}"),
-                                            window,
-                                        )
-                                        .await);
-                                    }
-                                };
-                            }
-
-                            // Set file path if not empty
-                            if !file_path.is_empty() {
-                                let filename = PathBuf::from(file_path)
-                                    .file_name()
-                                    .unwrap_or_default()
-                                    .to_string_lossy()
-                                    .to_string();
-                                part = part.file_name(filename);
-                            }
-
-                            multipart_form = multipart_form.part(name, part);
-                        }
-                    }
-                }
-            }
-            headers.remove("Content-Type"); // reqwest will add this automatically
-            request_builder = request_builder.multipart(multipart_form);
-        } else if request_body.contains_key("text") {
-            let body = get_str_h(&request_body, "text");
-            request_builder = request_builder.body(body.to_owned());
-        } else {
-            warn!("Unsupported body type: {}", body_type);
-        }
+/// Set variables in an environment, enabling them, and adding the ones it doesn't have yet
+async fn save_environment_variables<R: Runtime>(
+    window: &WebviewWindow<R>,
+    environment_id: &str,
+    variables: Vec<EnvironmentVariable>,
+) {
+    if variables.is_empty() {
+        return;
     }
-
-    // Add headers last, because previous steps may modify them
-    request_builder = request_builder.headers(headers);
-
-    let mut sendable_req = match request_builder.build() {
-        Ok(r) => r,
+    let mut environment = match get_environment(window, environment_id).await {
+        Ok(e) => e,
         Err(e) => {
-            warn!("Failed to build request builder {e:?}");
-            return Ok(response_err(&*response.lock().await, e.to_string(), window).await);
+            warn!("Failed to get environment to save variables to {e}");
+            return;
         }
     };
 
-    // Apply authentication
-
-    if let Some(auth_name) = request.authentication_type.to_owned() {
-        let req = CallHttpAuthenticationRequest {
-            context_id: format!("{:x}", md5::compute(request.id)),
-            values: serde_json::from_value(serde_json::to_value(&request.authentication).unwrap())
-                .unwrap(),
-            url: sendable_req.url().to_string(),
-            method: sendable_req.method().to_string(),
-            headers: sendable_req
-                .headers()
-                .iter()
-                .map(|(name, value)| HttpHeader {
-                    name: name.to_string(),
-                    value: value.to_str().unwrap_or_default().to_string(),
-                })
-                .collect(),
-        };
-        let auth_result = plugin_manager.call_http_authentication(window, &auth_name, req).await;
-        let plugin_result = match auth_result {
-            Ok(r) => r,
-            Err(e) => {
-                return Ok(response_err(&*response.lock().await, e.to_string(), window).await);
+    for variable in variables {
+        match environment.variables.iter_mut().find(|v| v.name == variable.name) {
+            Some(v) => {
+                v.value = variable.value;
+                v.enabled = true;
             }
-        };
-
-        let headers = sendable_req.headers_mut();
-        for header in plugin_result.set_headers {
-            headers.insert(
-                HeaderName::from_str(&header.name).unwrap(),
-                HeaderValue::from_str(&header.value).unwrap(),
-            );
+            None => environment.variables.push(variable),
         }
     }
+    if let Err(e) = upsert_environment(window, environment, &UpdateSource::Window).await {
+        warn!("Failed to save variables {e}");
+    }
+}
 
-    let (resp_tx, resp_rx) = oneshot::channel::<Result<Response, reqwest::Error>>();
-    let (done_tx, done_rx) = oneshot::channel::<HttpResponse>();
-
-    let start = std::time::Instant::now();
-
-    tokio::spawn(async move {
-        let _ = resp_tx.send(client.execute(sendable_req).await);
-    });
-
-    let raw_response = tokio::select! {
-        Ok(r) = resp_rx => r,
-        _ = cancelled_rx.changed() => {
-            debug!("Request cancelled");
-            return Ok(response_err(&*response.lock().await, "Request was cancelled".to_string(), window).await);
-        }
-    };
-
-    {
-        let window = window.clone();
-        let cancelled_rx = cancelled_rx.clone();
-        let response_id = response_id.clone();
-        let response = response.clone();
-        tokio::spawn(async move {
-            match raw_response {
-                Ok(mut v) => {
-                    let content_length = v.content_length();
-                    let response_headers = v.headers().clone();
-                    let dir = window.app_handle().path().app_data_dir().unwrap();
-                    let base_dir = dir.join("responses");
-                    create_dir_all(base_dir.clone()).await.expect("Failed to create responses dir");
-                    let body_path = if response_id.is_empty() {
-                        base_dir.join(uuid::Uuid::new_v4().to_string())
-                    } else {
-                        base_dir.join(response_id.clone())
-                    };
-
-                    {
-                        let mut r = response.lock().await;
-                        r.body_path = Some(body_path.to_str().unwrap().to_string());
-                        r.elapsed_headers = start.elapsed().as_millis() as i32;
-                        r.status = v.status().as_u16() as i32;
-                        r.status_reason = v.status().canonical_reason().map(|s| s.to_string());
-                        r.headers = response_headers
-                            .iter()
-                            .map(|(k, v)| HttpResponseHeader {
-                                name: k.as_str().to_string(),
-                                value: v.to_str().unwrap_or_default().to_string(),
-                            })
-                            .collect();
-                        r.url = v.url().to_string();
-                        r.remote_addr = v.remote_addr().map(|a| a.to_string());
-                        r.version = match v.version() {
-                            reqwest::Version::HTTP_09 => Some("HTTP/0.9".to_string()),
-                            reqwest::Version::HTTP_10 => Some("HTTP/1.0".to_string()),
-                            reqwest::Version::HTTP_11 => Some("HTTP/1.1".to_string()),
-                            reqwest::Version::HTTP_2 => Some("HTTP/2".to_string()),
-                            reqwest::Version::HTTP_3 => Some("HTTP/3".to_string()),
-                            _ => None,
-                        };
-
-                        r.state = HttpResponseState::Connected;
-                        update_response_if_id(&window, &r, &UpdateSource::Window)
-                            .await
-                            .expect("Failed to update response after connected");
-                    }
-
-                    // Write body to FS
-                    let mut f = File::options()
-                        .create(true)
-                        .truncate(true)
-                        .write(true)
-                        .open(&body_path)
-                        .await
-                        .expect("Failed to open file");
-
-                    let mut written_bytes: usize = 0;
-                    loop {
-                        let chunk = v.chunk().await;
-                        if *cancelled_rx.borrow() {
-                            // Request was canceled
-                            return;
-                        }
-                        match chunk {
-                            Ok(Some(bytes)) => {
-                                let mut r = response.lock().await;
-                                r.elapsed = start.elapsed().as_millis() as i32;
-                                f.write_all(&bytes).await.expect("Failed to write to file");
-                                f.flush().await.expect("Failed to flush file");
-                                written_bytes += bytes.len();
-                                r.content_length = Some(written_bytes as i32);
-                                update_response_if_id(&window, &r, &UpdateSource::Window)
-                                    .await
-                                    .expect("Failed to update response");
-                            }
-                            Ok(None) => {
-                                break;
-                            }
-                            Err(e) => {
-                                response_err(&*response.lock().await, e.to_string(), &window).await;
-                                break;
-                            }
-                        }
-                    }
-
-                    // Set final content length
-                    {
-                        let mut r = response.lock().await;
-                        r.content_length = match content_length {
-                            Some(l) => Some(l as i32),
-                            None => Some(written_bytes as i32),
-                        };
-                        r.state = HttpResponseState::Closed;
-                        update_response_if_id(&window, &r, &UpdateSource::Window)
-                            .await
-                            .expect("Failed to update response");
-                    };
-
-                    // Add cookie store if specified
-                    if let Some((cookie_store, mut cookie_jar)) = maybe_cookie_manager {
-                        // let cookies = response_headers.get_all(SET_COOKIE).iter().map(|h| {
-                        //     println!("RESPONSE COOKIE: {}", h.to_str().unwrap());
-                        //     cookie_store::RawCookie::from_str(h.to_str().unwrap())
-                        //         .expect("Failed to parse cookie")
-                        // });
-                        // store.store_response_cookies(cookies, &url);
-
-                        let json_cookies: Vec<Cookie> = cookie_store
-                            .lock()
-                            .unwrap()
-                            .iter_any()
-                            .map(|c| {
-                                let json_cookie =
-                                    serde_json::to_value(&c).expect("Failed to serialize cookie");
-                                serde_json::from_value(json_cookie)
-                                    .expect("Failed to deserialize cookie")
-                            })
-                            .collect::<Vec<_>>();
-                        cookie_jar.cookies = json_cookies;
-                        if let Err(e) =
-                            upsert_cookie_jar(&window, &cookie_jar, &UpdateSource::Window).await
-                        {
-                            error!("Failed to update cookie jar: {}", e);
-                        };
-                    }
-                }
-                Err(e) => {
-                    warn!("Failed to execute request {e}");
-                    response_err(&*response.lock().await, format!("{e} → {e:?}"), &window).await;
-                }
-            };
-
-            let r = response.lock().await.clone();
-            done_tx.send(r).unwrap();
-        });
-    };
+/// Persists responses to the database and calls auth plugins on behalf of a window
+struct WindowSendCallback<R: Runtime> {
+    window: WebviewWindow<R>,
+}
 
-    Ok(tokio::select! {
-        Ok(r) = done_rx => r,
-        _ = cancelled_rx.changed() => {
-            match get_http_response(window, response_id.as_str()).await {
-                Ok(mut r) => {
-                    r.state = HttpResponseState::Closed;
-                    update_response_if_id(&window, &r, &UpdateSource::Window).await.expect("Failed to update response")
-                },
-                _ => {
-                    response_err(&*response.lock().await, "Ephemeral request was cancelled".to_string(), &window).await
-                }.clone(),
-            }
+impl<R: Runtime> Clone for WindowSendCallback<R> {
+    fn clone(&self) -> Self {
+        Self {
+            window: self.window.clone(),
         }
-    })
+    }
 }
 
-fn ensure_proto(url_str: &str) -> String {
-    if url_str.starts_with("http://") || url_str.starts_with("https://") {
-        return url_str.to_string();
+impl<R: Runtime> HttpSendCallback for WindowSendCallback<R> {
+    async fn update_response(
+        &self,
+        response: &HttpResponse,
+    ) -> yaak_http::error::Result<HttpResponse> {
+        Ok(update_response_if_id(&self.window, response, &UpdateSource::Window).await?)
     }
 
-    // Url::from_str will fail without a proto, so add one
-    let parseable_url = format!("http://{}", url_str);
-    if let Ok(u) = Url::from_str(parseable_url.as_str()) {
-        match u.host() {
-            Some(host) => {
-                let h = host.to_string();
-                // These TLDs force HTTPS
-                if h.ends_with(".app") || h.ends_with(".dev") || h.ends_with(".page") {
-                    return format!("https://{url_str}");
-                }
-            }
-            None => {}
-        }
+    fn responses_dir(&self) -> PathBuf {
+        responses_dir(&self.window)
     }
 
-    format!("http://{url_str}")
-}
+    async fn authenticate(
+        &self,
+        request: &HttpRequest,
+        method: &str,
+        url: &str,
+        headers: Vec<HttpResponseHeader>,
+    ) -> yaak_http::error::Result<Vec<HttpResponseHeader>> {
+        let plugin_manager = self.window.state::<PluginManager>();
+        let auth_name = request.authentication_type.clone().unwrap_or_default();
+        let req = CallHttpAuthenticationRequest {
+            context_id: format!("{:x}", md5::compute(request.id.clone())),
+            values: serde_json::from_value(serde_json::to_value(&request.authentication)?)?,
+            url: url.to_string(),
+            method: method.to_string(),
+            headers: headers
+                .into_iter()
+                .map(|h| HttpHeader {
+                    name: h.name,
+                    value: h.value,
+                })
+                .collect(),
+        };
 
-fn get_bool(v: &Value, key: &str, fallback: bool) -> bool {
-    match v.get(key) {
-        None => fallback,
-        Some(v) => v.as_bool().unwrap_or(fallback),
+        let result = plugin_manager
+            .call_http_authentication(&self.window, &auth_name, req)
+            .await
+            .map_err(|e| GenericError(e.to_string()))?;
+
+        Ok(result
+            .set_headers
+            .into_iter()
+            .map(|h| HttpResponseHeader {
+                name: h.name,
+                value: h.value,
+            })
+            .collect())
     }
-}
 
-fn get_str<'a>(v: &'a Value, key: &str) -> &'a str {
-    match v.get(key) {
-        None => "",
-        Some(v) => v.as_str().unwrap_or_default(),
+    async fn update_cookie_jar(&self, cookie_jar: &CookieJar) -> yaak_http::error::Result<()> {
+        upsert_cookie_jar(&self.window, cookie_jar, &UpdateSource::Window).await?;
+        Ok(())
     }
-}
 
-fn get_str_h<'a>(v: &'a BTreeMap<String, Value>, key: &str) -> &'a str {
-    match v.get(key) {
-        None => "",
-        Some(v) => v.as_str().unwrap_or_default(),
+    async fn add_response_event(&self, event: &HttpResponseEvent) -> yaak_http::error::Result<()> {
+        // Ephemeral responses aren't persisted, so neither are their events
+        if !event.response_id.is_empty() {
+            upsert_http_response_event(&self.window, event, &UpdateSource::Window).await?;
+        }
+        Ok(())
     }
 }
@@ -1,14 +1,13 @@
 extern crate core;
 #[cfg(target_os = "macos")]
 extern crate objc;
-use crate::encoding::read_response_body;
+use crate::encoding::{read_response_body, response_charset};
 use crate::grpc::metadata_to_map;
-use crate::http_request::send_http_request;
+use crate::http_request::{responses_dir, send_http_request};
 use crate::notifications::YaakNotifier;
-use crate::render::{render_grpc_request, render_template};
+use crate::render::{render, render_grpc_request};
 use crate::updates::{UpdateMode, UpdateTrigger, YaakUpdater};
-use eventsource_client::{EventParser, SSE};
-use log::{debug, error, warn};
+use log::{debug, error, info, warn};
 use rand::random;
 use regex::Regex;
 use std::collections::{BTreeMap, HashMap};
@@ -28,30 +27,42 @@ use tokio::sync::Mutex;
 use tokio::task::block_in_place;
 use yaak_grpc::manager::{DynamicMessage, GrpcHandle};
 use yaak_grpc::{deserialize_message, serialize_message, Code, ServiceDefinition};
+use yaak_http::cookie_formats::{export_cookies, import_cookies, merge_cookies, CookieFormat};
+use yaak_http::cookies::store_cookie_from_cookie;
+use yaak_http::response_body::{hexdump_body, read_body_chunk, read_body_lines, search_body};
+use yaak_http::runner::{
+    format_report, http_requests_in_order, read_data_file, run_http_requests, ReportFormat,
+    RunOptions,
+};
 use yaak_models::models::{
-    CookieJar, Environment, EnvironmentVariable, Folder, GrpcConnection, GrpcConnectionState,
-    GrpcEvent, GrpcEventType, GrpcRequest, HttpRequest, HttpResponse, HttpResponseState, KeyValue,
-    ModelType, Plugin, Settings, WebsocketRequest, Workspace, WorkspaceMeta,
+    Cookie, CookieJar, Environment, EnvironmentVariable, Folder, GrpcConnection,
+    GrpcConnectionState, GrpcEvent, GrpcEventType, GrpcRequest, HttpRequest, HttpResponse,
+    HttpResponseBodyChunk, HttpResponseBodyHexdumpRow, HttpResponseBodyLines,
+    HttpResponseBodySearch, HttpResponseEvent, HttpResponseState, HttpRunReport, KeyValue,
+    ModelType, Plugin, Settings, TlsInfo, WebsocketRequest, Workspace, WorkspaceMeta,
 };
 use yaak_models::queries::{
     batch_upsert, cancel_pending_grpc_connections, cancel_pending_responses,
-    create_default_http_response, delete_all_grpc_connections,
-    delete_all_grpc_connections_for_workspace, delete_all_http_responses_for_request,
-    delete_all_http_responses_for_workspace, delete_all_websocket_connections_for_workspace,
-    delete_cookie_jar, delete_environment, delete_folder, delete_grpc_connection,
-    delete_grpc_request, delete_http_request, delete_http_response, delete_plugin,
-    delete_workspace, duplicate_folder, duplicate_grpc_request, duplicate_http_request,
-    ensure_base_environment, generate_model_id, get_base_environment, get_cookie_jar,
-    get_environment, get_folder, get_grpc_connection, get_grpc_request, get_http_request,
-    get_http_response, get_key_value_raw, get_or_create_settings, get_or_create_workspace_meta,
-    get_plugin, get_workspace, get_workspace_export_resources, list_cookie_jars, list_environments,
+    cancel_pending_websocket_connections, create_default_http_response,
+    delete_all_grpc_connections, delete_all_grpc_connections_for_workspace,
+    delete_all_http_responses_for_request, delete_all_http_responses_for_workspace,
+    delete_all_websocket_connections_for_workspace, delete_cookie_jar, delete_environment,
+    delete_folder, delete_grpc_connection, delete_grpc_request, delete_http_request,
+    delete_http_response, delete_plugin, delete_workspace, duplicate_folder,
+    duplicate_grpc_request, duplicate_http_request, ensure_base_environment, generate_model_id,
+    get_base_environment, get_client_certificates, get_cookie_jar, get_environment, get_folder,
+    get_grpc_connection, get_grpc_request, get_http_request, get_http_response, get_inherited,
+    get_key_value_raw, get_or_create_settings, get_or_create_workspace_meta, get_plugin,
+    get_workspace, get_workspace_export_resources, list_cookie_jars, list_environments,
     list_folders, list_grpc_connections_for_workspace, list_grpc_events, list_grpc_requests,
-    list_http_requests, list_http_responses_for_workspace, list_key_values_raw, list_plugins,
-    list_workspaces, set_key_value_raw, update_response_if_id, update_settings, upsert_cookie_jar,
-    upsert_environment, upsert_folder, upsert_grpc_connection, upsert_grpc_event,
-    upsert_grpc_request, upsert_http_request, upsert_plugin, upsert_workspace,
+    list_http_requests, list_http_response_events, list_http_responses_for_workspace,
+    list_key_values_raw, list_plugins, list_workspaces, set_key_value_raw, update_settings,
+    upsert_cookie_jar, upsert_environment, upsert_folder, upsert_grpc_connection,
+    upsert_grpc_event, upsert_grpc_request, upsert_http_request, upsert_plugin, upsert_workspace,
     upsert_workspace_meta, BatchUpsertResult, UpdateSource,
 };
+use yaak_models::render::ResolvedVariable;
+use yaak_models::retention::{collect_garbage, GarbageCollectionReport};
 use yaak_plugins::events::{
     BootResponse, CallHttpAuthenticationRequest, CallHttpRequestActionRequest, FilterResponse,
     GetHttpAuthenticationConfigResponse, GetHttpAuthenticationSummaryResponse,
@@ -60,17 +71,21 @@ use yaak_plugins::events::{
 };
 use yaak_plugins::manager::PluginManager;
 use yaak_plugins::template_callback::PluginTemplateCallback;
-use yaak_sse::sse::ServerSentEvent;
+use yaak_sse::parser::EventStreamParser;
 use yaak_templates::format::format_json;
 use yaak_templates::{Parser, Tokens};
+use yaak_tls::tls_info::TlsRecorder;
+use yaak_tls::TlsOptions;
 
 mod encoding;
 mod grpc;
+pub mod headless;
 mod history;
 mod http_request;
 mod notifications;
 mod plugin_events;
 mod render;
+mod scripts;
 #[cfg(target_os = "macos")]
 mod tauri_plugin_mac_window;
 mod updates;
@@ -125,6 +140,7 @@ async fn cmd_render_template<R: Runtime>(
     app_handle: AppHandle<R>,
     template: &str,
     workspace_id: &str,
+    folder_id: Option<&str>,
     environment_id: Option<&str>,
 ) -> Result<String, String> {
     let environment = match environment_id {
@@ -133,10 +149,12 @@ async fn cmd_render_template<R: Runtime>(
     };
     let base_environment =
         get_base_environment(&window, &workspace_id).await.map_err(|e| e.to_string())?;
-    let rendered = render_template(
+    let inherited =
+        get_inherited(&window, workspace_id, folder_id).await.map_err(|e| e.to_string())?;
+    let vars = inherited.variable_layers(&base_environment, environment.as_ref()).to_hashmap();
+    let rendered = render(
         template,
-        &base_environment,
-        environment.as_ref(),
+        &vars,
         &PluginTemplateCallback::new(
             &app_handle,
             &WindowContext::from_window(&window),
@@ -147,6 +165,39 @@ async fn cmd_render_template<R: Runtime>(
     Ok(rendered)
 }
 
+#[tauri::command]
+async fn cmd_render_variables<R: Runtime>(
+    window: WebviewWindow<R>,
+    app_handle: AppHandle<R>,
+    workspace_id: &str,
+    folder_id: Option<&str>,
+    environment_id: Option<&str>,
+) -> Result<Vec<ResolvedVariable>, String> {
+    let environment = match environment_id {
+        Some(id) => Some(get_environment(&window, id).await.map_err(|e| e.to_string())?),
+        None => None,
+    };
+    let base_environment =
+        get_base_environment(&window, workspace_id).await.map_err(|e| e.to_string())?;
+    let inherited =
+        get_inherited(&window, workspace_id, folder_id).await.map_err(|e| e.to_string())?;
+    let layers = inherited.variable_layers(&base_environment, environment.as_ref());
+    let cb = PluginTemplateCallback::new(
+        &app_handle,
+        &WindowContext::from_window(&window),
+        RenderPurpose::Preview,
+    );
+
+    // Values can reference other variables, so show what they render to
+    let vars = layers.to_hashmap();
+    let mut resolved = Vec::new();
+    for v in layers.resolve() {
+        let value = render(&v.value, &vars, &cb).await;
+        resolved.push(ResolvedVariable { value, ..v });
+    }
+    Ok(resolved)
+}
+
 #[tauri::command]
 async fn cmd_dismiss_notification<R: Runtime>(
     window: WebviewWindow<R>,
@@ -169,6 +220,15 @@ async fn cmd_grpc_reflect<R: Runtime>(
         .ok_or("Failed to find GRPC request")?;
 
     let uri = safe_uri(&req.url);
+    let workspace = get_workspace(&window, &req.workspace_id).await.map_err(|e| e.to_string())?;
+    let client_certificates =
+        get_client_certificates(&window, &workspace).await.map_err(|e| e.to_string())?;
+    let tls_options = TlsOptions::for_url(
+        &client_certificates,
+        &workspace.setting_ca_certificates,
+        &workspace.setting_certificate_pins,
+        &uri,
+    );
 
     grpc_handle
         .lock()
@@ -177,6 +237,7 @@ async fn cmd_grpc_reflect<R: Runtime>(
             &req.id,
             &uri,
             &proto_files.iter().map(|p| PathBuf::from_str(p).unwrap()).collect(),
+            &tls_options,
         )
         .await
 }
@@ -201,8 +262,18 @@ async fn cmd_grpc_go<R: Runtime>(
     let base_environment = get_base_environment(&window, &unrendered_request.workspace_id)
         .await
         .map_err(|e| e.to_string())?;
+    let inherited = get_inherited(
+        &window,
+        &unrendered_request.workspace_id,
+        unrendered_request.folder_id.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    // Messages are rendered with the same variables as the request, as they're sent
+    let vars = inherited.variable_layers(&base_environment, environment.as_ref()).to_hashmap();
     let request = render_grpc_request(
         &unrendered_request,
+        &inherited,
         &base_environment,
         environment.as_ref(),
         &PluginTemplateCallback::new(
@@ -284,6 +355,16 @@ async fn cmd_grpc_go<R: Runtime>(
     let (cancelled_tx, mut cancelled_rx) = tokio::sync::watch::channel(false);
 
     let uri = safe_uri(&request.url);
+    let workspace =
+        get_workspace(&window, &request.workspace_id).await.map_err(|e| e.to_string())?;
+    let client_certificates =
+        get_client_certificates(&window, &workspace).await.map_err(|e| e.to_string())?;
+    let tls_options = TlsOptions::for_url(
+        &client_certificates,
+        &workspace.setting_ca_certificates,
+        &workspace.setting_certificate_pins,
+        &uri,
+    );
 
     let in_msg_stream = tokio_stream::wrappers::ReceiverStream::new(in_msg_rx);
 
@@ -303,6 +384,7 @@ async fn cmd_grpc_go<R: Runtime>(
             &request.clone().id,
             uri.as_str(),
             &proto_files.iter().map(|p| PathBuf::from_str(p).unwrap()).collect(),
+            &tls_options,
         )
         .await;
 
@@ -315,6 +397,7 @@ async fn cmd_grpc_go<R: Runtime>(
                     elapsed: start.elapsed().as_millis() as i32,
                     error: Some(err.clone()),
                     state: GrpcConnectionState::Closed,
+                    tls: grpc_tls_info(&tls_options.recorder),
                     ..conn.clone()
                 },
                 &UpdateSource::Window,
@@ -337,8 +420,7 @@ async fn cmd_grpc_go<R: Runtime>(
     let cb = {
         let cancelled_rx = cancelled_rx.clone();
         let window = window.clone();
-        let workspace = base_environment.clone();
-        let environment = environment.clone();
+        let vars = vars.clone();
         let base_msg = base_msg.clone();
         let method_desc = method_desc.clone();
 
@@ -365,10 +447,9 @@ async fn cmd_grpc_go<R: Runtime>(
                     let msg = {
                         block_in_place(|| {
                             tauri::async_runtime::block_on(async {
-                                render_template(
+                                render(
                                     msg.as_str(),
-                                    &workspace,
-                                    environment.as_ref(),
+                                    &vars,
                                     &PluginTemplateCallback::new(
                                         window.app_handle(),
                                         &WindowContext::from_window(&window),
@@ -433,10 +514,9 @@ async fn cmd_grpc_go<R: Runtime>(
         let base_event = base_msg.clone();
         let req = request.clone();
         let msg = if req.message.is_empty() { "{}".to_string() } else { req.message };
-        let msg = render_template(
+        let msg = render(
             msg.as_str(),
-            &base_environment.clone(),
-            environment.as_ref(),
+            &vars,
             &PluginTemplateCallback::new(
                 window.app_handle(),
                 &WindowContext::from_window(&window),
@@ -678,6 +758,7 @@ async fn cmd_grpc_go<R: Runtime>(
 
     {
         let conn_id = conn_id.clone();
+        let tls_recorder = tls_options.recorder.clone();
         tauri::async_runtime::spawn(async move {
             let w = window.clone();
             tokio::select! {
@@ -695,6 +776,7 @@ async fn cmd_grpc_go<R: Runtime>(
                             elapsed: start.elapsed().as_millis() as i32,
                             status: closed_status,
                             state: GrpcConnectionState::Closed,
+                            tls: grpc_tls_info(&tls_recorder),
                             ..get_grpc_connection(&w, &conn_id).await.unwrap().clone()
                         },
                         &UpdateSource::Window,
@@ -717,6 +799,7 @@ async fn cmd_grpc_go<R: Runtime>(
                             elapsed: start.elapsed().as_millis() as i32,
                             status: Code::Cancelled as i32,
                             state: GrpcConnectionState::Closed,
+                            tls: grpc_tls_info(&tls_recorder),
                             ..get_grpc_connection(&w, &conn_id).await.unwrap().clone()
                         },
                         &UpdateSource::Window,
@@ -757,7 +840,16 @@ async fn cmd_send_ephemeral_request(
         }
     });
 
-    send_http_request(&window, &request, &response, environment, cookie_jar, &mut cancel_rx).await
+    send_http_request(
+        &window,
+        &request,
+        &response,
+        environment,
+        Vec::new(),
+        cookie_jar,
+        &mut cancel_rx,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -775,9 +867,10 @@ async fn cmd_filter_response<R: Runtime>(
     let response =
         get_http_response(&window, response_id).await.expect("Failed to get http response");
 
-    if let None = response.body_path {
-        return Err("Response body path not set".to_string());
-    }
+    let body = match read_response_body(&window, &response).await.map_err(|e| e.to_string())? {
+        None => return Err("Response body path not set".to_string()),
+        Some(b) => b.text,
+    };
 
     let mut content_type = "".to_string();
     for header in response.headers.iter() {
@@ -787,34 +880,55 @@ async fn cmd_filter_response<R: Runtime>(
         }
     }
 
-    let body = read_response_body(response).await.unwrap();
-
-    // TODO: Have plugins register their own content type (regex?)
-    plugin_manager
-        .filter_data(&window, filter, &body, &content_type)
+    // Plugins get first pick of the content types they registered for
+    let plugin = plugin_manager
+        .get_filter_plugin(&window, &content_type)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    if let Some(plugin) = plugin {
+        return plugin_manager
+            .filter_data(&window, &plugin, filter, &body, &content_type)
+            .await
+            .map_err(|e| e.to_string());
+    }
+
+    let content = yaak_filter::filter(&body, filter, &content_type).map_err(|e| e.to_string())?;
+    Ok(FilterResponse { content })
 }
 
 #[tauri::command]
-async fn cmd_get_sse_events(file_path: &str) -> Result<Vec<ServerSentEvent>, String> {
-    let body = fs::read(file_path).map_err(|e| e.to_string())?;
-    let mut event_parser = EventParser::new();
-    event_parser.process_bytes(body.into()).map_err(|e| e.to_string())?;
-
-    let mut events = Vec::new();
-    while let Some(e) = event_parser.get_event() {
-        if let SSE::Event(e) = e {
-            events.push(ServerSentEvent {
-                event_type: e.event_type,
-                data: e.data,
-                id: e.id,
-                retry: e.retry,
-            });
-        }
+async fn cmd_list_http_response_events(
+    response_id: &str,
+    window: WebviewWindow,
+) -> Result<Vec<HttpResponseEvent>, String> {
+    let events =
+        list_http_response_events(&window, response_id).await.map_err(|e| e.to_string())?;
+    if !events.is_empty() {
+        return Ok(events);
     }
 
-    Ok(events)
+    // Responses from before events were stored as they arrived only have the body to go on
+    let response = get_http_response(&window, response_id).await.map_err(|e| e.to_string())?;
+    let body = match (&response.state, &response.body_path) {
+        (HttpResponseState::Closed, Some(p)) => fs::read(p).map_err(|e| e.to_string())?,
+        _ => return Ok(Vec::new()),
+    };
+    let mut parser = EventStreamParser::new();
+    Ok(parser
+        .feed(&body)
+        .into_iter()
+        .map(|e| HttpResponseEvent {
+            model: "http_response_event".to_string(),
+            workspace_id: response.workspace_id.clone(),
+            request_id: response.request_id.clone(),
+            response_id: response.id.clone(),
+            event_type: e.event_type,
+            data: e.data,
+            event_id: e.id,
+            retry: e.retry.and_then(|r| i32::try_from(r).ok()),
+            ..Default::default()
+        })
+        .collect())
 }
 
 #[tauri::command]
@@ -1058,6 +1172,71 @@ async fn cmd_export_data(
     Ok(())
 }
 
+#[tauri::command]
+async fn cmd_read_response_body(
+    window: WebviewWindow,
+    response_id: &str,
+) -> Result<Option<String>, String> {
+    let response = get_http_response(&window, response_id).await.map_err(|e| e.to_string())?;
+    let body = read_response_body(&window, &response).await.map_err(|e| e.to_string())?;
+
+    // Bodies that aren't valid in their charset are most likely binary
+    Ok(body.filter(|b| !b.had_errors).map(|b| b.text))
+}
+
+#[tauri::command]
+async fn cmd_read_response_body_chunk(
+    window: WebviewWindow,
+    response_id: &str,
+    offset: u64,
+    length: u64,
+) -> Result<HttpResponseBodyChunk, String> {
+    let response = get_http_response(&window, response_id).await.map_err(|e| e.to_string())?;
+    let charset = response_charset(&window, &response).await.map_err(|e| e.to_string())?;
+    read_body_chunk(&response, charset.as_deref(), offset, length).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_read_response_body_lines(
+    window: WebviewWindow,
+    response_id: &str,
+    start_line: u64,
+    line_count: u64,
+) -> Result<HttpResponseBodyLines, String> {
+    let response = get_http_response(&window, response_id).await.map_err(|e| e.to_string())?;
+    let charset = response_charset(&window, &response).await.map_err(|e| e.to_string())?;
+    read_body_lines(&response, charset.as_deref(), start_line, line_count)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_search_response_body(
+    window: WebviewWindow,
+    response_id: &str,
+    query: &str,
+    case_sensitive: bool,
+    offset: u64,
+    line: u64,
+) -> Result<HttpResponseBodySearch, String> {
+    let response = get_http_response(&window, response_id).await.map_err(|e| e.to_string())?;
+    let charset = response_charset(&window, &response).await.map_err(|e| e.to_string())?;
+    search_body(&response, charset.as_deref(), query, case_sensitive, offset, line)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_hexdump_response_body(
+    window: WebviewWindow,
+    response_id: &str,
+    offset: u64,
+    length: u64,
+) -> Result<Vec<HttpResponseBodyHexdumpRow>, String> {
+    let response = get_http_response(&window, response_id).await.map_err(|e| e.to_string())?;
+    hexdump_body(&response, offset, length).await.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn cmd_save_response(
     window: WebviewWindow,
@@ -1115,22 +1294,106 @@ async fn cmd_send_http_request(
         None => None,
     };
 
-    send_http_request(&window, &request, &response, environment, cookie_jar, &mut cancel_rx).await
+    send_http_request(
+        &window,
+        &request,
+        &response,
+        environment,
+        Vec::new(),
+        cookie_jar,
+        &mut cancel_rx,
+    )
+    .await
 }
 
-async fn response_err<R: Runtime>(
-    response: &HttpResponse,
-    error: String,
-    w: &WebviewWindow<R>,
-) -> HttpResponse {
-    warn!("Failed to send request: {error:?}");
-    let mut response = response.clone();
-    response.state = HttpResponseState::Closed;
-    response.error = Some(error.clone());
-    response = update_response_if_id(w, &response, &UpdateSource::Window)
-        .await
-        .expect("Failed to update response");
-    response
+#[tauri::command]
+async fn cmd_run_http_requests(
+    window: WebviewWindow,
+    workspace_id: &str,
+    folder_id: Option<&str>,
+    environment_id: Option<&str>,
+    cookie_jar_id: Option<&str>,
+    stop_on_failure: bool,
+    delay: u64,
+    data_file: Option<&str>,
+) -> Result<HttpRunReport, String> {
+    let workspace = get_workspace(&window, workspace_id).await.map_err(|e| e.to_string())?;
+    let folders = list_folders(&window, workspace_id).await.map_err(|e| e.to_string())?;
+    let http_requests =
+        list_http_requests(&window, workspace_id).await.map_err(|e| e.to_string())?;
+    let name = match folder_id {
+        Some(id) => get_folder(&window, id).await.map_err(|e| e.to_string())?.name,
+        None => workspace.name,
+    };
+    let requests = http_requests_in_order(&folders, &http_requests, folder_id);
+    let options = RunOptions {
+        stop_on_failure,
+        delay: Duration::from_millis(delay),
+        data: match data_file {
+            Some(path) => read_data_file(&PathBuf::from(path))?,
+            None => Vec::new(),
+        },
+    };
+    let report = run_http_requests(requests, &options, |request, variables| {
+        let window = window.clone();
+        async move {
+            // Fetched for every request, since the previous one may have updated them
+            let environment = match environment_id {
+                Some(id) => Some(get_environment(&window, id).await.map_err(|e| e.to_string())?),
+                None => None,
+            };
+            let cookie_jar = match cookie_jar_id {
+                Some(id) => Some(get_cookie_jar(&window, id).await.map_err(|e| e.to_string())?),
+                None => None,
+            };
+            let response =
+                create_default_http_response(&window, &request.id, &UpdateSource::Window)
+                    .await
+                    .map_err(|e| e.to_string())?;
+
+            let (cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+            let cancel_event_id =
+                window.listen_any(format!("cancel_http_response_{}", response.id), move |_event| {
+                    if let Err(e) = cancel_tx.send(true) {
+                        warn!("Failed to send cancel event for request {e:?}");
+                    }
+                });
+
+            let result = send_http_request(
+                &window,
+                &request,
+                &response,
+                environment,
+                variables,
+                cookie_jar,
+                &mut cancel_rx,
+            )
+            .await;
+
+            // A run can send a lot of requests, so don't leave a listener behind for each
+            window.unlisten(cancel_event_id);
+            result
+        }
+    })
+    .await;
+
+    Ok(HttpRunReport {
+        name,
+        workspace_id: workspace_id.to_string(),
+        folder_id: folder_id.map(|id| id.to_string()),
+        environment_id: environment_id.map(|id| id.to_string()),
+        ..report
+    })
+}
+
+#[tauri::command]
+async fn cmd_save_run_report(
+    report: HttpRunReport,
+    format: &str,
+    filepath: &str,
+) -> Result<(), String> {
+    let contents = format_report(&report, ReportFormat::from_str(format)?)?;
+    fs::write(filepath, contents).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -1213,6 +1476,84 @@ async fn cmd_update_cookie_jar(
     upsert_cookie_jar(&w, &cookie_jar, &UpdateSource::Window).await.map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+async fn cmd_add_cookie(
+    cookie_jar_id: &str,
+    cookie: Cookie,
+    w: WebviewWindow,
+) -> Result<CookieJar, String> {
+    store_cookie_from_cookie(&cookie).map_err(|e| e.to_string())?;
+    let mut cookie_jar = get_cookie_jar(&w, cookie_jar_id).await.map_err(|e| e.to_string())?;
+
+    merge_cookies(&mut cookie_jar.cookies, vec![cookie]);
+
+    upsert_cookie_jar(&w, &cookie_jar, &UpdateSource::Window).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_update_cookie(
+    cookie_jar_id: &str,
+    old_cookie: Cookie,
+    cookie: Cookie,
+    w: WebviewWindow,
+) -> Result<CookieJar, String> {
+    store_cookie_from_cookie(&cookie).map_err(|e| e.to_string())?;
+    let mut cookie_jar = get_cookie_jar(&w, cookie_jar_id).await.map_err(|e| e.to_string())?;
+
+    // Editing the name, domain, or path must not leave a duplicate behind
+    if !cookie.same_key(&old_cookie) {
+        cookie_jar.cookies.retain(|c| !c.same_key(&cookie));
+    }
+
+    match cookie_jar.cookies.iter_mut().find(|c| c.same_key(&old_cookie)) {
+        Some(existing) => *existing = cookie,
+        None => return Err(format!("Cookie {} not found in jar", old_cookie.name)),
+    }
+
+    upsert_cookie_jar(&w, &cookie_jar, &UpdateSource::Window).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_delete_cookie(
+    cookie_jar_id: &str,
+    cookie: Cookie,
+    w: WebviewWindow,
+) -> Result<CookieJar, String> {
+    let mut cookie_jar = get_cookie_jar(&w, cookie_jar_id).await.map_err(|e| e.to_string())?;
+    cookie_jar.cookies.retain(|c| !c.same_key(&cookie));
+    upsert_cookie_jar(&w, &cookie_jar, &UpdateSource::Window).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_import_cookies(
+    cookie_jar_id: &str,
+    file_path: &str,
+    format: CookieFormat,
+    w: WebviewWindow,
+) -> Result<CookieJar, String> {
+    let contents = read_to_string(file_path).await.map_err(|e| e.to_string())?;
+    let cookies = import_cookies(&contents, format).map_err(|e| e.to_string())?;
+    for cookie in &cookies {
+        store_cookie_from_cookie(cookie).map_err(|e| e.to_string())?;
+    }
+
+    let mut cookie_jar = get_cookie_jar(&w, cookie_jar_id).await.map_err(|e| e.to_string())?;
+    merge_cookies(&mut cookie_jar.cookies, cookies);
+    upsert_cookie_jar(&w, &cookie_jar, &UpdateSource::Window).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cmd_export_cookies(
+    cookie_jar_id: &str,
+    file_path: &str,
+    format: CookieFormat,
+    w: WebviewWindow,
+) -> Result<(), String> {
+    let cookie_jar = get_cookie_jar(&w, cookie_jar_id).await.map_err(|e| e.to_string())?;
+    let contents = export_cookies(&cookie_jar.cookies, format).map_err(|e| e.to_string())?;
+    fs::write(file_path, contents).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn cmd_delete_cookie_jar(w: WebviewWindow, cookie_jar_id: &str) -> Result<CookieJar, String> {
     delete_cookie_jar(&w, cookie_jar_id, &UpdateSource::Window).await.map_err(|e| e.to_string())
@@ -1588,6 +1929,11 @@ async fn cmd_delete_send_history(workspace_id: &str, window: WebviewWindow) -> R
     Ok(())
 }
 
+#[tauri::command]
+async fn cmd_collect_garbage(window: WebviewWindow) -> Result<GarbageCollectionReport, String> {
+    collect_garbage(&window, &responses_dir(&window)).await.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn cmd_delete_all_http_responses(
     request_id: &str,
@@ -1602,18 +1948,10 @@ async fn cmd_delete_all_http_responses(
 async fn cmd_list_workspaces(window: WebviewWindow) -> Result<Vec<Workspace>, String> {
     let workspaces = list_workspaces(&window).await.expect("Failed to find workspaces");
     if workspaces.is_empty() {
-        let workspace = upsert_workspace(
-            &window,
-            Workspace {
-                name: "Yaak".to_string(),
-                setting_follow_redirects: true,
-                setting_validate_certificates: true,
-                ..Default::default()
-            },
-            &UpdateSource::Window,
-        )
-        .await
-        .expect("Failed to create Workspace");
+        let workspace =
+            upsert_workspace(&window, Workspace::new("Yaak".to_string()), &UpdateSource::Window)
+                .await
+                .expect("Failed to create Workspace");
         Ok(vec![workspace])
     } else {
         Ok(workspaces)
@@ -1740,7 +2078,6 @@ pub fn run() {
                 ])
                 .level_for("plugin_runtime", log::LevelFilter::Info)
                 .level_for("cookie_store", log::LevelFilter::Info)
-                .level_for("eventsource_client::event_parser", log::LevelFilter::Info)
                 .level_for("h2", log::LevelFilter::Info)
                 .level_for("hyper", log::LevelFilter::Info)
                 .level_for("hyper_util", log::LevelFilter::Info)
@@ -1767,7 +2104,6 @@ pub fn run() {
                 ])
                 .level_for("plugin_runtime", log::LevelFilter::Info)
                 .level_for("cookie_store", log::LevelFilter::Info)
-                .level_for("eventsource_client::event_parser", log::LevelFilter::Info)
                 .level_for("h2", log::LevelFilter::Info)
                 .level_for("hyper", log::LevelFilter::Info)
                 .level_for("hyper_util", log::LevelFilter::Info)
@@ -1835,9 +2171,11 @@ pub fn run() {
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
+            cmd_add_cookie,
             cmd_call_http_authentication_action,
             cmd_call_http_request_action,
             cmd_check_for_updates,
+            cmd_collect_garbage,
             cmd_create_cookie_jar,
             cmd_create_environment,
             cmd_create_grpc_request,
@@ -1845,6 +2183,7 @@ pub fn run() {
             cmd_curl_to_request,
             cmd_delete_all_grpc_connections,
             cmd_delete_all_http_responses,
+            cmd_delete_cookie,
             cmd_delete_cookie_jar,
             cmd_delete_environment,
             cmd_delete_folder,
@@ -1858,6 +2197,7 @@ pub fn run() {
             cmd_duplicate_folder,
             cmd_duplicate_grpc_request,
             cmd_duplicate_http_request,
+            cmd_export_cookies,
             cmd_export_data,
             cmd_filter_response,
             cmd_format_json,
@@ -1870,12 +2210,13 @@ pub fn run() {
             cmd_get_http_request,
             cmd_get_key_value,
             cmd_get_settings,
-            cmd_get_sse_events,
             cmd_get_workspace,
             cmd_get_workspace_meta,
             cmd_grpc_go,
             cmd_grpc_reflect,
+            cmd_hexdump_response_body,
             cmd_http_request_actions,
+            cmd_import_cookies,
             cmd_import_data,
             cmd_install_plugin,
             cmd_list_cookie_jars,
@@ -1886,6 +2227,7 @@ pub fn run() {
             cmd_list_grpc_requests,
             cmd_list_key_values,
             cmd_list_http_requests,
+            cmd_list_http_response_events,
             cmd_list_http_responses,
             cmd_list_plugins,
             cmd_list_workspaces,
@@ -1894,9 +2236,16 @@ pub fn run() {
             cmd_new_main_window,
             cmd_parse_template,
             cmd_plugin_info,
+            cmd_read_response_body,
+            cmd_read_response_body_chunk,
+            cmd_read_response_body_lines,
             cmd_reload_plugins,
             cmd_render_template,
+            cmd_render_variables,
+            cmd_run_http_requests,
             cmd_save_response,
+            cmd_save_run_report,
+            cmd_search_response_body,
             cmd_send_ephemeral_request,
             cmd_send_http_request,
             cmd_set_key_value,
@@ -1904,6 +2253,7 @@ pub fn run() {
             cmd_template_functions,
             cmd_template_tokens_to_string,
             cmd_uninstall_plugin,
+            cmd_update_cookie,
             cmd_update_cookie_jar,
             cmd_update_environment,
             cmd_update_folder,
@@ -1924,8 +2274,9 @@ pub fn run() {
             match event {
                 RunEvent::Ready => {
                     let w = create_main_window(app_handle, "/");
+                    let launch_window = w.clone();
                     tauri::async_runtime::spawn(async move {
-                        let info = history::store_launch_history(&w).await;
+                        let info = history::store_launch_history(&launch_window).await;
                         debug!("Launched Yaak {:?}", info);
                     });
 
@@ -1934,6 +2285,15 @@ pub fn run() {
                     tauri::async_runtime::block_on(async move {
                         let _ = cancel_pending_responses(&h).await;
                         let _ = cancel_pending_grpc_connections(&h).await;
+                        let _ = cancel_pending_websocket_connections(&h).await;
+                    });
+
+                    // Clean up history once the pending requests are out of the way
+                    tauri::async_runtime::spawn(async move {
+                        match collect_garbage(&w, &responses_dir(&w)).await {
+                            Ok(report) => info!("Collected garbage {:?}", report),
+                            Err(e) => warn!("Failed to collect garbage: {}", e),
+                        }
                     });
                 }
                 RunEvent::WindowEvent {
@@ -2034,6 +2394,14 @@ fn safe_uri(endpoint: &str) -> String {
     }
 }
 
+/// The gRPC transport only offers HTTP/2, so that's the ALPN protocol if TLS was used
+fn grpc_tls_info(tls_recorder: &TlsRecorder) -> Option<TlsInfo> {
+    tls_recorder.latest().map(|tls| TlsInfo {
+        alpn_protocol: Some("h2".to_string()),
+        ..tls
+    })
+}
+
 fn monitor_plugin_events<R: Runtime>(app_handle: &AppHandle<R>) {
     let app_handle = app_handle.clone();
     tauri::async_runtime::spawn(async move {
@@ -1,3 +1,4 @@
+use crate::encoding::{read_response_body, response_charset};
 use crate::http_request::send_http_request;
 use crate::render::{render_http_request, render_json_value};
 use crate::window::{create_window, CreateWindowConfig};
@@ -9,17 +10,21 @@ use chrono::Utc;
 use log::warn;
 use tauri::{AppHandle, Emitter, Manager, Runtime, State};
 use tauri_plugin_clipboard_manager::ClipboardExt;
+use yaak_http::response_body::{hexdump_body, read_body_chunk, read_body_lines, search_body};
 use yaak_models::models::{HttpResponse, Plugin};
 use yaak_models::queries::{
     create_default_http_response, delete_plugin_key_value, get_base_environment, get_http_request,
-    get_plugin_key_value, list_http_responses_for_request, list_plugins, set_plugin_key_value,
-    upsert_plugin, UpdateSource,
+    get_http_response, get_inherited, get_plugin_key_value, list_http_responses_for_request,
+    list_plugins, set_plugin_key_value, upsert_plugin, UpdateSource,
 };
 use yaak_plugins::events::{
     Color, DeleteKeyValueResponse, EmptyPayload, FindHttpResponsesResponse,
-    GetHttpRequestByIdResponse, GetKeyValueResponse, Icon, InternalEvent, InternalEventPayload,
-    RenderHttpRequestResponse, SendHttpRequestResponse, SetKeyValueResponse, ShowToastRequest,
-    TemplateRenderResponse, WindowContext, WindowNavigateEvent,
+    GetHttpRequestByIdResponse, GetHttpResponseBodyChunkResponse,
+    GetHttpResponseBodyHexdumpResponse, GetHttpResponseBodyLinesResponse,
+    GetHttpResponseBodyResponse, GetKeyValueResponse, Icon, InternalEvent, InternalEventPayload,
+    RenderHttpRequestResponse, SearchHttpResponseBodyResponse, SendHttpRequestResponse,
+    SetKeyValueResponse, ShowToastRequest, TemplateRenderResponse, WindowContext,
+    WindowNavigateEvent,
 };
 use yaak_plugins::manager::PluginManager;
 use yaak_plugins::plugin_handle::PluginHandle;
@@ -66,6 +71,70 @@ pub(crate) async fn handle_plugin_event<R: Runtime>(
                 http_responses,
             }))
         }
+        InternalEventPayload::GetHttpResponseBodyRequest(req) => {
+            let body = match get_http_response(app_handle, req.response_id.as_str()).await {
+                Ok(response) => read_response_body(app_handle, &response).await,
+                Err(e) => Err(e.into()),
+            };
+            let body = match body {
+                Ok(body) => body.map(|b| b.text),
+                Err(e) => {
+                    warn!("Failed to read response body {e:?}");
+                    None
+                }
+            };
+            Some(InternalEventPayload::GetHttpResponseBodyResponse(GetHttpResponseBodyResponse {
+                body,
+            }))
+        }
+        InternalEventPayload::GetHttpResponseBodyChunkRequest(req) => {
+            let chunk = match response_and_charset(app_handle, &req.response_id).await {
+                Ok((r, charset)) => {
+                    read_body_chunk(&r, charset.as_deref(), req.offset, req.length).await
+                }
+                Err(e) => Err(e),
+            };
+            let chunk = chunk.map_err(|e| warn!("Failed to read response body chunk {e:?}")).ok();
+            Some(InternalEventPayload::GetHttpResponseBodyChunkResponse(
+                GetHttpResponseBodyChunkResponse { chunk },
+            ))
+        }
+        InternalEventPayload::GetHttpResponseBodyLinesRequest(req) => {
+            let lines = match response_and_charset(app_handle, &req.response_id).await {
+                Ok((r, charset)) => {
+                    read_body_lines(&r, charset.as_deref(), req.start_line, req.line_count).await
+                }
+                Err(e) => Err(e),
+            };
+            let lines = lines.map_err(|e| warn!("Failed to read response body lines {e:?}")).ok();
+            Some(InternalEventPayload::GetHttpResponseBodyLinesResponse(
+                GetHttpResponseBodyLinesResponse { lines },
+            ))
+        }
+        InternalEventPayload::SearchHttpResponseBodyRequest(req) => {
+            let search = match response_and_charset(app_handle, &req.response_id).await {
+                Ok((r, charset)) => {
+                    let charset = charset.as_deref();
+                    search_body(&r, charset, &req.query, req.case_sensitive, req.offset, req.line)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            let search = search.map_err(|e| warn!("Failed to search response body {e:?}")).ok();
+            Some(InternalEventPayload::SearchHttpResponseBodyResponse(
+                SearchHttpResponseBodyResponse { search },
+            ))
+        }
+        InternalEventPayload::GetHttpResponseBodyHexdumpRequest(req) => {
+            let rows = match get_http_response(app_handle, &req.response_id).await {
+                Ok(r) => hexdump_body(&r, req.offset, req.length).await,
+                Err(e) => Err(e.into()),
+            };
+            let rows = rows.map_err(|e| warn!("Failed to hexdump response body {e:?}")).ok();
+            Some(InternalEventPayload::GetHttpResponseBodyHexdumpResponse(
+                GetHttpResponseBodyHexdumpResponse { rows },
+            ))
+        }
         InternalEventPayload::GetHttpRequestByIdRequest(req) => {
             let http_request = get_http_request(app_handle, req.id.as_str()).await.unwrap();
             Some(InternalEventPayload::GetHttpRequestByIdResponse(GetHttpRequestByIdResponse {
@@ -83,9 +152,17 @@ pub(crate) async fn handle_plugin_event<R: Runtime>(
             let base_environment = get_base_environment(&window, workspace.id.as_str())
                 .await
                 .expect("Failed to get base environment");
+            let inherited = get_inherited(
+                &window,
+                workspace.id.as_str(),
+                req.http_request.folder_id.as_deref(),
+            )
+            .await
+            .expect("Failed to get inherited headers and authentication");
             let cb = PluginTemplateCallback::new(app_handle, &window_context, req.purpose);
             let http_request = render_http_request(
                 &req.http_request,
+                &inherited,
                 &base_environment,
                 environment.as_ref(),
                 &cb,
@@ -188,6 +265,7 @@ pub(crate) async fn handle_plugin_event<R: Runtime>(
                 &http_request,
                 &resp,
                 environment,
+                Vec::new(),
                 cookie_jar,
                 &mut tokio::sync::watch::channel(false).1, // No-op cancel channel
             )
@@ -286,3 +364,12 @@ pub(crate) async fn handle_plugin_event<R: Runtime>(
         }
     }
 }
+
+async fn response_and_charset<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    response_id: &str,
+) -> yaak_http::error::Result<(HttpResponse, Option<String>)> {
+    let response = get_http_response(app_handle, response_id).await?;
+    let charset = response_charset(app_handle, &response).await?;
+    Ok((response, charset))
+}
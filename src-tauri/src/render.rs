@@ -4,19 +4,9 @@ use yaak_http::apply_path_placeholders;
 use yaak_models::models::{
     Environment, GrpcMetadataEntry, GrpcRequest, HttpRequest, HttpRequestHeader, HttpUrlParameter,
 };
-use yaak_models::render::make_vars_hashmap;
+use yaak_models::render::{make_vars_hashmap, Inherited, VariableLayers};
 use yaak_templates::{parse_and_render, render_json_value_raw, TemplateCallback};
 
-pub async fn render_template<T: TemplateCallback>(
-    template: &str,
-    base_environment: &Environment,
-    environment: Option<&Environment>,
-    cb: &T,
-) -> String {
-    let vars = &make_vars_hashmap(base_environment, environment);
-    render(template, vars, cb).await
-}
-
 pub async fn render_json_value<T: TemplateCallback>(
     value: Value,
     base_environment: &Environment,
@@ -29,14 +19,27 @@ pub async fn render_json_value<T: TemplateCallback>(
 
 pub async fn render_grpc_request<T: TemplateCallback>(
     r: &GrpcRequest,
+    inherited: &Inherited,
     base_environment: &Environment,
     environment: Option<&Environment>,
     cb: &T,
 ) -> GrpcRequest {
-    let vars = &make_vars_hashmap(base_environment, environment);
+    let vars = &inherited.variable_layers(base_environment, environment).to_hashmap();
+
+    // Inherited headers are sent as metadata, with the request's own entries overriding them
+    let entries: Vec<HttpRequestHeader> = r
+        .metadata
+        .iter()
+        .map(|m| HttpRequestHeader {
+            enabled: m.enabled,
+            name: m.name.clone(),
+            value: m.value.clone(),
+            id: m.id.clone(),
+        })
+        .collect();
 
     let mut metadata = Vec::new();
-    for p in r.metadata.clone() {
+    for p in inherited.headers(&entries) {
         metadata.push(GrpcMetadataEntry {
             enabled: p.enabled,
             name: render(p.name.as_str(), vars, cb).await,
@@ -45,8 +48,10 @@ pub async fn render_grpc_request<T: TemplateCallback>(
         })
     }
 
+    let (authentication_type, unrendered_authentication) =
+        inherited.authentication(&r.authentication_type, &r.authentication);
     let mut authentication = BTreeMap::new();
-    for (k, v) in r.authentication.clone() {
+    for (k, v) in unrendered_authentication {
         authentication.insert(k, render_json_value_raw(v, vars, cb).await);
     }
 
@@ -56,17 +61,30 @@ pub async fn render_grpc_request<T: TemplateCallback>(
         url,
         metadata,
         authentication,
+        authentication_type,
         ..r.to_owned()
     }
 }
 
 pub async fn render_http_request<T: TemplateCallback>(
     r: &HttpRequest,
+    inherited: &Inherited,
     base_environment: &Environment,
     environment: Option<&Environment>,
     cb: &T,
 ) -> HttpRequest {
-    let vars = &make_vars_hashmap(base_environment, environment);
+    let layers = inherited.variable_layers(base_environment, environment);
+    render_http_request_with_layers(r, inherited, &layers, cb).await
+}
+
+/// Render a request with variables beyond those of the environments, like a row of a data file
+pub async fn render_http_request_with_layers<T: TemplateCallback>(
+    r: &HttpRequest,
+    inherited: &Inherited,
+    layers: &VariableLayers,
+    cb: &T,
+) -> HttpRequest {
+    let vars = &layers.to_hashmap();
 
     let mut url_parameters = Vec::new();
     for p in r.url_parameters.clone() {
@@ -79,7 +97,7 @@ pub async fn render_http_request<T: TemplateCallback>(
     }
 
     let mut headers = Vec::new();
-    for p in r.headers.clone() {
+    for p in inherited.headers(&r.headers) {
         headers.push(HttpRequestHeader {
             enabled: p.enabled,
             name: render(p.name.as_str(), vars, cb).await,
@@ -93,8 +111,10 @@ pub async fn render_http_request<T: TemplateCallback>(
         body.insert(k, render_json_value_raw(v, vars, cb).await);
     }
 
+    let (authentication_type, unrendered_authentication) =
+        inherited.authentication(&r.authentication_type, &r.authentication);
     let mut authentication = BTreeMap::new();
-    for (k, v) in r.authentication.clone() {
+    for (k, v) in unrendered_authentication {
         authentication.insert(k, render_json_value_raw(v, vars, cb).await);
     }
 
@@ -109,6 +129,7 @@ pub async fn render_http_request<T: TemplateCallback>(
         headers,
         body,
         authentication,
+        authentication_type,
         ..r.to_owned()
     }
 }
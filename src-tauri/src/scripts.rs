@@ -0,0 +1,203 @@
+use log::warn;
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::{Emitter, Listener, Manager, Runtime, WebviewWindow};
+use yaak_models::models::{
+    EnvironmentVariable, Folder, HttpAssertion, HttpAssertionResult, HttpAssertionType,
+    HttpRequest, HttpResponse, Workspace, WorkspaceMeta,
+};
+use yaak_models::queries::{
+    generate_id, get_or_create_workspace_meta, upsert_workspace_meta, UpdateSource,
+};
+use yaak_plugins::events::{
+    CallPostResponseScriptRequest, CallPreRequestScriptRequest, WindowContext,
+};
+use yaak_plugins::manager::PluginManager;
+
+/// Scripts to run around sending a request, in the order they run. The workspace's go first,
+/// then each folder's from the outermost in, and the request's own last.
+#[derive(Debug, Clone, Default)]
+pub struct RequestScripts {
+    pub pre_request: Vec<String>,
+    pub post_response: Vec<String>,
+}
+
+impl RequestScripts {
+    pub fn new(workspace: &Workspace, folders: &[Folder], request: &HttpRequest) -> Self {
+        let pre_request = [workspace.pre_request_script.as_str()]
+            .into_iter()
+            .chain(folders.iter().map(|f| f.pre_request_script.as_str()))
+            .chain([request.pre_request_script.as_str()]);
+        let post_response = [workspace.post_response_script.as_str()]
+            .into_iter()
+            .chain(folders.iter().map(|f| f.post_response_script.as_str()))
+            .chain([request.post_response_script.as_str()]);
+        Self {
+            pre_request: non_empty(pre_request),
+            post_response: non_empty(post_response),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pre_request.is_empty() && self.post_response.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TrustScriptsRequest {
+    reply_id: String,
+    workspace_name: String,
+}
+
+/// Scripts come from workspace data, which may have been synced or imported, so the user is
+/// asked to trust a workspace before any of its scripts run. The answer is kept in the workspace
+/// meta, which isn't synced, so every machine asks for itself.
+pub async fn ensure_scripts_trusted<R: Runtime>(
+    window: &WebviewWindow<R>,
+    workspace: &Workspace,
+) -> Result<(), String> {
+    let workspace_meta = get_or_create_workspace_meta(window, workspace, &UpdateSource::Background)
+        .await
+        .map_err(|e| e.to_string())?;
+    if workspace_meta.setting_trust_scripts {
+        return Ok(());
+    }
+
+    if !confirm_trust_scripts(window, workspace).await {
+        return Err("Scripts were not run because the workspace isn't trusted".to_string());
+    }
+
+    let workspace_meta = WorkspaceMeta {
+        setting_trust_scripts: true,
+        ..workspace_meta
+    };
+    upsert_workspace_meta(window, workspace_meta, &UpdateSource::Background)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Ask the window whether to trust the workspace's scripts, waiting for the answer
+async fn confirm_trust_scripts<R: Runtime>(
+    window: &WebviewWindow<R>,
+    workspace: &Workspace,
+) -> bool {
+    let reply_id = generate_id();
+    let (tx, mut rx) = tokio::sync::watch::channel(false);
+    let event_id = window.listen(reply_id.clone(), move |ev| {
+        let trusted = serde_json::from_str(ev.payload()).unwrap_or(false);
+        if let Err(e) = tx.send(trusted) {
+            warn!("Failed to send trust scripts reply {e:?}");
+        }
+    });
+
+    let req = TrustScriptsRequest {
+        reply_id,
+        workspace_name: workspace.name.clone(),
+    };
+    let trusted = match window.emit_to(window.label(), "trust_scripts_request", req) {
+        Ok(_) => rx.changed().await.is_ok() && *rx.borrow(),
+        Err(e) => {
+            warn!("Failed to ask to trust scripts {e:?}");
+            false
+        }
+    };
+    window.unlisten(event_id);
+    trusted
+}
+
+/// Run pre-request scripts on the unrendered request, one after another. Returns the request
+/// with their changes, and the variables they set.
+pub async fn run_pre_request_scripts<R: Runtime>(
+    window: &WebviewWindow<R>,
+    scripts: &[String],
+    request: &HttpRequest,
+    variables: HashMap<String, String>,
+) -> Result<(HttpRequest, Vec<EnvironmentVariable>), String> {
+    let plugin_manager = window.state::<PluginManager>();
+    let window_context = WindowContext::from_window(window);
+
+    let mut request = request.clone();
+    let mut variables = variables;
+    let mut set_variables = HashMap::new();
+    for script in scripts {
+        let req = CallPreRequestScriptRequest {
+            script: script.to_string(),
+            http_request: request,
+            variables: variables.clone(),
+        };
+        let resp = plugin_manager
+            .call_pre_request_script(&window_context, req)
+            .await
+            .map_err(|e| e.to_string())?;
+        request = resp.http_request;
+        variables.extend(resp.variables.clone());
+        set_variables.extend(resp.variables);
+    }
+
+    Ok((request, to_environment_variables(set_variables)))
+}
+
+/// Run post-response scripts, one after another. Returns the variables they set and the results
+/// of their tests, where a script that throws counts as a failed test.
+pub async fn run_post_response_scripts<R: Runtime>(
+    window: &WebviewWindow<R>,
+    scripts: &[String],
+    request: &HttpRequest,
+    response: &HttpResponse,
+    variables: HashMap<String, String>,
+) -> (Vec<EnvironmentVariable>, Vec<HttpAssertionResult>) {
+    let plugin_manager = window.state::<PluginManager>();
+    let window_context = WindowContext::from_window(window);
+
+    let mut variables = variables;
+    let mut set_variables = HashMap::new();
+    let mut results = Vec::new();
+    for script in scripts {
+        let req = CallPostResponseScriptRequest {
+            script: script.to_string(),
+            http_request: request.clone(),
+            http_response: response.clone(),
+            variables: variables.clone(),
+        };
+        match plugin_manager.call_post_response_script(&window_context, req).await {
+            Ok(resp) => {
+                variables.extend(resp.variables.clone());
+                set_variables.extend(resp.variables);
+                results.extend(resp.assertion_results);
+            }
+            Err(e) => results.push(HttpAssertionResult {
+                assertion: HttpAssertion {
+                    enabled: true,
+                    assertion_type: HttpAssertionType::Script,
+                    target: "Post-response script".to_string(),
+                    ..Default::default()
+                },
+                passed: false,
+                actual: None,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    (to_environment_variables(set_variables), results)
+}
+
+fn non_empty<'a>(scripts: impl Iterator<Item = &'a str>) -> Vec<String> {
+    scripts.filter(|s| !s.trim().is_empty()).map(|s| s.to_string()).collect()
+}
+
+fn to_environment_variables(variables: HashMap<String, String>) -> Vec<EnvironmentVariable> {
+    let mut variables: Vec<EnvironmentVariable> = variables
+        .into_iter()
+        .map(|(name, value)| EnvironmentVariable {
+            enabled: true,
+            name,
+            value,
+            id: None,
+        })
+        .collect();
+    variables.sort_by(|a, b| a.name.cmp(&b.name));
+    variables
+}
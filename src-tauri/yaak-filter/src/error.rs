@@ -0,0 +1,31 @@
+use serde::{Serialize, Serializer};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("JSON error: {0}")]
+    JsonErr(#[from] serde_json::Error),
+
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+
+    #[error("Failed to parse content: {0}")]
+    InvalidContent(String),
+
+    #[error("Filter failed: {0}")]
+    FilterFailed(String),
+
+    #[error("Content of type {0} can't be filtered")]
+    UnsupportedContentType(String),
+}
+
+impl Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_ref())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,52 @@
+use crate::error::Error::{FilterFailed, InvalidContent, InvalidFilter};
+use crate::error::Result;
+use jaq_core::load::{Arena, File, Loader};
+use jaq_core::{Compiler, Ctx, RcIter};
+use jaq_json::Val;
+use serde_json::Value;
+use serde_json_path::JsonPath;
+
+/// Select values with a JSONPath expression, returning them as a JSON array
+pub(crate) fn filter_jsonpath(content: &str, filter: &str) -> Result<String> {
+    let value = parse_json(content)?;
+    let nodes = query_jsonpath(&value, filter)?;
+    Ok(serde_json::to_string_pretty(&nodes)?)
+}
+
+/// Select the values a JSONPath expression matches in some JSON
+pub fn query_jsonpath<'a>(value: &'a Value, path: &str) -> Result<Vec<&'a Value>> {
+    let path = JsonPath::parse(path).map_err(|e| InvalidFilter(e.to_string()))?;
+    Ok(path.query(value).all())
+}
+
+/// Run a jq program, returning each value it outputs on its own line(s), like `jq` does
+pub(crate) fn filter_jq(content: &str, filter: &str) -> Result<String> {
+    let value = parse_json(content)?;
+
+    let program = File {
+        code: filter,
+        path: (),
+    };
+    let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+    let arena = Arena::default();
+    let modules = loader
+        .load(&arena, program)
+        .map_err(|_| InvalidFilter("Failed to parse jq expression".to_string()))?;
+    let filter = Compiler::default()
+        .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+        .compile(modules)
+        .map_err(|_| InvalidFilter("Undefined function or variable".to_string()))?;
+
+    let inputs = RcIter::new(core::iter::empty());
+    let mut outputs = Vec::new();
+    for output in filter.run((Ctx::new([], &inputs), Val::from(value))) {
+        let output = output.map_err(|e| FilterFailed(e.to_string()))?;
+        outputs.push(serde_json::to_string_pretty(&Value::from(output))?);
+    }
+
+    Ok(outputs.join("\n"))
+}
+
+fn parse_json(content: &str) -> Result<Value> {
+    serde_json::from_str(content).map_err(|e| InvalidContent(e.to_string()))
+}
@@ -0,0 +1,98 @@
+use crate::error::Error::UnsupportedContentType;
+use crate::error::Result;
+
+pub mod error;
+mod json;
+mod xml;
+
+pub use json::query_jsonpath;
+
+/// The expression languages the built-in filters understand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterLanguage {
+    JsonPath,
+    Jq,
+    XPath,
+}
+
+impl FilterLanguage {
+    /// Pick the language of a filter over some content. JSON can be filtered with JSONPath or
+    /// jq, which are told apart by JSONPath expressions always starting at the root, `$`.
+    pub fn detect(filter: &str, content: &str, content_type: &str) -> Option<FilterLanguage> {
+        let is_json = match content_type.to_lowercase() {
+            t if t.contains("json") => true,
+            t if t.contains("xml") || t.contains("html") => false,
+            // Servers often get the content type wrong, so fall back to sniffing the content
+            _ => match content.trim_start().chars().next() {
+                Some('{') | Some('[') => true,
+                Some('<') => false,
+                _ => return None,
+            },
+        };
+
+        if !is_json {
+            Some(FilterLanguage::XPath)
+        } else if filter.trim_start().starts_with('$') {
+            Some(FilterLanguage::JsonPath)
+        } else {
+            Some(FilterLanguage::Jq)
+        }
+    }
+}
+
+/// Filter content with a JSONPath, jq, or XPath expression, returning what it selects as text
+pub fn filter(content: &str, filter: &str, content_type: &str) -> Result<String> {
+    match FilterLanguage::detect(filter, content, content_type) {
+        Some(FilterLanguage::JsonPath) => json::filter_jsonpath(content, filter),
+        Some(FilterLanguage::Jq) => json::filter_jq(content, filter),
+        Some(FilterLanguage::XPath) => xml::filter_xpath(content, filter),
+        None => Err(UnsupportedContentType(content_type.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod filter_tests {
+    use crate::{filter, FilterLanguage};
+
+    const BOOKS_JSON: &str = r#"{"books":[{"title":"A","price":5},{"title":"B","price":15}]}"#;
+    const BOOKS_XML: &str = r#"<books><book id="1"><title>A</title></book><book id="2"><title>B</title></book></books>"#;
+
+    #[test]
+    fn detects_language() {
+        let detect = FilterLanguage::detect;
+        assert_eq!(detect("$.a", "{}", "application/json"), Some(FilterLanguage::JsonPath));
+        assert_eq!(detect(".a", "{}", "application/vnd.api+json"), Some(FilterLanguage::Jq));
+        assert_eq!(detect("//a", "<a/>", "text/xml; charset=utf-8"), Some(FilterLanguage::XPath));
+        assert_eq!(detect(".[0]", " [1]", "text/plain"), Some(FilterLanguage::Jq));
+        assert_eq!(detect("//a", "<a/>", ""), Some(FilterLanguage::XPath));
+        assert_eq!(detect(".a", "hello", "text/plain"), None);
+    }
+
+    #[test]
+    fn filters_with_jsonpath() {
+        let result = filter(BOOKS_JSON, "$.books[?@.price < 10].title", "application/json");
+        assert_eq!(result.unwrap(), "[\n  \"A\"\n]");
+        assert!(filter(BOOKS_JSON, "$.books[", "application/json").is_err());
+    }
+
+    #[test]
+    fn filters_with_jq() {
+        let result = filter(BOOKS_JSON, ".books[] | select(.price > 10) | .title", "");
+        assert_eq!(result.unwrap(), "\"B\"");
+        let result = filter(BOOKS_JSON, ".books[].price", "application/json");
+        assert_eq!(result.unwrap(), "5\n15");
+        assert!(filter(BOOKS_JSON, ".books[", "application/json").is_err());
+        assert!(filter(BOOKS_JSON, "error(\"nope\")", "application/json").is_err());
+    }
+
+    #[test]
+    fn filters_with_xpath() {
+        let result = filter(BOOKS_XML, "//book[@id='2']", "application/xml");
+        assert_eq!(result.unwrap(), r#"<book id="2"><title>B</title></book>"#);
+        let result = filter(BOOKS_XML, "//book/@id", "application/xml");
+        assert_eq!(result.unwrap(), "1\n2");
+        let result = filter(BOOKS_XML, "count(//book)", "application/xml");
+        assert_eq!(result.unwrap(), "2");
+        assert!(filter(BOOKS_XML, "//book[", "application/xml").is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::error::Error::{InvalidContent, InvalidFilter};
+use crate::error::Result;
+use std::fmt::Write;
+use sxd_document::dom::{ChildOfElement, Element};
+use sxd_document::parser;
+use sxd_xpath::nodeset::Node;
+use sxd_xpath::{evaluate_xpath, Value};
+
+/// Evaluate an XPath expression, returning selected elements as XML and other values as text,
+/// one per line
+pub(crate) fn filter_xpath(content: &str, filter: &str) -> Result<String> {
+    let package = parser::parse(content).map_err(|e| InvalidContent(e.to_string()))?;
+    let document = package.as_document();
+    let value = evaluate_xpath(&document, filter).map_err(|e| InvalidFilter(e.to_string()))?;
+
+    Ok(match value {
+        Value::Nodeset(nodes) => {
+            let nodes: Vec<String> = nodes.document_order().into_iter().map(node_text).collect();
+            nodes.join("\n")
+        }
+        Value::Boolean(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s,
+    })
+}
+
+fn node_text(node: Node) -> String {
+    match node {
+        Node::Element(element) => {
+            let mut out = String::new();
+            write_element(&mut out, element);
+            out
+        }
+        node => node.string_value(),
+    }
+}
+
+fn write_element(out: &mut String, element: Element) {
+    let name = qualified_name(element.preferred_prefix(), element.name().local_part());
+    let _ = write!(out, "<{name}");
+    for attr in element.attributes() {
+        let attr_name = qualified_name(attr.preferred_prefix(), attr.name().local_part());
+        let _ = write!(out, " {attr_name}=\"{}\"", escape(attr.value()));
+    }
+
+    let children = element.children();
+    if children.is_empty() {
+        out.push_str("/>");
+        return;
+    }
+
+    out.push('>');
+    for child in children {
+        match child {
+            ChildOfElement::Element(e) => write_element(out, e),
+            ChildOfElement::Text(t) => out.push_str(&escape(t.text())),
+            ChildOfElement::Comment(c) => {
+                let _ = write!(out, "<!--{}-->", c.text());
+            }
+            ChildOfElement::ProcessingInstruction(pi) => {
+                let _ = match pi.value() {
+                    Some(value) => write!(out, "<?{} {value}?>", pi.target()),
+                    None => write!(out, "<?{}?>", pi.target()),
+                };
+            }
+        }
+    }
+    let _ = write!(out, "</{name}>");
+}
+
+fn qualified_name(prefix: Option<&str>, local_part: &str) -> String {
+    match prefix {
+        Some(prefix) => format!("{prefix}:{local_part}"),
+        None => local_part.to_string(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -1,16 +1,37 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
+export type CaCertificate = { enabled: boolean, path: string, };
+
+export type CertificatePin = { enabled: boolean, host: string, pin: string, };
+
+export type ClientCertificate = { enabled: boolean, host: string, certPath: string | null, keyPath: string | null, pfxPath: string | null, };
+
 export type Environment = { model: "environment", id: string, workspaceId: string, environmentId: string | null, createdAt: string, updatedAt: string, name: string, variables: Array<EnvironmentVariable>, };
 
 export type EnvironmentVariable = { enabled?: boolean, name: string, value: string, id?: string, };
 
-export type Folder = { model: "folder", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, name: string, description: string, sortPriority: number, };
+export type Folder = { model: "folder", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, name: string, description: string, preRequestScript: string, postResponseScript: string, headers: Array<HttpRequestHeader>, authentication: Record<string, any>, authenticationType: string | null, variables: Array<EnvironmentVariable>, sortPriority: number, };
 
 export type GrpcMetadataEntry = { enabled?: boolean, name: string, value: string, id?: string, };
 
 export type GrpcRequest = { model: "grpc_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authenticationType: string | null, authentication: Record<string, any>, description: string, message: string, metadata: Array<GrpcMetadataEntry>, method: string | null, name: string, service: string | null, sortPriority: number, url: string, };
 
-export type HttpRequest = { model: "http_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, body: Record<string, any>, bodyType: string | null, description: string, headers: Array<HttpRequestHeader>, method: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
+/**
+ * A check run against each response to a request once it's finished
+ */
+export type HttpAssertion = { enabled?: boolean, assertionType: HttpAssertionType, target: string, value: string, id?: string, };
+
+export type HttpAssertionType = "status" | "header" | "json_path_equals" | "json_path_exists" | "body_matches" | "response_time_under" | "json_schema" | "script";
+
+/**
+ * Pulls a value out of each successful response to a request and saves it as a variable, so
+ * later requests can use it
+ */
+export type HttpExtractor = { enabled?: boolean, extractorType: HttpExtractorType, source: string, variable: string, environmentId: string | null, id?: string, };
+
+export type HttpExtractorType = "json_path" | "header" | "regex" | "cookie";
+
+export type HttpRequest = { model: "http_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, body: Record<string, any>, bodyType: string | null, description: string, disabledCookies: Array<string>, extractors: Array<HttpExtractor>, headers: Array<HttpRequestHeader>, method: string, name: string, assertions: Array<HttpAssertion>, preRequestScript: string, postResponseScript: string, responseCharset: string | null, historyMaxCount: number | null, historyMaxAgeDays: number | null, historyMaxBytes: number | null, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
 
 export type HttpRequestHeader = { enabled?: boolean, name: string, value: string, id?: string, };
 
@@ -20,4 +41,4 @@ export type SyncModel = { "type": "workspace" } & Workspace | { "type": "environ
 
 export type WebsocketRequest = { model: "websocket_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, message: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
 
-export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, name: string, description: string, settingValidateCertificates: boolean, settingFollowRedirects: boolean, settingRequestTimeout: number, };
+export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, name: string, description: string, preRequestScript: string, postResponseScript: string, headers: Array<HttpRequestHeader>, authentication: Record<string, any>, authenticationType: string | null, settingClientCertificates: Array<ClientCertificate>, settingCaCertificates: Array<CaCertificate>, settingCertificatePins: Array<CertificatePin>, settingValidateCertificates: boolean, settingFollowRedirects: boolean, settingMaxRedirects: number, settingReconnectEventStreams: boolean, settingRequestTimeout: number, settingHistoryMaxCount: number, settingHistoryMaxAgeDays: number, settingHistoryMaxBytes: number, };
@@ -16,6 +16,7 @@ use tonic_reflection::pb::v1::{
 };
 use tonic_reflection::pb::v1::{ExtensionRequest, FileDescriptorResponse};
 use tonic_reflection::pb::{v1, v1alpha};
+use yaak_tls::TlsOptions;
 
 pub struct AutoReflectionClient<T = Client<HttpsConnector<HttpConnector>, BoxBody>> {
     use_v1alpha: bool,
@@ -24,20 +25,20 @@ pub struct AutoReflectionClient<T = Client<HttpsConnector<HttpConnector>, BoxBod
 }
 
 impl AutoReflectionClient {
-    pub fn new(uri: &Uri) -> Self {
+    pub fn new(uri: &Uri, tls_options: &TlsOptions) -> Result<Self, String> {
         let client_v1 = v1::server_reflection_client::ServerReflectionClient::with_origin(
-            get_transport(),
+            get_transport(tls_options)?,
             uri.clone(),
         );
         let client_v1alpha = v1alpha::server_reflection_client::ServerReflectionClient::with_origin(
-            get_transport(),
+            get_transport(tls_options)?,
             uri.clone(),
         );
-        AutoReflectionClient {
+        Ok(AutoReflectionClient {
             use_v1alpha: false,
             client_v1,
             client_v1alpha,
-        }
+        })
     }
 
     #[async_recursion]
@@ -2,19 +2,13 @@ use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
 use hyper_util::client::legacy::connect::HttpConnector;
 use hyper_util::client::legacy::Client;
 use hyper_util::rt::TokioExecutor;
-use rustls::crypto::ring;
-use rustls::ClientConfig;
-use rustls_platform_verifier::BuilderVerifierExt;
-use std::sync::Arc;
 use tonic::body::BoxBody;
+use yaak_tls::{get_config, TlsOptions};
 
-pub(crate) fn get_transport() -> Client<HttpsConnector<HttpConnector>, BoxBody> {
-    let arc_crypto_provider = Arc::new(ring::default_provider());
-    let config = ClientConfig::builder_with_provider(arc_crypto_provider)
-        .with_safe_default_protocol_versions()
-        .unwrap()
-        .with_platform_verifier()
-        .with_no_client_auth();
+pub(crate) fn get_transport(
+    tls_options: &TlsOptions,
+) -> Result<Client<HttpsConnector<HttpConnector>, BoxBody>, String> {
+    let config = get_config(tls_options).map_err(|e| e.to_string())?;
 
     let mut http = HttpConnector::new();
     http.enforce_http(false);
@@ -27,5 +21,5 @@ pub(crate) fn get_transport() -> Client<HttpsConnector<HttpConnector>, BoxBody>
         .http2_only(true)
         .build(connector);
 
-    client
+    Ok(client)
 }
@@ -0,0 +1,284 @@
+use crate::response_body::read_response_body;
+use regex::Regex;
+use serde_json::Value;
+use yaak_filter::query_jsonpath;
+use yaak_models::models::{
+    HttpAssertion, HttpAssertionResult, HttpAssertionType, HttpResponse, HttpResponseHeader,
+};
+
+/// Found values longer than this are cut off, so a result doesn't hold a whole body
+const MAX_ACTUAL_LENGTH: usize = 1000;
+
+/// Whether an assertion passed and what was found, or why it couldn't be checked
+type Check = Result<(bool, Option<String>), String>;
+
+/// Check a finished response against its request's enabled assertions
+pub async fn check_assertions(
+    assertions: &[HttpAssertion],
+    response: &HttpResponse,
+    charset_override: Option<&str>,
+) -> Vec<HttpAssertionResult> {
+    let assertions: Vec<&HttpAssertion> = assertions.iter().filter(|a| a.enabled).collect();
+
+    // Only read and parse the body if something needs it, since it can be large. These are only
+    // looked at by assertions that need them, so the errors for when they weren't read don't matter
+    let needs_json = assertions.iter().any(|a| needs_json(&a.assertion_type));
+    let needs_body = needs_json || assertions.iter().any(|a| needs_body(&a.assertion_type));
+    let body = match needs_body {
+        true => read_body_text(response, charset_override).await,
+        false => Err(String::new()),
+    };
+    let json = match needs_json {
+        true => body.as_ref().map_err(|e| e.clone()).and_then(|text| {
+            serde_json::from_str::<Value>(text).map_err(|e| format!("Body isn't JSON: {e}"))
+        }),
+        false => Err(String::new()),
+    };
+    let body = body.as_ref().map_err(|e| e.clone());
+    let json = json.as_ref().map_err(|e| e.clone());
+
+    let mut results = Vec::new();
+    for assertion in assertions {
+        let target = assertion.target.trim();
+        let value = assertion.value.as_str();
+        let check = match assertion.assertion_type {
+            HttpAssertionType::Status => check_status(value, response.status),
+            HttpAssertionType::Header => check_header(target, value, &response.headers),
+            HttpAssertionType::ResponseTimeUnder => check_response_time(value, response.elapsed),
+            HttpAssertionType::BodyMatches => {
+                body.clone().and_then(|b| check_body_matches(b, value))
+            }
+            HttpAssertionType::JsonPathEquals => {
+                json.clone().and_then(|j| check_json_path_equals(j, target, value))
+            }
+            HttpAssertionType::JsonPathExists => {
+                json.clone().and_then(|j| check_json_path_exists(j, target))
+            }
+            HttpAssertionType::JsonSchema => json.clone().and_then(|j| check_json_schema(j, value)),
+            HttpAssertionType::Script => {
+                Err("Only a post-response script can run this".to_string())
+            }
+        };
+
+        results.push(match check {
+            Ok((passed, actual)) => HttpAssertionResult {
+                assertion: assertion.clone(),
+                passed,
+                actual: actual.map(truncate),
+                error: None,
+            },
+            Err(e) => HttpAssertionResult {
+                assertion: assertion.clone(),
+                passed: false,
+                actual: None,
+                error: Some(e),
+            },
+        });
+    }
+
+    results
+}
+
+fn needs_body(assertion_type: &HttpAssertionType) -> bool {
+    matches!(assertion_type, HttpAssertionType::BodyMatches)
+}
+
+fn needs_json(assertion_type: &HttpAssertionType) -> bool {
+    matches!(
+        assertion_type,
+        HttpAssertionType::JsonPathEquals
+            | HttpAssertionType::JsonPathExists
+            | HttpAssertionType::JsonSchema
+    )
+}
+
+pub(crate) async fn read_body_text(
+    response: &HttpResponse,
+    charset_override: Option<&str>,
+) -> Result<String, String> {
+    match read_response_body(response, charset_override).await {
+        Ok(Some(body)) => Ok(body.text),
+        Ok(None) => Err("Response has no body".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn check_status(expected: &str, status: i32) -> Check {
+    let expected = expected.trim().to_lowercase();
+    let actual = status.to_string();
+    let passed = match expected.strip_suffix("xx") {
+        // A class of status codes, like 2xx
+        Some(class) if class.len() == 1 => actual.len() == 3 && actual.starts_with(class),
+        _ => expected == actual,
+    };
+    Ok((passed, Some(actual)))
+}
+
+fn check_header(name: &str, expected: &str, headers: &[HttpResponseHeader]) -> Check {
+    if name.is_empty() {
+        return Err("No header name to check".to_string());
+    }
+
+    let values: Vec<&str> = headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+        .collect();
+    let passed = match expected {
+        "" => !values.is_empty(),
+        expected => values.contains(&expected),
+    };
+    let actual = (!values.is_empty()).then(|| values.join(", "));
+    Ok((passed, actual))
+}
+
+fn check_response_time(expected: &str, elapsed: i32) -> Check {
+    let max: i64 = expected
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid number of milliseconds \"{expected}\""))?;
+    Ok(((elapsed as i64) < max, Some(format!("{elapsed} ms"))))
+}
+
+fn check_body_matches(body: &str, pattern: &str) -> Check {
+    let regex = Regex::new(pattern).map_err(|e| e.to_string())?;
+    let found = regex.find(body).map(|m| m.as_str().to_string());
+    Ok((found.is_some(), found))
+}
+
+fn check_json_path_equals(json: &Value, path: &str, expected: &str) -> Check {
+    let nodes = query_jsonpath(json, path).map_err(|e| e.to_string())?;
+    // Bare text like `foo` isn't valid JSON, but is clearly meant as a string
+    let expected =
+        serde_json::from_str(expected).unwrap_or_else(|_| Value::String(expected.to_string()));
+    let actual = match nodes.as_slice() {
+        [] => return Ok((false, None)),
+        [node] => (*node).clone(),
+        nodes => Value::Array(nodes.iter().map(|n| (*n).clone()).collect()),
+    };
+    Ok((actual == expected, Some(actual.to_string())))
+}
+
+fn check_json_path_exists(json: &Value, path: &str) -> Check {
+    let nodes = query_jsonpath(json, path).map_err(|e| e.to_string())?;
+    let actual = match nodes.as_slice() {
+        [] => None,
+        [node] => Some(node.to_string()),
+        nodes => Some(Value::Array(nodes.iter().map(|n| (*n).clone()).collect()).to_string()),
+    };
+    Ok((actual.is_some(), actual))
+}
+
+fn check_json_schema(json: &Value, schema: &str) -> Check {
+    let schema: Value =
+        serde_json::from_str(schema).map_err(|e| format!("Invalid JSON Schema: {e}"))?;
+    let validator =
+        jsonschema::validator_for(&schema).map_err(|e| format!("Invalid JSON Schema: {e}"))?;
+    let errors: Vec<String> = validator.iter_errors(json).map(|e| e.to_string()).collect();
+    Ok((errors.is_empty(), (!errors.is_empty()).then(|| errors.join("\n"))))
+}
+
+fn truncate(mut text: String) -> String {
+    if text.len() > MAX_ACTUAL_LENGTH {
+        let mut end = MAX_ACTUAL_LENGTH;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        text.push('…');
+    }
+    text
+}
+
+#[cfg(test)]
+mod assertions_tests {
+    use crate::assertions::check_assertions;
+    use yaak_models::models::{HttpAssertion, HttpAssertionType, HttpResponse, HttpResponseHeader};
+
+    fn assertion(assertion_type: HttpAssertionType, target: &str, value: &str) -> HttpAssertion {
+        HttpAssertion {
+            enabled: true,
+            assertion_type,
+            target: target.to_string(),
+            value: value.to_string(),
+            id: None,
+        }
+    }
+
+    async fn response(body: &str) -> HttpResponse {
+        let path = std::env::temp_dir().join(format!("yaak-assertions-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, body).await.unwrap();
+        HttpResponse {
+            status: 201,
+            elapsed: 120,
+            headers: vec![HttpResponseHeader {
+                name: "Content-Type".to_string(),
+                value: "application/json".to_string(),
+            }],
+            body_path: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn checks_status_headers_and_timing() {
+        let response = response("{}").await;
+        let assertions = vec![
+            assertion(HttpAssertionType::Status, "", "201"),
+            assertion(HttpAssertionType::Status, "", "2xx"),
+            assertion(HttpAssertionType::Status, "", "200"),
+            assertion(HttpAssertionType::Header, "content-type", "application/json"),
+            assertion(HttpAssertionType::Header, "x-missing", ""),
+            assertion(HttpAssertionType::ResponseTimeUnder, "", "500"),
+            assertion(HttpAssertionType::ResponseTimeUnder, "", "100"),
+            assertion(HttpAssertionType::ResponseTimeUnder, "", "soon"),
+        ];
+        let results = check_assertions(&assertions, &response, None).await;
+        let passed: Vec<bool> = results.iter().map(|r| r.passed).collect();
+        assert_eq!(passed, [true, true, false, true, false, true, false, false]);
+        assert_eq!(results[2].actual.as_deref(), Some("201"));
+        assert_eq!(results[6].actual.as_deref(), Some("120 ms"));
+        assert!(results[7].error.is_some());
+    }
+
+    #[tokio::test]
+    async fn checks_body() {
+        let response = response(r#"{"user":{"id":7,"name":"Ada","tags":["a","b"]}}"#).await;
+        let schema = r#"{"type":"object","required":["user"]}"#;
+        let bad_schema = r#"{"type":"object","required":["users"]}"#;
+        let mut disabled = assertion(HttpAssertionType::Status, "", "500");
+        disabled.enabled = false;
+        let assertions = vec![
+            assertion(HttpAssertionType::JsonPathEquals, "$.user.id", "7"),
+            assertion(HttpAssertionType::JsonPathEquals, "$.user.name", "Ada"),
+            assertion(HttpAssertionType::JsonPathEquals, "$.user.tags[*]", r#"["a","b"]"#),
+            assertion(HttpAssertionType::JsonPathEquals, "$.user.id", "8"),
+            assertion(HttpAssertionType::JsonPathExists, "$.user.tags", ""),
+            assertion(HttpAssertionType::JsonPathExists, "$.user.email", ""),
+            assertion(HttpAssertionType::BodyMatches, "", r#""name":"A\w+""#),
+            assertion(HttpAssertionType::JsonSchema, "", schema),
+            assertion(HttpAssertionType::JsonSchema, "", bad_schema),
+            disabled,
+        ];
+        let results = check_assertions(&assertions, &response, None).await;
+        let passed: Vec<bool> = results.iter().map(|r| r.passed).collect();
+        assert_eq!(passed, [true, true, true, false, true, false, true, true, false]);
+        assert_eq!(results[3].actual.as_deref(), Some("7"));
+        assert_eq!(results[6].actual.as_deref(), Some(r#""name":"Ada""#));
+        assert!(results[8].actual.is_some());
+    }
+
+    #[tokio::test]
+    async fn reports_unreadable_bodies() {
+        let response = response("not json").await;
+        let assertions = vec![
+            assertion(HttpAssertionType::JsonPathExists, "$.id", ""),
+            assertion(HttpAssertionType::BodyMatches, "", "("),
+            assertion(HttpAssertionType::BodyMatches, "", "json"),
+        ];
+        let results = check_assertions(&assertions, &response, None).await;
+        assert!(results[0].error.as_deref().unwrap().starts_with("Body isn't JSON"));
+        assert!(results[1].error.is_some());
+        assert!(results[2].passed);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::error::Result;
+use crate::timing::{TimingLayer, TimingRecorder, TimingResolver};
+use log::debug;
+use reqwest::redirect::Policy;
+use reqwest::{Client, Proxy, Url};
+use std::sync::Arc;
+use std::time::Duration;
+use yaak_models::models::{
+    CaCertificate, CertificatePin, ClientCertificate, ProxySetting, ProxySettingAuth, Workspace,
+};
+use yaak_tls::tls_info::TlsRecorder;
+use yaak_tls::{get_config, TlsOptions};
+
+#[derive(Debug, Clone)]
+pub struct HttpConnectionOptions {
+    pub follow_redirects: bool,
+    pub max_redirects: usize,
+    pub validate_certificates: bool,
+    pub client_certificates: Vec<ClientCertificate>,
+    pub ca_certificates: Vec<CaCertificate>,
+    pub certificate_pins: Vec<CertificatePin>,
+    pub timeout: Option<Duration>,
+    pub proxy: Option<ProxySetting>,
+    pub reconnect_event_streams: bool,
+}
+
+impl HttpConnectionOptions {
+    pub fn new(workspace: &Workspace, proxy: Option<ProxySetting>) -> Self {
+        let timeout = match workspace.setting_request_timeout > 0 {
+            true => {
+                Some(Duration::from_millis(workspace.setting_request_timeout.unsigned_abs() as u64))
+            }
+            false => None,
+        };
+
+        Self {
+            follow_redirects: workspace.setting_follow_redirects,
+            max_redirects: workspace.setting_max_redirects.max(0) as usize,
+            validate_certificates: workspace.setting_validate_certificates,
+            client_certificates: workspace.setting_client_certificates.clone(),
+            ca_certificates: workspace.setting_ca_certificates.clone(),
+            certificate_pins: workspace.setting_certificate_pins.clone(),
+            timeout,
+            proxy,
+            reconnect_event_streams: workspace.setting_reconnect_event_streams,
+        }
+    }
+}
+
+/// Make a client for sending to `url`, which selects the client certificate to present, so
+/// following a redirect to another host needs a client of its own. Details of the TLS handshakes it makes are collected by `tls_recorder`, and the timing of
+/// each phase of its requests by `timing_recorder`.
+pub fn make_client(
+    options: &HttpConnectionOptions,
+    url: &Url,
+    tls_recorder: &TlsRecorder,
+    timing_recorder: &TimingRecorder,
+) -> Result<Client> {
+    let mut client_builder = Client::builder()
+        .redirect(Policy::none()) // Redirects are followed by the sender so hops can be recorded
+        .connection_verbose(true)
+        .gzip(true)
+        .brotli(true)
+        .deflate(true)
+        .referer(false)
+        .dns_resolver(Arc::new(TimingResolver(timing_recorder.clone())))
+        .connector_layer(TimingLayer(timing_recorder.clone()));
+
+    let tls_options = TlsOptions {
+        validate_certificates: options.validate_certificates,
+        recorder: tls_recorder.clone(),
+        ..TlsOptions::for_url(
+            &options.client_certificates,
+            &options.ca_certificates,
+            &options.certificate_pins,
+            url.as_str(),
+        )
+    };
+    client_builder = client_builder.use_preconfigured_tls(get_config(&tls_options)?);
+
+    match options.proxy.clone() {
+        Some(ProxySetting::Disabled) => client_builder = client_builder.no_proxy(),
+        Some(ProxySetting::Enabled { http, https, auth }) => {
+            debug!("Using proxy http={http} https={https}");
+            let mut proxy = Proxy::custom(move |url| {
+                let http = if http.is_empty() { None } else { Some(http.to_owned()) };
+                let https = if https.is_empty() { None } else { Some(https.to_owned()) };
+                let proxy_url = match (url.scheme(), http, https) {
+                    ("http", Some(proxy_url), _) => Some(proxy_url),
+                    ("https", _, Some(proxy_url)) => Some(proxy_url),
+                    _ => None,
+                };
+                proxy_url
+            });
+
+            if let Some(ProxySettingAuth { user, password }) = auth {
+                debug!("Using proxy auth");
+                proxy = proxy.basic_auth(user.as_str(), password.as_str());
+            }
+
+            client_builder = client_builder.proxy(proxy);
+        }
+        None => {} // Nothing to do for this one, as it is the default
+    }
+
+    if let Some(timeout) = options.timeout {
+        client_builder = client_builder.timeout(timeout);
+    }
+
+    Ok(client_builder.build()?)
+}
@@ -0,0 +1,351 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use chrono::{DateTime, NaiveDateTime, SecondsFormat};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use yaak_models::models::{Cookie, CookieSameSite};
+
+const NETSCAPE_HEADER: &str = "# Netscape HTTP Cookie File";
+const NETSCAPE_HTTP_ONLY_PREFIX: &str = "#HttpOnly_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CookieFormat {
+    /// The `cookies.txt` format used by curl, wget, and browser extensions
+    Netscape,
+    /// The `cookies` arrays of an HTTP Archive
+    Har,
+}
+
+pub fn export_cookies(cookies: &[Cookie], format: CookieFormat) -> Result<String> {
+    match format {
+        CookieFormat::Netscape => Ok(cookies_to_netscape(cookies)),
+        CookieFormat::Har => cookies_to_har(cookies),
+    }
+}
+
+pub fn import_cookies(contents: &str, format: CookieFormat) -> Result<Vec<Cookie>> {
+    match format {
+        CookieFormat::Netscape => cookies_from_netscape(contents),
+        CookieFormat::Har => cookies_from_har(contents),
+    }
+}
+
+/// Merge imported cookies into existing ones. Cookies with the same name, domain, and path are
+/// replaced, like a browser would when receiving the cookie again.
+pub fn merge_cookies(existing: &mut Vec<Cookie>, imported: Vec<Cookie>) {
+    for cookie in imported {
+        match existing.iter_mut().find(|c| c.same_key(&cookie)) {
+            Some(c) => *c = cookie,
+            None => existing.push(cookie),
+        }
+    }
+}
+
+pub fn cookies_to_netscape(cookies: &[Cookie]) -> String {
+    let mut lines = vec![NETSCAPE_HEADER.to_string(), String::new()];
+    for c in cookies {
+        // Domain cookies are written with a leading dot, which older parsers rely on
+        let domain = match c.host_only {
+            true => c.domain.clone(),
+            false => format!(".{}", c.domain),
+        };
+        let prefix = if c.http_only { NETSCAPE_HTTP_ONLY_PREFIX } else { "" };
+        let expires = c.expires.map(|e| e.and_utc().timestamp()).unwrap_or(0);
+        lines.push(format!(
+            "{prefix}{domain}\t{}\t{}\t{}\t{expires}\t{}\t{}",
+            netscape_bool(!c.host_only),
+            c.path,
+            netscape_bool(c.secure),
+            c.name,
+            c.value,
+        ));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+pub fn cookies_from_netscape(contents: &str) -> Result<Vec<Cookie>> {
+    let mut cookies = Vec::new();
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        let (line, http_only) = match line.strip_prefix(NETSCAPE_HTTP_ONLY_PREFIX) {
+            Some(l) => (l, true),
+            None => (line, false),
+        };
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.splitn(7, '\t').collect();
+        let [domain, include_subdomains, path, secure, expires, name, value] = parts[..] else {
+            return Err(GenericError(format!("Invalid cookie on line {}", i + 1)));
+        };
+
+        let expires = match expires.trim().parse::<i64>() {
+            Ok(0) => None,
+            Ok(t) => DateTime::from_timestamp(t, 0).map(|d| d.naive_utc()),
+            Err(_) => return Err(GenericError(format!("Invalid expiry on line {}", i + 1))),
+        };
+
+        cookies.push(Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: domain.trim_start_matches('.').to_lowercase(),
+            host_only: !include_subdomains.eq_ignore_ascii_case("TRUE"),
+            path: path.to_string(),
+            expires,
+            secure: secure.eq_ignore_ascii_case("TRUE"),
+            http_only,
+            same_site: None,
+        });
+    }
+
+    Ok(cookies)
+}
+
+fn netscape_bool(v: bool) -> &'static str {
+    if v {
+        "TRUE"
+    } else {
+        "FALSE"
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct HarCookie {
+    name: String,
+    value: String,
+    path: Option<String>,
+    domain: Option<String>,
+    expires: Option<String>,
+    http_only: bool,
+    secure: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    same_site: Option<String>,
+}
+
+pub fn cookies_to_har(cookies: &[Cookie]) -> Result<String> {
+    let har_cookies: Vec<HarCookie> = cookies
+        .iter()
+        .map(|c| HarCookie {
+            name: c.name.clone(),
+            value: c.value.clone(),
+            path: Some(c.path.clone()),
+            // HAR has no host-only flag, so use the same leading-dot convention as browsers
+            domain: Some(match c.host_only {
+                true => c.domain.clone(),
+                false => format!(".{}", c.domain),
+            }),
+            expires: c.expires.map(|e| e.and_utc().to_rfc3339_opts(SecondsFormat::Millis, true)),
+            http_only: c.http_only,
+            secure: c.secure,
+            same_site: c.same_site.as_ref().map(|s| {
+                match s {
+                    CookieSameSite::Strict => "Strict",
+                    CookieSameSite::Lax => "Lax",
+                    CookieSameSite::None => "None",
+                }
+                .to_string()
+            }),
+        })
+        .collect();
+
+    Ok(serde_json::to_string_pretty(&har_cookies)?)
+}
+
+/// Read cookies from either a bare HAR `cookies` array or a full HAR file. For a full file,
+/// cookies from every request and response are collected, with later ones taking precedence.
+pub fn cookies_from_har(contents: &str) -> Result<Vec<Cookie>> {
+    let har: Value = serde_json::from_str(contents)?;
+
+    let mut cookies = Vec::new();
+    if har.is_array() {
+        for c in serde_json::from_value::<Vec<HarCookie>>(har)? {
+            cookies.push(cookie_from_har(c, None)?);
+        }
+        return Ok(cookies);
+    }
+
+    let entries = match har.pointer("/log/entries").and_then(|e| e.as_array()) {
+        Some(entries) => entries,
+        None => return Err(GenericError("HAR file has no log entries".to_string())),
+    };
+
+    for entry in entries {
+        let url = entry.pointer("/request/url").and_then(|u| u.as_str());
+        let url = url.and_then(|u| Url::parse(u).ok());
+        for section in ["/request/cookies", "/response/cookies"] {
+            let Some(section) = entry.pointer(section) else {
+                continue;
+            };
+            let mut imported = Vec::new();
+            for c in serde_json::from_value::<Vec<HarCookie>>(section.clone())? {
+                imported.push(cookie_from_har(c, url.as_ref())?);
+            }
+            merge_cookies(&mut cookies, imported);
+        }
+    }
+
+    Ok(cookies)
+}
+
+/// Convert a HAR cookie, filling in a missing domain and path from the URL it was sent to or
+/// received from, the same way a browser would for a cookie without those attributes.
+fn cookie_from_har(c: HarCookie, url: Option<&Url>) -> Result<Cookie> {
+    let (domain, host_only) = match (c.domain.filter(|d| !d.is_empty()), url) {
+        (Some(d), _) => match d.strip_prefix('.') {
+            Some(d) => (d.to_lowercase(), false),
+            None => (d.to_lowercase(), true),
+        },
+        (None, Some(url)) => (url.host_str().unwrap_or_default().to_string(), true),
+        (None, None) => return Err(GenericError(format!("Cookie {} has no domain", c.name))),
+    };
+
+    let path = match (c.path.filter(|p| p.starts_with('/')), url) {
+        (Some(p), _) => p,
+        (None, Some(url)) => default_path(url),
+        (None, None) => "/".to_string(),
+    };
+
+    let expires = match c.expires.filter(|e| !e.is_empty()) {
+        Some(e) => Some(parse_har_date(&e)?),
+        None => None,
+    };
+
+    let same_site = match c.same_site.map(|s| s.to_lowercase()).as_deref() {
+        Some("strict") => Some(CookieSameSite::Strict),
+        Some("lax") => Some(CookieSameSite::Lax),
+        Some("none") => Some(CookieSameSite::None),
+        _ => None,
+    };
+
+    Ok(Cookie {
+        name: c.name,
+        value: c.value,
+        domain,
+        host_only,
+        path,
+        expires,
+        secure: c.secure,
+        http_only: c.http_only,
+        same_site,
+    })
+}
+
+fn parse_har_date(date: &str) -> Result<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(date)
+        .map(|d| d.naive_utc())
+        .map_err(|e| GenericError(format!("Invalid cookie expiry {date}: {e}")))
+}
+
+/// The default cookie path for a URL (RFC 6265 5.1.4)
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(i) => url.path()[..i].to_string(),
+    }
+}
+
+#[cfg(test)]
+mod cookie_formats_tests {
+    use crate::cookie_formats::{
+        cookies_from_har, cookies_from_netscape, cookies_to_har, cookies_to_netscape,
+    };
+    use yaak_models::models::{Cookie, CookieSameSite};
+
+    fn cookies() -> Vec<Cookie> {
+        vec![
+            Cookie {
+                name: "session".to_string(),
+                value: "abc=123".to_string(),
+                domain: "api.example.com".to_string(),
+                host_only: true,
+                path: "/".to_string(),
+                expires: None,
+                secure: true,
+                http_only: true,
+                same_site: None,
+            },
+            Cookie {
+                name: "theme".to_string(),
+                value: "dark".to_string(),
+                domain: "example.com".to_string(),
+                host_only: false,
+                path: "/app".to_string(),
+                expires: Some("2099-10-21T07:28:00".parse().unwrap()),
+                secure: false,
+                http_only: false,
+                same_site: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn netscape_round_trip() {
+        let txt = cookies_to_netscape(&cookies());
+        assert_eq!(
+            txt,
+            "# Netscape HTTP Cookie File\n\n\
+             #HttpOnly_api.example.com\tFALSE\t/\tTRUE\t0\tsession\tabc=123\n\
+             .example.com\tTRUE\t/app\tFALSE\t4096250880\ttheme\tdark\n"
+        );
+        assert_eq!(cookies_from_netscape(&txt).unwrap(), cookies());
+    }
+
+    #[test]
+    fn netscape_from_curl() {
+        let txt = "# Netscape HTTP Cookie File\n\
+                   # https://curl.se/docs/http-cookies.html\n\
+                   \n\
+                   .example.com\tTRUE\t/\tFALSE\t0\ta\t1\r\n\
+                   example.com\tFALSE\t/\tFALSE\t0\tb\t\n";
+        let cookies = cookies_from_netscape(txt).unwrap();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].domain, "example.com");
+        assert!(!cookies[0].host_only);
+        assert_eq!(cookies[0].value, "1");
+        assert!(cookies[1].host_only);
+        assert_eq!(cookies[1].value, "");
+        assert!(cookies_from_netscape("example.com\tFALSE\t/").is_err());
+    }
+
+    #[test]
+    fn har_round_trip() {
+        let mut cookies = cookies();
+        cookies[1].same_site = Some(CookieSameSite::Lax);
+        let har = cookies_to_har(&cookies).unwrap();
+        assert!(har.contains(r#""domain": ".example.com""#));
+        assert!(har.contains(r#""expires": "2099-10-21T07:28:00.000Z""#));
+        assert_eq!(cookies_from_har(&har).unwrap(), cookies);
+    }
+
+    #[test]
+    fn har_from_entries() {
+        let har = r#"{"log": {"entries": [
+            {
+                "request": {"url": "https://api.example.com/v1/users", "cookies": []},
+                "response": {"cookies": [
+                    {"name": "a", "value": "1"},
+                    {"name": "b", "value": "1", "domain": ".example.com", "path": "/", "expires": null}
+                ]}
+            },
+            {
+                "request": {"url": "https://api.example.com/v1/users", "cookies": []},
+                "response": {"cookies": [{"name": "a", "value": "2"}]}
+            }
+        ]}}"#;
+        let cookies = cookies_from_har(har).unwrap();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].name, "a");
+        assert_eq!(cookies[0].value, "2");
+        assert_eq!(cookies[0].domain, "api.example.com");
+        assert!(cookies[0].host_only);
+        assert_eq!(cookies[0].path, "/v1");
+        assert_eq!(cookies[1].domain, "example.com");
+        assert!(!cookies[1].host_only);
+        assert!(cookies_from_har("{}").is_err());
+    }
+}
@@ -0,0 +1,317 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use chrono::{DateTime, NaiveDateTime};
+use cookie::time::OffsetDateTime;
+use cookie::SameSite;
+use cookie_store::{CookieDomain, CookieExpiration, CookiePath};
+use http::header::SET_COOKIE;
+use http::HeaderMap;
+use reqwest::Url;
+use reqwest_cookie_store::{CookieStore, CookieStoreMutex, RawCookie};
+use std::cmp::Reverse;
+use yaak_models::models::{Cookie, CookieJar, CookieSameSite, HttpResponseCookie};
+
+pub type StoreCookie = cookie_store::Cookie<'static>;
+
+pub fn cookie_store_from_jar(cookie_jar: &CookieJar) -> Result<CookieStoreMutex> {
+    let cookies = cookie_jar.cookies.iter().map(store_cookie_from_cookie);
+    let store = CookieStore::from_cookies(cookies, true)?;
+    Ok(CookieStoreMutex::new(store))
+}
+
+pub fn cookies_from_store(cookie_store: &CookieStoreMutex) -> Result<Vec<Cookie>> {
+    let store = cookie_store.lock().unwrap();
+    Ok(store.iter_any().map(cookie_from_store_cookie).collect())
+}
+
+/// Convert a cookie_store cookie to our own model
+pub fn cookie_from_store_cookie(c: &StoreCookie) -> Cookie {
+    let (domain, host_only) = match &c.domain {
+        CookieDomain::HostOnly(d) => (d.to_string(), true),
+        CookieDomain::Suffix(d) => (d.to_string(), false),
+        CookieDomain::NotPresent | CookieDomain::Empty => (String::new(), true),
+    };
+
+    let expires = match c.expires {
+        CookieExpiration::AtUtc(t) => {
+            DateTime::from_timestamp(t.unix_timestamp(), 0).map(|d| d.naive_utc())
+        }
+        CookieExpiration::SessionEnd => None,
+    };
+
+    Cookie {
+        name: c.name().to_string(),
+        value: c.value().to_string(),
+        domain,
+        host_only,
+        path: c.path.to_string(),
+        expires,
+        secure: c.secure().unwrap_or(false),
+        http_only: c.http_only().unwrap_or(false),
+        same_site: c.same_site().map(|s| match s {
+            SameSite::Strict => CookieSameSite::Strict,
+            SameSite::Lax => CookieSameSite::Lax,
+            SameSite::None => CookieSameSite::None,
+        }),
+    }
+}
+
+/// Convert our own cookie model to a cookie_store cookie
+pub fn store_cookie_from_cookie(c: &Cookie) -> Result<StoreCookie> {
+    if c.name.is_empty() {
+        return Err(GenericError("Cookie name is required".to_string()));
+    }
+
+    let expires = match c.expires {
+        Some(t) => Some(offset_date_time(t)?),
+        None => None,
+    };
+
+    let mut raw = RawCookie::build((c.name.clone(), c.value.clone()))
+        .path(c.path.clone())
+        .secure(c.secure)
+        .http_only(c.http_only);
+    if !c.host_only {
+        raw = raw.domain(c.domain.clone());
+    }
+    if let Some(expires) = expires {
+        raw = raw.expires(expires);
+    }
+    if let Some(same_site) = &c.same_site {
+        raw = raw.same_site(match same_site {
+            CookieSameSite::Strict => SameSite::Strict,
+            CookieSameSite::Lax => SameSite::Lax,
+            CookieSameSite::None => SameSite::None,
+        });
+    }
+
+    // cookie_store only creates cookies in the context of a request, so pretend it came
+    // from the cookie's own domain and then set the fields we actually want
+    let url = Url::parse(&format!("http://{}/", c.domain))
+        .map_err(|e| GenericError(format!("Invalid cookie domain \"{}\": {e}", c.domain)))?;
+    let mut cookie = StoreCookie::try_from_raw_cookie(&raw.build(), &url)
+        .map_err(|e| GenericError(format!("Invalid cookie \"{}\": {e}", c.name)))?
+        .into_owned();
+
+    cookie.domain = match c.host_only {
+        true => CookieDomain::HostOnly(c.domain.clone()),
+        false => CookieDomain::Suffix(c.domain.clone()),
+    };
+    cookie.path = CookiePath::parse(&c.path)
+        .ok_or(GenericError(format!("Invalid cookie path \"{}\"", c.path)))?;
+    cookie.expires = match expires {
+        Some(t) => CookieExpiration::AtUtc(t),
+        None => CookieExpiration::SessionEnd,
+    };
+
+    Ok(cookie)
+}
+
+fn offset_date_time(t: NaiveDateTime) -> Result<OffsetDateTime> {
+    OffsetDateTime::from_unix_timestamp(t.and_utc().timestamp())
+        .map_err(|e| GenericError(format!("Invalid cookie expiry: {e}")))
+}
+
+/// Select the cookies that should be sent to a URL, following RFC 6265 domain, path, secure,
+/// and expiry matching. Cookies whose names are in `disabled` are skipped.
+pub fn cookies_for_url(
+    cookie_store: &CookieStoreMutex,
+    url: &Url,
+    disabled: &[String],
+) -> Vec<HttpResponseCookie> {
+    // WebSocket upgrades are HTTP requests too, so wss:// counts as secure
+    let is_secure = matches!(url.scheme(), "https" | "wss");
+
+    let store = cookie_store.lock().unwrap();
+    let mut cookies: Vec<HttpResponseCookie> = store
+        .iter_any()
+        .filter(|c| !c.is_expired())
+        .filter(|c| c.domain.matches(url))
+        .filter(|c| c.path.matches(url))
+        .filter(|c| is_secure || !c.secure().unwrap_or(false))
+        .filter(|c| !disabled.iter().any(|d| d == c.name()))
+        .map(|c| HttpResponseCookie {
+            name: c.name().to_string(),
+            value: c.value().to_string(),
+            domain: c.domain.as_cow().map(|d| d.to_string()),
+            path: c.path.to_string(),
+        })
+        .collect();
+
+    // Cookies with longer paths are listed first (RFC 6265 5.4)
+    cookies.sort_by_key(|c| Reverse(c.path.len()));
+    cookies
+}
+
+/// Build the value of the `Cookie` header, if there are any cookies to send
+pub fn cookie_header_value(cookies: &[HttpResponseCookie]) -> Option<String> {
+    if cookies.is_empty() {
+        return None;
+    }
+
+    let pairs: Vec<String> = cookies.iter().map(|c| format!("{}={}", c.name, c.value)).collect();
+    Some(pairs.join("; "))
+}
+
+/// Store any `Set-Cookie` headers from a response received from `url`
+pub fn store_response_cookies(cookie_store: &CookieStoreMutex, headers: &HeaderMap, url: &Url) {
+    let cookies = headers.get_all(SET_COOKIE).iter().filter_map(|v| {
+        let v = v.to_str().ok()?;
+        RawCookie::parse(v).map(|c| c.into_owned()).ok()
+    });
+
+    let mut store = cookie_store.lock().unwrap();
+    store.store_response_cookies(cookies, url);
+}
+
+#[cfg(test)]
+mod cookies_tests {
+    use crate::cookies::{
+        cookie_header_value, cookie_store_from_jar, cookies_for_url, cookies_from_store,
+        store_response_cookies,
+    };
+    use http::header::SET_COOKIE;
+    use http::{HeaderMap, HeaderValue};
+    use reqwest::Url;
+    use reqwest_cookie_store::CookieStoreMutex;
+    use yaak_models::models::{Cookie, CookieJar, CookieSameSite};
+
+    fn store_with(url: &str, set_cookies: &[&str]) -> CookieStoreMutex {
+        let store = CookieStoreMutex::default();
+        let mut headers = HeaderMap::new();
+        for c in set_cookies {
+            headers.append(SET_COOKIE, HeaderValue::from_str(c).unwrap());
+        }
+        store_response_cookies(&store, &headers, &Url::parse(url).unwrap());
+        store
+    }
+
+    fn names(store: &CookieStoreMutex, url: &str, disabled: &[String]) -> Vec<String> {
+        let url = Url::parse(url).unwrap();
+        cookies_for_url(store, &url, disabled).into_iter().map(|c| c.name).collect()
+    }
+
+    #[test]
+    fn matches_domain() {
+        let store =
+            store_with("https://api.example.com/", &["host=1", "suffix=1; Domain=example.com"]);
+        let mut api_names = names(&store, "https://api.example.com/", &[]);
+        api_names.sort();
+        assert_eq!(api_names, vec!["host", "suffix"]);
+        assert_eq!(names(&store, "https://www.example.com/", &[]), vec!["suffix"]);
+        assert!(names(&store, "https://example.org/", &[]).is_empty());
+    }
+
+    #[test]
+    fn matches_path() {
+        let store = store_with("https://example.com/", &["root=1; Path=/", "deep=1; Path=/a/b"]);
+        assert_eq!(names(&store, "https://example.com/a/b/c", &[]), vec!["deep", "root"]);
+        assert_eq!(names(&store, "https://example.com/a", &[]), vec!["root"]);
+        assert_eq!(names(&store, "https://example.com/a/bc", &[]), vec!["root"]);
+    }
+
+    #[test]
+    fn matches_secure() {
+        let store = store_with("https://example.com/", &["s=1; Secure"]);
+        assert_eq!(names(&store, "https://example.com/", &[]), vec!["s"]);
+        assert_eq!(names(&store, "wss://example.com/", &[]), vec!["s"]);
+        assert!(names(&store, "http://example.com/", &[]).is_empty());
+    }
+
+    #[test]
+    fn skips_expired() {
+        let store = store_with(
+            "https://example.com/",
+            &[
+                "old=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT",
+                "new=1; Max-Age=3600",
+            ],
+        );
+        assert_eq!(names(&store, "https://example.com/", &[]), vec!["new"]);
+    }
+
+    #[test]
+    fn skips_disabled() {
+        let store = store_with("https://example.com/", &["a=1", "b=2"]);
+        let url = Url::parse("https://example.com/").unwrap();
+        let cookies = cookies_for_url(&store, &url, &["a".to_string()]);
+        assert_eq!(cookie_header_value(&cookies), Some("b=2".to_string()));
+        assert_eq!(cookie_header_value(&[]), None);
+    }
+
+    #[test]
+    fn converts_losslessly() {
+        let store = store_with(
+            "https://api.example.com/a/b",
+            &[
+                "host=1",
+                "suffix=2; Domain=example.com; Path=/; Secure; HttpOnly; SameSite=Strict",
+                "expiring=3; Expires=Wed, 21 Oct 2099 07:28:00 GMT; SameSite=Lax",
+            ],
+        );
+
+        let mut cookies = cookies_from_store(&store).unwrap();
+        cookies.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(
+            cookies[2],
+            Cookie {
+                name: "suffix".to_string(),
+                value: "2".to_string(),
+                domain: "example.com".to_string(),
+                host_only: false,
+                path: "/".to_string(),
+                expires: None,
+                secure: true,
+                http_only: true,
+                same_site: Some(CookieSameSite::Strict),
+            }
+        );
+        assert!(cookies[1].host_only);
+        assert_eq!(cookies[1].domain, "api.example.com");
+        assert_eq!(cookies[1].path, "/a");
+        assert_eq!(cookies[0].expires.unwrap().to_string(), "2099-10-21 07:28:00");
+
+        // Back to a store and out again should be identical
+        let cookie_jar = CookieJar {
+            cookies: cookies.clone(),
+            ..Default::default()
+        };
+        let store = cookie_store_from_jar(&cookie_jar).unwrap();
+        let mut round_tripped = cookies_from_store(&store).unwrap();
+        round_tripped.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(round_tripped, cookies);
+
+        // And should still match the same URLs
+        let mut sent = names(&store, "https://api.example.com/a/b", &[]);
+        sent.sort();
+        assert_eq!(sent, vec!["expiring", "host", "suffix"]);
+        assert_eq!(names(&store, "https://www.example.com/", &[]), vec!["suffix"]);
+    }
+
+    #[test]
+    fn reads_legacy_cookies() {
+        let cookies: Vec<Cookie> = serde_json::from_str(
+            r#"[{
+                "raw_cookie": "foo=bar; Secure; SameSite=Lax; Path=/",
+                "domain": {"Suffix": "example.com"},
+                "expires": {"AtUtc": "2099-10-21T07:28:00Z"},
+                "path": ["/", true]
+            }]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            cookies,
+            vec![Cookie {
+                name: "foo".to_string(),
+                value: "bar".to_string(),
+                domain: "example.com".to_string(),
+                host_only: false,
+                path: "/".to_string(),
+                expires: Some("2099-10-21T07:28:00".parse().unwrap()),
+                secure: true,
+                http_only: false,
+                same_site: Some(CookieSameSite::Lax),
+            }]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::assertions::read_body_text;
+use cookie::Cookie;
+use regex::Regex;
+use serde_json::Value;
+use yaak_filter::query_jsonpath;
+use yaak_models::models::{
+    HttpExtractedValue, HttpExtractor, HttpExtractorType, HttpResponse, HttpResponseHeader,
+};
+
+/// Pull values out of a finished response with its request's enabled extractors
+pub async fn extract_values(
+    extractors: &[HttpExtractor],
+    response: &HttpResponse,
+    charset_override: Option<&str>,
+) -> Vec<HttpExtractedValue> {
+    let extractors: Vec<&HttpExtractor> = extractors.iter().filter(|e| e.enabled).collect();
+
+    // Only read the body if something needs it, since it can be large
+    let needs_body = extractors.iter().any(|e| {
+        matches!(e.extractor_type, HttpExtractorType::JsonPath | HttpExtractorType::Regex)
+    });
+    let body = match needs_body {
+        true => read_body_text(response, charset_override).await,
+        false => Err(String::new()),
+    };
+
+    let mut values = Vec::new();
+    for extractor in extractors {
+        let source = extractor.source.trim();
+        let value = match extractor.extractor_type {
+            HttpExtractorType::JsonPath => {
+                body.as_ref().map_err(|e| e.clone()).and_then(|b| extract_json_path(b, source))
+            }
+            HttpExtractorType::Header => extract_header(&response.headers, source),
+            HttpExtractorType::Regex => {
+                body.as_ref().map_err(|e| e.clone()).and_then(|b| extract_regex(b, source))
+            }
+            HttpExtractorType::Cookie => extract_cookie(response, source),
+        };
+
+        values.push(match value {
+            Ok(value) => HttpExtractedValue {
+                extractor: extractor.clone(),
+                value: Some(value),
+                error: None,
+            },
+            Err(e) => HttpExtractedValue {
+                extractor: extractor.clone(),
+                value: None,
+                error: Some(e),
+            },
+        });
+    }
+
+    values
+}
+
+fn extract_json_path(body: &str, path: &str) -> Result<String, String> {
+    let json: Value = serde_json::from_str(body).map_err(|e| format!("Body isn't JSON: {e}"))?;
+    let nodes = query_jsonpath(&json, path).map_err(|e| e.to_string())?;
+    match nodes.as_slice() {
+        [] => Err(format!("Nothing matched {path}")),
+        // Strings are used as-is, so they don't end up quoted in the variable
+        [Value::String(s)] => Ok(s.to_string()),
+        [node] => Ok(node.to_string()),
+        nodes => Ok(Value::Array(nodes.iter().map(|n| (*n).clone()).collect()).to_string()),
+    }
+}
+
+fn extract_header(headers: &[HttpResponseHeader], name: &str) -> Result<String, String> {
+    let values: Vec<&str> = headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+        .collect();
+    match values.is_empty() {
+        true => Err(format!("No {name} header")),
+        false => Ok(values.join(", ")),
+    }
+}
+
+fn extract_regex(body: &str, pattern: &str) -> Result<String, String> {
+    let regex = Regex::new(pattern).map_err(|e| e.to_string())?;
+    let captures = regex.captures(body).ok_or(format!("Nothing matched /{pattern}/"))?;
+    let m = captures.get(1).or_else(|| captures.get(0)).unwrap();
+    Ok(m.as_str().to_string())
+}
+
+fn extract_cookie(response: &HttpResponse, name: &str) -> Result<String, String> {
+    // Logins often set cookies on a redirect, so look through every hop, keeping the latest
+    let headers = response.redirects.iter().flat_map(|r| r.headers.iter()).chain(&response.headers);
+    headers
+        .filter(|h| h.name.eq_ignore_ascii_case("set-cookie"))
+        .filter_map(|h| Cookie::parse(h.value.as_str()).ok())
+        .rfind(|c| c.name() == name)
+        .map(|c| c.value().to_string())
+        .ok_or(format!("No {name} cookie was set"))
+}
+
+#[cfg(test)]
+mod extractors_tests {
+    use crate::extractors::extract_values;
+    use yaak_models::models::{
+        HttpExtractor, HttpExtractorType, HttpResponse, HttpResponseHeader, HttpResponseRedirect,
+    };
+
+    fn extractor(extractor_type: HttpExtractorType, source: &str) -> HttpExtractor {
+        HttpExtractor {
+            enabled: true,
+            extractor_type,
+            source: source.to_string(),
+            variable: "v".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn header(name: &str, value: &str) -> HttpResponseHeader {
+        HttpResponseHeader {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn extracts_values() {
+        let path = std::env::temp_dir().join(format!("yaak-extractors-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, r#"{"token":"abc","user":{"id":7},"ids":[1,2]}"#).await.unwrap();
+        let response = HttpResponse {
+            body_path: Some(path.to_string_lossy().to_string()),
+            headers: vec![
+                header("X-Request-Id", "r1"),
+                header("Set-Cookie", "b=2; Path=/"),
+            ],
+            redirects: vec![HttpResponseRedirect {
+                headers: vec![header("set-cookie", "session=s1; HttpOnly")],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let extractors = vec![
+            extractor(HttpExtractorType::JsonPath, "$.token"),
+            extractor(HttpExtractorType::JsonPath, "$.user.id"),
+            extractor(HttpExtractorType::JsonPath, "$.ids[*]"),
+            extractor(HttpExtractorType::JsonPath, "$.missing"),
+            extractor(HttpExtractorType::Header, "x-request-id"),
+            extractor(HttpExtractorType::Regex, r#""token":"(\w+)""#),
+            extractor(HttpExtractorType::Regex, r#"\d+"#),
+            extractor(HttpExtractorType::Cookie, "session"),
+            extractor(HttpExtractorType::Cookie, "nope"),
+        ];
+        let values = extract_values(&extractors, &response, None).await;
+        let values: Vec<Option<&str>> = values.iter().map(|v| v.value.as_deref()).collect();
+        assert_eq!(
+            values,
+            [
+                Some("abc"),
+                Some("7"),
+                Some("[1,2]"),
+                None,
+                Some("r1"),
+                Some("abc"),
+                Some("7"),
+                Some("s1"),
+                None
+            ]
+        );
+    }
+}
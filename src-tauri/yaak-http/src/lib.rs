@@ -1,5 +1,18 @@
 use yaak_models::models::HttpUrlParameter;
 
+pub mod assertions;
+pub mod client;
+pub mod cookie_formats;
+pub mod cookies;
+pub mod error;
+pub mod extractors;
+pub mod progress;
+pub mod response_body;
+pub mod runner;
+pub mod sender;
+pub mod timing;
+pub mod upload;
+
 pub fn apply_path_placeholders(
     url: &str,
     parameters: Vec<HttpUrlParameter>,
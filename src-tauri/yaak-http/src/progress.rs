@@ -0,0 +1,76 @@
+use std::time::{Duration, Instant};
+
+/// Decides when to persist a response's progress while its body downloads. Persisting means a
+/// database write and an event to the window, which is far too slow to do for every chunk of a
+/// large body, so chunks are coalesced until enough time has passed or enough bytes arrived.
+#[derive(Debug, Clone)]
+pub struct ProgressThrottle {
+    interval: Duration,
+    max_pending_bytes: usize,
+    last_persisted: Instant,
+    pending_bytes: usize,
+}
+
+impl ProgressThrottle {
+    pub fn new(interval: Duration, max_pending_bytes: usize) -> Self {
+        Self {
+            interval,
+            max_pending_bytes,
+            last_persisted: Instant::now(),
+            pending_bytes: 0,
+        }
+    }
+
+    /// Record that a chunk arrived, returning whether progress should be persisted now
+    pub fn record(&mut self, bytes: usize) -> bool {
+        self.pending_bytes += bytes;
+        self.pending_bytes >= self.max_pending_bytes
+            || self.last_persisted.elapsed() >= self.interval
+    }
+
+    pub fn persisted(&mut self) {
+        self.last_persisted = Instant::now();
+        self.pending_bytes = 0;
+    }
+
+    /// When progress that hasn't been persisted yet is due, so a stream that goes quiet still
+    /// gets its last chunks persisted. `None` if there's nothing pending.
+    pub fn deadline(&self) -> Option<Instant> {
+        match self.pending_bytes {
+            0 => None,
+            _ => Some(self.last_persisted + self.interval),
+        }
+    }
+}
+
+#[cfg(test)]
+mod progress_tests {
+    use crate::progress::ProgressThrottle;
+    use std::time::Duration;
+
+    #[test]
+    fn coalesces_by_bytes() {
+        let mut progress = ProgressThrottle::new(Duration::from_secs(60), 100);
+        assert_eq!(progress.deadline(), None);
+        assert!(!progress.record(40));
+        assert!(!progress.record(40));
+        assert!(progress.deadline().is_some());
+        assert!(progress.record(40));
+
+        progress.persisted();
+        assert_eq!(progress.deadline(), None);
+        assert!(!progress.record(99));
+    }
+
+    #[test]
+    fn coalesces_by_time() {
+        let mut progress = ProgressThrottle::new(Duration::from_millis(20), usize::MAX);
+        assert!(!progress.record(1));
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(progress.deadline().unwrap() <= std::time::Instant::now());
+        assert!(progress.record(1));
+
+        progress.persisted();
+        assert!(!progress.record(1));
+    }
+}
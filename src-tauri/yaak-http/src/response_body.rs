@@ -0,0 +1,703 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use log::warn;
+use mime_guess::Mime;
+use regex::bytes::{Regex, RegexBuilder};
+use std::io::SeekFrom;
+use tokio::fs;
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, BufReader};
+use yaak_models::models::{
+    HttpResponse, HttpResponseBodyChunk, HttpResponseBodyHexdumpRow, HttpResponseBodyLines,
+    HttpResponseBodyMatch, HttpResponseBodySearch, HttpResponseHeader,
+};
+
+/// How many bytes of a document to look through for a charset declaration, same as browsers
+const PRESCAN_LENGTH: usize = 1024;
+
+/// The most bytes of a body that a chunk or range of lines will hold, so a huge body is never
+/// loaded into memory all at once
+const MAX_READ_LENGTH: u64 = 4 * 1024 * 1024;
+
+/// Lines longer than this are cut off, so a minified document doesn't come back whole
+const MAX_LINE_LENGTH: usize = 64 * 1024;
+const MAX_LINES: u64 = 100_000;
+
+const MAX_HEXDUMP_LENGTH: u64 = 64 * 1024;
+const HEXDUMP_WIDTH: usize = 16;
+
+const SEARCH_CHUNK_LENGTH: usize = 1024 * 1024;
+const MAX_SEARCH_MATCHES: usize = 1000;
+
+/// Bytes of context on either side of a search match to include in its preview
+const PREVIEW_CONTEXT: usize = 40;
+
+/// A response body decoded to text
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedBody {
+    pub text: String,
+    // Name of the charset the body was decoded with
+    pub charset: &'static str,
+    // Some bytes weren't valid in the charset and were replaced with U+FFFD
+    pub had_errors: bool,
+}
+
+/// Read a response's body from disk and decode it. Returns `None` if the response has no body.
+pub async fn read_response_body(
+    response: &HttpResponse,
+    charset_override: Option<&str>,
+) -> Result<Option<DecodedBody>> {
+    let body_path = match &response.body_path {
+        None => return Ok(None),
+        Some(p) => p,
+    };
+
+    let bytes = fs::read(body_path).await?;
+    Ok(Some(decode_body(&bytes, &response.headers, charset_override)))
+}
+
+/// Decode a body with the given charset, or the one detected from the headers and content
+pub fn decode_body(
+    bytes: &[u8],
+    headers: &[HttpResponseHeader],
+    charset_override: Option<&str>,
+) -> DecodedBody {
+    let encoding = resolve_charset(bytes, headers, charset_override);
+    let (text, had_errors) = encoding.decode_with_bom_removal(bytes);
+    DecodedBody {
+        text: text.into_owned(),
+        charset: encoding.name(),
+        had_errors,
+    }
+}
+
+/// The charset to decode a body with, given its first bytes
+fn resolve_charset(
+    head: &[u8],
+    headers: &[HttpResponseHeader],
+    charset_override: Option<&str>,
+) -> &'static Encoding {
+    charset_override
+        .filter(|label| !label.is_empty())
+        .and_then(|label| {
+            let encoding = Encoding::for_label(label.as_bytes());
+            if encoding.is_none() {
+                warn!("Ignoring unknown charset override {label}");
+            }
+            encoding
+        })
+        .unwrap_or_else(|| detect_charset(head, headers))
+}
+
+/// Detect a body's charset from its BOM, the `Content-Type` header, or a declaration in the
+/// document itself, in that order. Falls back to UTF-8.
+pub fn detect_charset(bytes: &[u8], headers: &[HttpResponseHeader]) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return encoding;
+    }
+
+    charset_from_content_type(headers).or_else(|| charset_from_document(bytes)).unwrap_or(UTF_8)
+}
+
+fn charset_from_content_type(headers: &[HttpResponseHeader]) -> Option<&'static Encoding> {
+    let content_type = headers.iter().find(|h| h.name.eq_ignore_ascii_case("content-type"))?;
+    let mime = content_type.value.parse::<Mime>().ok()?;
+    let charset = mime.get_param(mime_guess::mime::CHARSET)?;
+    Encoding::for_label(charset.as_str().as_bytes())
+}
+
+/// Look for an XML declaration or HTML `<meta>` charset near the start of the document
+fn charset_from_document(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(PRESCAN_LENGTH)];
+    let head = String::from_utf8_lossy(head).to_ascii_lowercase();
+
+    let label = if head.starts_with("<?xml") {
+        let declaration = &head[..head.find("?>")?];
+        attribute_value(declaration, "encoding")?
+    } else {
+        head.match_indices("<meta").find_map(|(i, _)| {
+            let tag = &head[i..];
+            let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+            // Covers both <meta charset> and <meta http-equiv="Content-Type" content>
+            attribute_value(tag, "charset")
+        })?
+    };
+
+    let encoding = Encoding::for_label(label.as_bytes())?;
+
+    // A document can't declare itself UTF-16 in ASCII, so the declaration must be wrong
+    match encoding == encoding_rs::UTF_16LE || encoding == encoding_rs::UTF_16BE {
+        true => Some(UTF_8),
+        false => Some(encoding),
+    }
+}
+
+fn attribute_value<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+    let rest = &s[s.find(name)? + name.len()..];
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let value = match rest.chars().next()? {
+        q @ ('"' | '\'') => {
+            let rest = &rest[1..];
+            &rest[..rest.find(q)?]
+        }
+        _ => {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | ';' | '/' | '>'))
+                .unwrap_or(rest.len());
+            &rest[..end]
+        }
+    };
+
+    match value.trim() {
+        "" => None,
+        v => Some(v),
+    }
+}
+
+/// Read part of a response's body, starting `offset` bytes in
+pub async fn read_body_chunk(
+    response: &HttpResponse,
+    charset_override: Option<&str>,
+    offset: u64,
+    length: u64,
+) -> Result<HttpResponseBodyChunk> {
+    let (mut file, total_length) = open_body(response).await?;
+    let encoding = body_charset(&mut file, response, charset_override).await?;
+
+    let offset = offset.min(total_length);
+    let length = length.min(MAX_READ_LENGTH).min(total_length - offset);
+    let mut bytes = vec![0; length as usize];
+    file.seek(SeekFrom::Start(offset)).await?;
+    file.read_exact(&mut bytes).await?;
+
+    // End on a character boundary so chunks read back to back decode the same as the whole body
+    if offset + length < total_length {
+        let complete = complete_length(encoding, &bytes);
+        if complete > 0 {
+            bytes.truncate(complete);
+        }
+    }
+
+    Ok(HttpResponseBodyChunk {
+        offset,
+        length: bytes.len() as u64,
+        total_length,
+        charset: encoding.name().to_string(),
+        text: decode_at(encoding, &bytes, offset),
+    })
+}
+
+/// Read `line_count` lines of a response's body, starting at the zero-based `start_line`
+pub async fn read_body_lines(
+    response: &HttpResponse,
+    charset_override: Option<&str>,
+    start_line: u64,
+    line_count: u64,
+) -> Result<HttpResponseBodyLines> {
+    let (mut file, _) = open_body(response).await?;
+    let encoding = body_charset(&mut file, response, charset_override).await?;
+    require_ascii_compatible(encoding)?;
+    let mut reader = BufReader::new(file);
+
+    // Skip to the first line without holding on to any of the ones before it
+    let mut offset = 0;
+    let mut line = 0;
+    while line < start_line {
+        let buf = reader.fill_buf().await?;
+        if buf.is_empty() {
+            break;
+        }
+        let (consumed, newline) = match buf.iter().position(|b| *b == b'\n') {
+            Some(i) => (i + 1, true),
+            None => (buf.len(), false),
+        };
+        reader.consume(consumed);
+        offset += consumed as u64;
+        if newline {
+            line += 1;
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut end_offset = offset;
+    let mut read_length = 0;
+    let mut eof = false;
+    let line_count = line_count.min(MAX_LINES);
+    while (lines.len() as u64) < line_count && read_length < MAX_READ_LENGTH {
+        let line_offset = end_offset;
+        let mut bytes = Vec::new();
+        let mut newline = false;
+        loop {
+            let buf = reader.fill_buf().await?;
+            if buf.is_empty() {
+                break;
+            }
+            let (consumed, found) = match buf.iter().position(|b| *b == b'\n') {
+                Some(i) => (i + 1, true),
+                None => (buf.len(), false),
+            };
+            let room = MAX_LINE_LENGTH.saturating_sub(bytes.len());
+            bytes.extend_from_slice(&buf[..consumed.min(room)]);
+            reader.consume(consumed);
+            end_offset += consumed as u64;
+            if found {
+                newline = true;
+                break;
+            }
+        }
+
+        if !newline {
+            eof = true;
+            if bytes.is_empty() {
+                break;
+            }
+        }
+
+        read_length += bytes.len() as u64;
+        let text = decode_at(encoding, &bytes, line_offset);
+        lines.push(text.trim_end_matches('\n').trim_end_matches('\r').to_string());
+        if eof {
+            break;
+        }
+    }
+
+    if !eof {
+        eof = reader.fill_buf().await?.is_empty();
+    }
+
+    Ok(HttpResponseBodyLines {
+        start_line: line,
+        lines,
+        offset,
+        end_offset,
+        eof,
+    })
+}
+
+/// Find occurrences of `query` in a response's body, starting `offset` bytes in. `line` is the
+/// zero-based line that `offset` is on, so matches can be given line numbers without reading
+/// the body from the start. Case-insensitive searches only fold ASCII letters.
+pub async fn search_body(
+    response: &HttpResponse,
+    charset_override: Option<&str>,
+    query: &str,
+    case_sensitive: bool,
+    offset: u64,
+    line: u64,
+) -> Result<HttpResponseBodySearch> {
+    let (mut file, _) = open_body(response).await?;
+    let encoding = body_charset(&mut file, response, charset_override).await?;
+    require_ascii_compatible(encoding)?;
+
+    let mut search = HttpResponseBodySearch::default();
+    let (needle, _, _) = encoding.encode(query);
+    if needle.is_empty() {
+        return Ok(search);
+    }
+    let pattern = byte_pattern(&needle, case_sensitive);
+
+    file.seek(SeekFrom::Start(offset)).await?;
+    let mut chunk = vec![0; SEARCH_CHUNK_LENGTH];
+    let mut window: Vec<u8> = Vec::new();
+    let mut window_offset = offset;
+    let mut window_line = line;
+    let mut search_from = 0;
+    loop {
+        let n = file.read(&mut chunk).await?;
+        let eof = n == 0;
+        window.extend_from_slice(&chunk[..n]);
+
+        // Matches have to start before the last few bytes, which might be the beginning of a
+        // match that finishes in the next chunk
+        let searchable = match eof {
+            true => window.len(),
+            false => window.len().saturating_sub(needle.len() - 1),
+        };
+
+        let mut line = window_line;
+        let mut counted_to = 0;
+        let mut resume = search_from;
+        for m in pattern.find_iter(&window[search_from..]) {
+            let start = search_from + m.start();
+            if start >= searchable {
+                break;
+            }
+            line += count_newlines(&window[counted_to..start]);
+            counted_to = start;
+            search.matches.push(HttpResponseBodyMatch {
+                offset: window_offset + start as u64,
+                length: m.len() as u64,
+                line,
+                preview: preview(encoding, &window, start, search_from + m.end()),
+            });
+
+            if search.matches.len() >= MAX_SEARCH_MATCHES {
+                let end = search_from + m.end();
+                search.next_offset = Some(window_offset + end as u64);
+                search.next_line = Some(line + count_newlines(&window[start..end]));
+                return Ok(search);
+            }
+            resume = search_from + m.end();
+        }
+
+        if eof {
+            return Ok(search);
+        }
+
+        // Keep what's left to search, plus enough before it for previews
+        let resume = resume.max(searchable);
+        let keep_from = resume.saturating_sub(PREVIEW_CONTEXT);
+        window_line += count_newlines(&window[..keep_from]);
+        window_offset += keep_from as u64;
+        window.drain(..keep_from);
+        search_from = resume - keep_from;
+    }
+}
+
+/// Dump part of a response's body as rows of hex bytes
+pub async fn hexdump_body(
+    response: &HttpResponse,
+    offset: u64,
+    length: u64,
+) -> Result<Vec<HttpResponseBodyHexdumpRow>> {
+    let (mut file, total_length) = open_body(response).await?;
+    let offset = offset.min(total_length);
+    let length = length.min(MAX_HEXDUMP_LENGTH).min(total_length - offset);
+    let mut bytes = vec![0; length as usize];
+    file.seek(SeekFrom::Start(offset)).await?;
+    file.read_exact(&mut bytes).await?;
+    Ok(hexdump(offset, &bytes))
+}
+
+async fn open_body(response: &HttpResponse) -> Result<(File, u64)> {
+    let body_path = match &response.body_path {
+        None => return Err(GenericError("Response has no body".to_string())),
+        Some(p) => p,
+    };
+    let file = File::open(body_path).await?;
+    let length = file.metadata().await?.len();
+    Ok((file, length))
+}
+
+/// Detect the charset of a body from its first bytes, leaving the file at the start
+async fn body_charset(
+    file: &mut File,
+    response: &HttpResponse,
+    charset_override: Option<&str>,
+) -> Result<&'static Encoding> {
+    let mut head = Vec::with_capacity(PRESCAN_LENGTH);
+    (&mut *file).take(PRESCAN_LENGTH as u64).read_to_end(&mut head).await?;
+    file.seek(SeekFrom::Start(0)).await?;
+    Ok(resolve_charset(&head, &response.headers, charset_override))
+}
+
+/// Lines and search work on raw bytes, which only lines up with the text if ASCII characters
+/// are encoded as themselves
+fn require_ascii_compatible(encoding: &'static Encoding) -> Result<()> {
+    match encoding.is_ascii_compatible() {
+        true => Ok(()),
+        false => Err(GenericError(format!("Can't search or split {} bodies", encoding.name()))),
+    }
+}
+
+/// Decode bytes from partway through a body, where only the start can have a BOM
+fn decode_at(encoding: &'static Encoding, bytes: &[u8], offset: u64) -> String {
+    let (text, _) = match offset {
+        0 => encoding.decode_with_bom_removal(bytes),
+        _ => encoding.decode_without_bom_handling(bytes),
+    };
+    text.into_owned()
+}
+
+/// How many bytes of `bytes` make up whole characters, leaving off a character that's cut off
+/// at the end
+fn complete_length(encoding: &'static Encoding, bytes: &[u8]) -> usize {
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        return bytes.len() & !1;
+    }
+    if encoding != UTF_8 {
+        return bytes.len();
+    }
+
+    let lead = bytes.iter().rev().take(4).position(|b| b & 0xC0 != 0x80);
+    if let Some(back) = lead {
+        let start = bytes.len() - 1 - back;
+        let width = match bytes[start] {
+            b if b >= 0xF0 => 4,
+            b if b >= 0xE0 => 3,
+            b if b >= 0xC0 => 2,
+            _ => 1,
+        };
+        if start + width > bytes.len() {
+            return start;
+        }
+    }
+    bytes.len()
+}
+
+/// A regex matching the exact bytes of `needle`, which might not be UTF-8
+fn byte_pattern(needle: &[u8], case_sensitive: bool) -> Regex {
+    let pattern: String = needle.iter().map(|b| format!("\\x{b:02x}")).collect();
+    RegexBuilder::new(&pattern)
+        .unicode(false)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .expect("Escaped bytes are always a valid pattern")
+}
+
+fn count_newlines(bytes: &[u8]) -> u64 {
+    bytes.iter().filter(|b| **b == b'\n').count() as u64
+}
+
+/// The text around a match, without crossing onto other lines
+fn preview(encoding: &'static Encoding, window: &[u8], start: usize, end: usize) -> String {
+    let before = &window[start.saturating_sub(PREVIEW_CONTEXT)..start];
+    let before = match before.iter().rposition(|b| *b == b'\n') {
+        Some(i) => &before[i + 1..],
+        None => before,
+    };
+    let after = &window[end..window.len().min(end + PREVIEW_CONTEXT)];
+    let after = match after.iter().position(|b| *b == b'\n') {
+        Some(i) => &after[..i],
+        None => after,
+    };
+
+    let from = start - before.len();
+    let to = end + after.len();
+    let (text, _) = encoding.decode_without_bom_handling(&window[from..to]);
+
+    // The context can cut characters in half, which decode as replacement characters
+    text.trim_matches('\u{FFFD}').trim_end_matches('\r').to_string()
+}
+
+fn hexdump(offset: u64, bytes: &[u8]) -> Vec<HttpResponseBodyHexdumpRow> {
+    bytes
+        .chunks(HEXDUMP_WIDTH)
+        .enumerate()
+        .map(|(i, row)| {
+            let hex: Vec<String> = row.iter().map(|b| format!("{b:02x}")).collect();
+            let hex = match hex.len() > HEXDUMP_WIDTH / 2 {
+                true => {
+                    let (left, right) = hex.split_at(HEXDUMP_WIDTH / 2);
+                    format!("{}  {}", left.join(" "), right.join(" "))
+                }
+                false => hex.join(" "),
+            };
+            let ascii = row
+                .iter()
+                .map(|b| match b.is_ascii_graphic() || *b == b' ' {
+                    true => *b as char,
+                    false => '.',
+                })
+                .collect();
+            HttpResponseBodyHexdumpRow {
+                offset: offset + (i * HEXDUMP_WIDTH) as u64,
+                hex,
+                ascii,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod response_body_tests {
+    use crate::response_body::{
+        decode_body, detect_charset, hexdump, read_body_chunk, read_body_lines, search_body,
+        SEARCH_CHUNK_LENGTH,
+    };
+    use yaak_models::models::{HttpResponse, HttpResponseHeader};
+
+    fn content_type(value: &str) -> Vec<HttpResponseHeader> {
+        vec![HttpResponseHeader {
+            name: "Content-Type".to_string(),
+            value: value.to_string(),
+        }]
+    }
+
+    async fn body_file(contents: &[u8]) -> HttpResponse {
+        let path = std::env::temp_dir().join(format!("yaak-body-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, contents).await.unwrap();
+        HttpResponse {
+            body_path: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        }
+    }
+
+    async fn remove_body_file(response: HttpResponse) {
+        let _ = tokio::fs::remove_file(response.body_path.unwrap()).await;
+    }
+
+    #[test]
+    fn defaults_to_utf8() {
+        let decoded = decode_body("héllo ✓".as_bytes(), &[], None);
+        assert_eq!(decoded.text, "héllo ✓");
+        assert_eq!(decoded.charset, "UTF-8");
+        assert!(!decoded.had_errors);
+    }
+
+    #[test]
+    fn detects_content_type_charset() {
+        let headers = content_type("text/plain; charset=ISO-8859-1");
+        let decoded = decode_body(b"caf\xE9", &headers, None);
+        assert_eq!(decoded.text, "café");
+        assert_eq!(decoded.charset, "windows-1252");
+
+        let headers = content_type("text/plain; charset=\"Shift_JIS\"");
+        assert_eq!(decode_body(b"\x82\xA0", &headers, None).text, "あ");
+    }
+
+    #[test]
+    fn detects_bom() {
+        // The BOM wins over the header
+        let headers = content_type("text/plain; charset=windows-1252");
+        let decoded = decode_body(b"\xFF\xFEh\0i\0", &headers, None);
+        assert_eq!(decoded.text, "hi");
+        assert_eq!(decoded.charset, "UTF-16LE");
+
+        let decoded = decode_body("\u{feff}café".as_bytes(), &headers, None);
+        assert_eq!(decoded.text, "café");
+        assert_eq!(decoded.charset, "UTF-8");
+    }
+
+    #[test]
+    fn detects_document_declarations() {
+        let html =
+            b"<!doctype html><head><meta charset=\"windows-1251\"><title>\xCF\xF0\xE8</title>";
+        assert_eq!(decode_body(html, &[], None).charset, "windows-1251");
+
+        let html = b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=EUC-JP\">";
+        assert_eq!(detect_charset(html, &[]).name(), "EUC-JP");
+
+        let xml = b"<?xml version='1.0' encoding='ISO-8859-2'?><a>\xB1</a>";
+        assert_eq!(
+            decode_body(xml, &[], None).text,
+            "<?xml version='1.0' encoding='ISO-8859-2'?><a>ą</a>"
+        );
+
+        // UTF-16 can't be declared in an ASCII-compatible document
+        let html = b"<meta charset=utf-16>";
+        assert_eq!(detect_charset(html, &[]).name(), "UTF-8");
+
+        // The header wins over the document
+        let headers = content_type("text/html; charset=utf-8");
+        assert_eq!(detect_charset(b"<meta charset=koi8-r>", &headers).name(), "UTF-8");
+    }
+
+    #[test]
+    fn uses_override() {
+        let headers = content_type("text/plain; charset=utf-8");
+        let decoded = decode_body(b"\x82\xA0", &headers, Some("shift_jis"));
+        assert_eq!(decoded.text, "あ");
+        assert_eq!(decoded.charset, "Shift_JIS");
+
+        // Unknown labels fall back to detection
+        let decoded = decode_body(b"\x82\xA0", &headers, Some("nope"));
+        assert_eq!(decoded.charset, "UTF-8");
+        assert!(decoded.had_errors);
+    }
+
+    #[tokio::test]
+    async fn reads_chunks_on_character_boundaries() {
+        let text = "héllo wörld ✓";
+        let response = body_file(text.as_bytes()).await;
+
+        let chunk = read_body_chunk(&response, None, 0, 2).await.unwrap();
+        assert_eq!(chunk.text, "h");
+        assert_eq!(chunk.length, 1);
+        assert_eq!(chunk.total_length, text.len() as u64);
+
+        let mut offset = 0;
+        let mut joined = String::new();
+        while offset < chunk.total_length {
+            let chunk = read_body_chunk(&response, None, offset, 3).await.unwrap();
+            joined.push_str(&chunk.text);
+            offset += chunk.length;
+        }
+        assert_eq!(joined, text);
+
+        remove_body_file(response).await;
+    }
+
+    #[tokio::test]
+    async fn reads_line_ranges() {
+        let response = body_file(b"zero\r\none\ntwo\nthree").await;
+
+        let lines = read_body_lines(&response, None, 1, 2).await.unwrap();
+        assert_eq!(lines.lines, vec!["one", "two"]);
+        assert_eq!(lines.start_line, 1);
+        assert_eq!((lines.offset, lines.end_offset), (6, 14));
+        assert!(!lines.eof);
+
+        let lines = read_body_lines(&response, None, 3, 10).await.unwrap();
+        assert_eq!(lines.lines, vec!["three"]);
+        assert!(lines.eof);
+
+        let lines = read_body_lines(&response, None, 10, 1).await.unwrap();
+        assert!(lines.lines.is_empty());
+        assert!(lines.eof);
+
+        remove_body_file(response).await;
+    }
+
+    #[tokio::test]
+    async fn searches_across_chunks() {
+        // The first match straddles the end of the first chunk that's read
+        let mut body = vec![b'x'; SEARCH_CHUNK_LENGTH - 3];
+        body.extend_from_slice(b"needle\nthe NEEDLE!");
+        let response = body_file(&body).await;
+
+        let search = search_body(&response, None, "needle", false, 0, 0).await.unwrap();
+        assert_eq!(search.matches.len(), 2);
+        assert_eq!(search.matches[0].offset, SEARCH_CHUNK_LENGTH as u64 - 3);
+        assert_eq!(search.matches[0].line, 0);
+        assert_eq!(search.matches[1].line, 1);
+        assert_eq!(search.matches[1].preview, "the NEEDLE!");
+        assert_eq!(search.next_offset, None);
+
+        let search = search_body(&response, None, "NEEDLE", true, 0, 0).await.unwrap();
+        assert_eq!(search.matches.len(), 1);
+        assert_eq!(search.matches[0].line, 1);
+
+        remove_body_file(response).await;
+    }
+
+    #[tokio::test]
+    async fn pages_through_search_matches() {
+        let response = body_file("ab\n".repeat(1500).as_bytes()).await;
+
+        let search = search_body(&response, None, "ab", true, 0, 0).await.unwrap();
+        assert_eq!(search.matches.len(), 1000);
+        assert_eq!(search.next_offset, Some(2999));
+        assert_eq!(search.next_line, Some(999));
+
+        let search = search_body(&response, None, "ab", true, 2999, 999).await.unwrap();
+        assert_eq!(search.matches.len(), 500);
+        assert_eq!(search.matches[0].offset, 3000);
+        assert_eq!(search.matches[0].line, 1000);
+        assert_eq!(search.matches[0].preview, "ab");
+
+        remove_body_file(response).await;
+    }
+
+    #[tokio::test]
+    async fn refuses_to_split_utf16() {
+        let response = body_file(b"\xFF\xFEh\0i\0").await;
+        assert!(search_body(&response, None, "hi", true, 0, 0).await.is_err());
+        assert!(read_body_lines(&response, None, 0, 1).await.is_err());
+        assert_eq!(read_body_chunk(&response, None, 0, 10).await.unwrap().text, "hi");
+        remove_body_file(response).await;
+    }
+
+    #[test]
+    fn dumps_hex() {
+        let rows = hexdump(16, b"Hello, world!\x00\x01\x02\xFF");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].offset, 16);
+        assert_eq!(rows[0].hex, "48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 00 01 02");
+        assert_eq!(rows[0].ascii, "Hello, world!...");
+        assert_eq!(rows[1].offset, 32);
+        assert_eq!(rows[1].hex, "ff");
+        assert_eq!(rows[1].ascii, ".");
+    }
+}
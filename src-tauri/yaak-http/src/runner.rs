@@ -0,0 +1,562 @@
+use chrono::Utc;
+use serde_json::Value;
+use std::fmt::Write;
+use std::future::Future;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use yaak_models::models::{
+    EnvironmentVariable, Folder, HttpAssertion, HttpAssertionType, HttpRequest, HttpResponse,
+    HttpRunReport, HttpRunResult,
+};
+
+/// How to run the requests of a folder or workspace
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Skip the remaining requests once one fails
+    pub stop_on_failure: bool,
+    /// How long to wait between sending requests
+    pub delay: Duration,
+    /// Rows of variables from a data file. When there are any, every request is sent once per
+    /// row with its variables overriding the environment's.
+    pub data: Vec<Vec<EnvironmentVariable>>,
+}
+
+/// Formats a run report can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    JUnit,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ReportFormat::Json),
+            "junit" | "xml" => Ok(ReportFormat::JUnit),
+            _ => Err(format!("Unknown report format \"{s}\"")),
+        }
+    }
+}
+
+/// All HTTP requests under the given folder (or the whole workspace), in the same order they
+/// appear in the sidebar, which is by `sort_priority` with folders expanded in place.
+pub fn http_requests_in_order(
+    folders: &[Folder],
+    http_requests: &[HttpRequest],
+    folder_id: Option<&str>,
+) -> Vec<HttpRequest> {
+    enum Item<'a> {
+        Folder(&'a Folder),
+        Request(&'a HttpRequest),
+    }
+
+    let mut children = Vec::new();
+    for f in folders.iter().filter(|f| f.folder_id.as_deref() == folder_id) {
+        children.push((f.sort_priority, Item::Folder(f)));
+    }
+    for r in http_requests.iter().filter(|r| r.folder_id.as_deref() == folder_id) {
+        children.push((r.sort_priority, Item::Request(r)));
+    }
+    children.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    let mut requests = Vec::new();
+    for (_, item) in children {
+        match item {
+            Item::Folder(f) => {
+                requests.extend(http_requests_in_order(folders, http_requests, Some(&f.id)))
+            }
+            Item::Request(r) => requests.push(r.to_owned()),
+        }
+    }
+
+    requests
+}
+
+/// Whether a response counts as passing. Requests with assertions pass if they all did, and
+/// the rest pass if they got a non-error status.
+pub fn response_passed(response: &HttpResponse) -> bool {
+    if response.error.is_some() {
+        return false;
+    }
+    match response.assertion_results.is_empty() {
+        true => response.status > 0 && response.status < 400,
+        false => response.assertion_results.iter().all(|r| r.passed),
+    }
+}
+
+/// Send requests one after another with `send`, along with the variables of the data row they're
+/// being sent for, collecting how each went. The returned report only has the timing and results
+/// filled in, so the caller should add what was run.
+pub async fn run_http_requests<F, Fut>(
+    requests: Vec<HttpRequest>,
+    options: &RunOptions,
+    mut send: F,
+) -> HttpRunReport
+where
+    F: FnMut(HttpRequest, Vec<EnvironmentVariable>) -> Fut,
+    Fut: Future<Output = Result<HttpResponse, String>>,
+{
+    let started_at = Utc::now().naive_utc();
+    let start = Instant::now();
+
+    // Without a data file, everything is sent once with no extra variables
+    let no_data = vec![Vec::new()];
+    let rows = if options.data.is_empty() { &no_data } else { &options.data };
+
+    let mut results = Vec::new();
+    let mut stopped = false;
+    let mut sent_any = false;
+    for (i, row) in rows.iter().enumerate() {
+        // Extracted values are sent with the rest of the row's requests. That includes ones
+        // saved to an environment, which was loaded before the run started.
+        let mut extracted: Vec<EnvironmentVariable> = Vec::new();
+        let iteration = (!options.data.is_empty()).then_some(i as i32);
+        for request in &requests {
+            let name = request_name(request);
+            if stopped {
+                results.push(HttpRunResult {
+                    request_id: request.id.clone(),
+                    name,
+                    iteration,
+                    passed: false,
+                    response: None,
+                });
+                continue;
+            }
+
+            if sent_any && !options.delay.is_zero() {
+                tokio::time::sleep(options.delay).await;
+            }
+            sent_any = true;
+
+            let variables = row.iter().chain(&extracted).cloned().collect();
+            let response = match send(request.clone(), variables).await {
+                Ok(r) => r,
+                Err(e) => HttpResponse {
+                    request_id: request.id.clone(),
+                    error: Some(e),
+                    ..Default::default()
+                },
+            };
+            for v in &response.extracted_values {
+                if let Some(value) = &v.value {
+                    extracted.retain(|e| e.name != v.extractor.variable);
+                    extracted.push(variable(&v.extractor.variable, value));
+                }
+            }
+
+            let passed = response_passed(&response);
+            stopped = !passed && options.stop_on_failure;
+            results.push(HttpRunResult {
+                request_id: request.id.clone(),
+                name,
+                iteration,
+                passed,
+                response: Some(response),
+            });
+        }
+    }
+
+    HttpRunReport {
+        started_at,
+        elapsed: start.elapsed().as_millis() as i32,
+        results,
+        ..Default::default()
+    }
+}
+
+/// Read the rows of a CSV or JSON data file as variables named by their column or property.
+/// CSV files need a header row, and JSON files must be an array of objects.
+pub fn read_data_file(path: &Path) -> Result<Vec<Vec<EnvironmentVariable>>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read data file {path:?}: {e}"))?;
+    let extension = path.extension().map(|e| e.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        Some("json") => parse_json_rows(&contents),
+        Some("csv") => parse_csv_rows(&contents),
+        _ if contents.trim_start().starts_with('[') => parse_json_rows(&contents),
+        _ => parse_csv_rows(&contents),
+    }
+}
+
+fn parse_csv_rows(contents: &str) -> Result<Vec<Vec<EnvironmentVariable>>, String> {
+    let mut reader = csv::Reader::from_reader(contents.as_bytes());
+    let headers = reader.headers().map_err(|e| format!("Invalid CSV: {e}"))?.clone();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Invalid CSV: {e}"))?;
+        rows.push(headers.iter().zip(record.iter()).map(|(n, v)| variable(n, v)).collect());
+    }
+
+    Ok(rows)
+}
+
+fn parse_json_rows(contents: &str) -> Result<Vec<Vec<EnvironmentVariable>>, String> {
+    let rows: Vec<serde_json::Map<String, Value>> = serde_json::from_str(contents)
+        .map_err(|e| format!("Data file must be a JSON array of objects: {e}"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|(name, value)| match value {
+                    Value::String(s) => variable(&name, &s),
+                    Value::Null => variable(&name, ""),
+                    // Nested values are passed as JSON, to be used in JSON bodies
+                    v => variable(&name, &v.to_string()),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+fn variable(name: &str, value: &str) -> EnvironmentVariable {
+    EnvironmentVariable {
+        enabled: true,
+        name: name.to_string(),
+        value: value.to_string(),
+        id: None,
+    }
+}
+
+/// Write a run report as pretty JSON or as JUnit XML, for CI systems to pick up
+pub fn format_report(report: &HttpRunReport, format: ReportFormat) -> Result<String, String> {
+    match format {
+        ReportFormat::Json => serde_json::to_string_pretty(report).map_err(|e| e.to_string()),
+        ReportFormat::JUnit => Ok(junit_report(report)),
+    }
+}
+
+fn junit_report(report: &HttpRunReport) -> String {
+    let count = |f: fn(&HttpRunResult) -> bool| report.results.iter().filter(|r| f(r)).count();
+    let tests = report.results.len();
+    let errors = count(|r| r.response.as_ref().is_some_and(|r| r.error.is_some()));
+    let skipped = count(|r| r.response.is_none());
+    let failures = count(|r| !r.passed) - errors - skipped;
+    let name = escape_xml(&report.name);
+    let time = seconds(report.elapsed);
+
+    let counts = format!(
+        r#"tests="{tests}" failures="{failures}" errors="{errors}" skipped="{skipped}" time="{time}""#
+    );
+    let timestamp = report.started_at.format("%Y-%m-%dT%H:%M:%S");
+
+    let mut out = String::new();
+    let _ = writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(out, r#"<testsuites name="{name}" {counts}>"#);
+    let _ = writeln!(out, r#"  <testsuite name="{name}" {counts} timestamp="{timestamp}">"#);
+
+    for result in &report.results {
+        let case_name = match result.iteration {
+            Some(i) => escape_xml(&format!("{} [row {}]", result.name, i + 1)),
+            None => escape_xml(&result.name),
+        };
+        let response = match &result.response {
+            None => {
+                let _ = writeln!(out, r#"    <testcase name="{case_name}" classname="{name}">"#);
+                let _ = writeln!(out, "      <skipped/>");
+                let _ = writeln!(out, "    </testcase>");
+                continue;
+            }
+            Some(r) => r,
+        };
+
+        let _ = writeln!(
+            out,
+            r#"    <testcase name="{case_name}" classname="{name}" time="{}">"#,
+            seconds(response.elapsed)
+        );
+        if let Some(error) = &response.error {
+            let _ = writeln!(out, r#"      <error message="{}"/>"#, escape_xml(error));
+        } else if !result.passed {
+            let failed: Vec<String> = match response.assertion_results.is_empty() {
+                true => vec![format!("Status {}", response.status)],
+                false => response
+                    .assertion_results
+                    .iter()
+                    .filter(|r| !r.passed)
+                    .map(|r| {
+                        let found = match (&r.error, &r.actual) {
+                            (Some(e), _) => e.to_string(),
+                            (None, Some(a)) => format!("got {a}"),
+                            (None, None) => "got nothing".to_string(),
+                        };
+                        format!("{}: {found}", describe_assertion(&r.assertion))
+                    })
+                    .collect(),
+            };
+            let _ = writeln!(
+                out,
+                r#"      <failure message="{}" type="assertion">{}</failure>"#,
+                escape_xml(failed.first().map(String::as_str).unwrap_or_default()),
+                escape_xml(&failed.join("\n")),
+            );
+        }
+        if !response.assertion_results.is_empty() {
+            let _ = writeln!(out, "      <properties>");
+            for r in &response.assertion_results {
+                let status = if r.passed { "passed" } else { "failed" };
+                let _ = writeln!(
+                    out,
+                    r#"        <property name="{}" value="{status}"/>"#,
+                    escape_xml(&describe_assertion(&r.assertion))
+                );
+            }
+            let _ = writeln!(out, "      </properties>");
+        }
+        let _ = writeln!(out, "    </testcase>");
+    }
+
+    let _ = writeln!(out, "  </testsuite>");
+    let _ = writeln!(out, "</testsuites>");
+    out
+}
+
+/// A short description of what an assertion checks, like `Status is 2xx`
+pub fn describe_assertion(assertion: &HttpAssertion) -> String {
+    let HttpAssertion { target, value, .. } = assertion;
+    match assertion.assertion_type {
+        HttpAssertionType::Status => format!("Status is {value}"),
+        HttpAssertionType::Header if value.is_empty() => format!("Header {target} exists"),
+        HttpAssertionType::Header => format!("Header {target} is {value}"),
+        HttpAssertionType::JsonPathEquals => format!("{target} equals {value}"),
+        HttpAssertionType::JsonPathExists => format!("{target} exists"),
+        HttpAssertionType::BodyMatches => format!("Body matches /{value}/"),
+        HttpAssertionType::ResponseTimeUnder => format!("Response time under {value} ms"),
+        HttpAssertionType::JsonSchema => "Body matches JSON Schema".to_string(),
+        HttpAssertionType::Script => target.to_string(),
+    }
+}
+
+fn request_name(request: &HttpRequest) -> String {
+    match request.name.is_empty() {
+        true => request.url.clone(),
+        false => request.name.clone(),
+    }
+}
+
+fn seconds(millis: i32) -> String {
+    format!("{:.3}", millis as f64 / 1000.0)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod runner_tests {
+    use crate::runner::{
+        format_report, http_requests_in_order, read_data_file, run_http_requests, ReportFormat,
+        RunOptions,
+    };
+    use yaak_models::models::{
+        EnvironmentVariable, Folder, HttpAssertion, HttpAssertionResult, HttpAssertionType,
+        HttpExtractedValue, HttpExtractor, HttpRequest, HttpResponse,
+    };
+
+    fn request(id: &str, folder_id: Option<&str>, sort_priority: f32) -> HttpRequest {
+        HttpRequest {
+            id: id.to_string(),
+            name: id.to_string(),
+            folder_id: folder_id.map(|s| s.to_string()),
+            sort_priority,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn orders_requests_like_the_sidebar() {
+        let folders = vec![Folder {
+            id: "f".to_string(),
+            sort_priority: 1.0,
+            ..Default::default()
+        }];
+        let requests = vec![
+            request("c", None, 2.0),
+            request("f2", Some("f"), 1.0),
+            request("a", None, 0.0),
+            request("f1", Some("f"), 0.0),
+        ];
+        let ids =
+            |requests: Vec<HttpRequest>| requests.into_iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(ids(http_requests_in_order(&folders, &requests, None)), ["a", "f1", "f2", "c"]);
+        assert_eq!(ids(http_requests_in_order(&folders, &requests, Some("f"))), ["f1", "f2"]);
+    }
+
+    #[tokio::test]
+    async fn stops_on_failure() {
+        let requests = vec![
+            request("a", None, 0.0),
+            request("b", None, 1.0),
+            request("c", None, 2.0),
+        ];
+        let send = |r: HttpRequest, _| async move {
+            match r.id.as_str() {
+                "a" => Ok(HttpResponse {
+                    status: 200,
+                    ..Default::default()
+                }),
+                _ => Ok(HttpResponse {
+                    status: 500,
+                    ..Default::default()
+                }),
+            }
+        };
+
+        let options = RunOptions {
+            stop_on_failure: true,
+            ..Default::default()
+        };
+        let report = run_http_requests(requests.clone(), &options, send).await;
+        let passed: Vec<bool> = report.results.iter().map(|r| r.passed).collect();
+        assert_eq!(passed, [true, false, false]);
+        assert!(report.results[1].response.is_some());
+        assert!(report.results[2].response.is_none());
+
+        let report = run_http_requests(requests, &RunOptions::default(), send).await;
+        assert!(report.results.iter().all(|r| r.response.is_some()));
+    }
+
+    #[tokio::test]
+    async fn writes_junit_reports() {
+        let requests = vec![request("ok", None, 0.0), request("<bad>", None, 1.0)];
+        let send = |r: HttpRequest, _| async move {
+            match r.id.as_str() {
+                "ok" => Ok(HttpResponse {
+                    status: 200,
+                    elapsed: 1500,
+                    ..Default::default()
+                }),
+                _ => Ok(HttpResponse {
+                    status: 404,
+                    assertion_results: vec![HttpAssertionResult {
+                        assertion: HttpAssertion {
+                            enabled: true,
+                            assertion_type: HttpAssertionType::Status,
+                            value: "2xx".to_string(),
+                            ..Default::default()
+                        },
+                        passed: false,
+                        actual: Some("404".to_string()),
+                        error: None,
+                    }],
+                    ..Default::default()
+                }),
+            }
+        };
+
+        let mut report = run_http_requests(requests, &RunOptions::default(), send).await;
+        report.name = "Users & Teams".to_string();
+        let xml = format_report(&report, ReportFormat::JUnit).unwrap();
+        assert!(xml.contains(
+            r#"<testsuites name="Users &amp; Teams" tests="2" failures="1" errors="0" skipped="0""#
+        ));
+        assert!(xml.contains(r#"<testcase name="ok" classname="Users &amp; Teams" time="1.500">"#));
+        assert!(xml.contains(r#"<testcase name="&lt;bad&gt;""#));
+        assert!(xml.contains(r#"<failure message="Status is 2xx: got 404" type="assertion">"#));
+
+        let json = format_report(&report, ReportFormat::Json).unwrap();
+        assert!(json.contains(r#""requestId": "ok""#));
+    }
+
+    #[tokio::test]
+    async fn sends_each_data_row() {
+        let requests = vec![request("a", None, 0.0), request("b", None, 1.0)];
+        let send = |r: HttpRequest, row: Vec<EnvironmentVariable>| async move {
+            let user = row.iter().find(|v| v.name == "user").map(|v| v.value.clone());
+            Ok(HttpResponse {
+                request_id: r.id,
+                status: if user.as_deref() == Some("bad") { 500 } else { 200 },
+                ..Default::default()
+            })
+        };
+
+        let path = std::env::temp_dir().join(format!("yaak-data-{}.csv", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "user,note\nada,\"hi, there\"\nbad,\n").unwrap();
+        let data = read_data_file(&path).unwrap();
+        let _ = std::fs::remove_file(path);
+        assert_eq!(data[0][1].value, "hi, there");
+
+        let options = RunOptions {
+            data,
+            ..Default::default()
+        };
+        let report = run_http_requests(requests, &options, send).await;
+        let results: Vec<(Option<i32>, bool)> =
+            report.results.iter().map(|r| (r.iteration, r.passed)).collect();
+        assert_eq!(
+            results,
+            [
+                (Some(0), true),
+                (Some(0), true),
+                (Some(1), false),
+                (Some(1), false)
+            ]
+        );
+
+        let xml = format_report(&report, ReportFormat::JUnit).unwrap();
+        assert!(xml.contains(r#"<testcase name="b [row 2]""#));
+    }
+
+    #[tokio::test]
+    async fn sends_extracted_values() {
+        let requests = vec![request("login", None, 0.0), request("me", None, 1.0)];
+        let send = |r: HttpRequest, vars: Vec<EnvironmentVariable>| async move {
+            let token = vars.iter().find(|v| v.name == "token").map(|v| v.value.clone());
+            let extracted_values = match r.id.as_str() {
+                "login" => vec![HttpExtractedValue {
+                    extractor: HttpExtractor {
+                        variable: "token".to_string(),
+                        ..Default::default()
+                    },
+                    value: Some(format!("t{}", token.is_some())),
+                    error: None,
+                }],
+                _ => Vec::new(),
+            };
+            Ok(HttpResponse {
+                request_id: r.id,
+                status: if token.as_deref() == Some("tfalse") { 200 } else { 401 },
+                extracted_values,
+                ..Default::default()
+            })
+        };
+
+        // Each data row starts over without what the previous one extracted
+        let options = RunOptions {
+            data: vec![Vec::new(), Vec::new()],
+            ..Default::default()
+        };
+        let report = run_http_requests(requests, &options, send).await;
+        let results: Vec<(&str, bool)> =
+            report.results.iter().map(|r| (r.request_id.as_str(), r.passed)).collect();
+        assert_eq!(
+            results,
+            [
+                ("login", false),
+                ("me", true),
+                ("login", false),
+                ("me", true)
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_json_data_files() {
+        let path = std::env::temp_dir().join(format!("yaak-data-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, r#"[{"id":7,"name":"Ada","tags":["a"]},{"name":null}]"#).unwrap();
+        let data = read_data_file(&path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let row = |i: usize| -> Vec<(String, String)> {
+            data[i].iter().map(|v| (v.name.clone(), v.value.clone())).collect()
+        };
+        assert!(row(0).contains(&("id".to_string(), "7".to_string())));
+        assert!(row(0).contains(&("tags".to_string(), r#"["a"]"#.to_string())));
+        assert_eq!(row(1), [("name".to_string(), String::new())]);
+    }
+}
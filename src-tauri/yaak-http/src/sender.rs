@@ -0,0 +1,1665 @@
+use crate::assertions::check_assertions;
+use crate::client::{make_client, HttpConnectionOptions};
+use crate::cookies::{
+    cookie_header_value, cookie_store_from_jar, cookies_for_url, cookies_from_store,
+    store_response_cookies,
+};
+use crate::error::Result;
+use crate::extractors::extract_values;
+use crate::progress::ProgressThrottle;
+use crate::timing::TimingRecorder;
+use crate::upload::{FileBody, UploadCounter};
+use http::header::{
+    ACCEPT, AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, LOCATION,
+    PROXY_AUTHORIZATION, TRANSFER_ENCODING, USER_AGENT, WWW_AUTHENTICATE,
+};
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode, Uri};
+use http_body::Body as _;
+use log::{debug, error, warn};
+use mime_guess::Mime;
+use reqwest::{multipart, Body, Client, Method, Request, RequestBuilder, Response, Url};
+use reqwest_cookie_store::CookieStoreMutex;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::fs;
+use tokio::fs::{create_dir_all, File};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::watch::Receiver;
+use tokio::sync::{oneshot, Mutex};
+use yaak_models::models::{
+    CookieJar, HttpRequest, HttpResponse, HttpResponseCookie, HttpResponseEvent,
+    HttpResponseHeader, HttpResponseRedirect, HttpResponseState, TlsInfo,
+};
+use yaak_sse::parser::EventStreamParser;
+use yaak_sse::sse::ServerSentEvent;
+use yaak_tls::tls_info::TlsRecorder;
+
+/// Hooks for everything the send pipeline needs from its environment, so the same
+/// pipeline can be driven by the app, the CLI, or tests.
+pub trait HttpSendCallback: Clone + Send + Sync + 'static {
+    /// Persist the in-flight response. This is called every time the response changes, and
+    /// periodically while the body downloads, so it doubles as the progress event.
+    fn update_response(
+        &self,
+        response: &HttpResponse,
+    ) -> impl Future<Output = Result<HttpResponse>> + Send;
+
+    /// Directory to write response bodies to
+    fn responses_dir(&self) -> PathBuf;
+
+    /// Apply the request's authentication, returning headers to set on the request.
+    /// Existing headers will be replaced, while new headers will be added.
+    fn authenticate(
+        &self,
+        request: &HttpRequest,
+        method: &str,
+        url: &str,
+        headers: Vec<HttpResponseHeader>,
+    ) -> impl Future<Output = Result<Vec<HttpResponseHeader>>> + Send;
+
+    /// Persist the cookie jar after it was updated by the response
+    fn update_cookie_jar(&self, cookie_jar: &CookieJar) -> impl Future<Output = Result<()>> + Send;
+
+    /// Persist an event received on an event stream response, as soon as it arrives
+    fn add_response_event(
+        &self,
+        event: &HttpResponseEvent,
+    ) -> impl Future<Output = Result<()>> + Send;
+}
+
+/// How long to wait before reconnecting to an event stream, if the server didn't say
+const DEFAULT_RECONNECT_DELAY: Duration = Duration::from_secs(3);
+const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
+
+/// How often to persist a response's progress while its body downloads
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+/// Persist progress sooner if this much of the body arrived since the last time
+const PROGRESS_BYTES: usize = 16 * 1024 * 1024;
+
+/// Send an already-rendered request, streaming the response body to disk
+pub async fn send_http_request<C: HttpSendCallback>(
+    cb: &C,
+    request: &HttpRequest,
+    og_response: &HttpResponse,
+    options: &HttpConnectionOptions,
+    cookie_jar: Option<CookieJar>,
+    cancelled_rx: &mut Receiver<bool>,
+) -> Result<HttpResponse> {
+    let response_id = og_response.id.clone();
+    let response = Arc::new(Mutex::new(og_response.clone()));
+
+    let url_string = ensure_proto(&request.url);
+    debug!("Sending request to {} {url_string}", request.method);
+
+    // Add cookie store if specified
+    let maybe_cookie_manager = match cookie_jar {
+        Some(cj) => match cookie_store_from_jar(&cj) {
+            Ok(store) => Some((Arc::new(store), cj)),
+            Err(e) => return response_err(cb, &*response.lock().await, e.to_string()).await,
+        },
+        None => None,
+    };
+
+    // Render query parameters
+    let mut query_params = Vec::new();
+    for p in request.url_parameters.clone() {
+        if !p.enabled || p.name.is_empty() {
+            continue;
+        }
+        query_params.push((p.name, p.value));
+    }
+
+    let uri = match Uri::from_str(url_string.as_str()) {
+        Ok(u) => u,
+        Err(e) => {
+            let error = format!("Failed to parse URL \"{url_string}\": {e}");
+            return response_err(cb, &*response.lock().await, error).await;
+        }
+    };
+    // Yes, we're parsing both URI and URL because they could return different errors
+    let url = match Url::from_str(uri.to_string().as_str()) {
+        Ok(u) => u,
+        Err(e) => {
+            let error = format!("Failed to parse URL \"{url_string}\": {e}");
+            return response_err(cb, &*response.lock().await, error).await;
+        }
+    };
+
+    let tls_recorder = TlsRecorder::default();
+    let timing_recorder = TimingRecorder::new(&tls_recorder);
+    let client = match make_client(options, &url, &tls_recorder, &timing_recorder) {
+        Ok(c) => c,
+        Err(e) => return response_err(cb, &*response.lock().await, e.to_string()).await,
+    };
+
+    let m = match Method::from_bytes(request.method.to_uppercase().as_bytes()) {
+        Ok(m) => m,
+        Err(e) => return response_err(cb, &*response.lock().await, e.to_string()).await,
+    };
+    let request_builder = client.request(m, url).query(&query_params);
+
+    let mut headers = HeaderMap::new();
+    headers.insert(USER_AGENT, HeaderValue::from_static("yaak"));
+    headers.insert(ACCEPT, HeaderValue::from_static("*/*"));
+
+    for h in request.headers.clone() {
+        if h.name.is_empty() && h.value.is_empty() {
+            continue;
+        }
+
+        if !h.enabled {
+            continue;
+        }
+
+        let header_name = match HeaderName::from_str(&h.name) {
+            Ok(n) => n,
+            Err(e) => {
+                error!("Failed to create header name: {}", e);
+                continue;
+            }
+        };
+        let header_value = match HeaderValue::from_str(&h.value) {
+            Ok(n) => n,
+            Err(e) => {
+                error!("Failed to create header value: {}", e);
+                continue;
+            }
+        };
+
+        headers.insert(header_name, header_value);
+    }
+
+    let upload = UploadCounter::default();
+    let request_builder = match apply_body(request_builder, request, &mut headers, &upload).await {
+        Ok(b) => b,
+        Err(e) => return response_err(cb, &*response.lock().await, e).await,
+    };
+
+    // Add headers last, because previous steps may modify them
+    let request_builder = request_builder.headers(headers);
+
+    let mut sendable_req = match request_builder.build() {
+        Ok(r) => r,
+        Err(e) => {
+            warn!("Failed to build request builder {e:?}");
+            return response_err(cb, &*response.lock().await, e.to_string()).await;
+        }
+    };
+
+    // Apply authentication
+    if request.authentication_type.is_some() {
+        let method = sendable_req.method().to_string();
+        let url = sendable_req.url().to_string();
+        let headers = headers_to_vec(sendable_req.headers());
+        let set_headers = match cb.authenticate(request, &method, &url, headers).await {
+            Ok(h) => h,
+            Err(e) => return response_err(cb, &*response.lock().await, e.to_string()).await,
+        };
+
+        let headers = sendable_req.headers_mut();
+        for header in set_headers {
+            let name = HeaderName::from_str(&header.name);
+            let value = HeaderValue::from_str(&header.value);
+            match (name, value) {
+                (Ok(name), Ok(value)) => {
+                    headers.insert(name, value);
+                }
+                _ => {
+                    let error = format!("Invalid header from authentication {}", header.name);
+                    return response_err(cb, &*response.lock().await, error).await;
+                }
+            }
+        }
+    }
+
+    // Set the Cookie header ourselves (rather than letting reqwest do it) so we know what was sent
+    let cookies = maybe_cookie_manager.as_ref().map(|(store, _)| RequestCookies {
+        store: Arc::clone(store),
+        disabled: request.disabled_cookies.clone(),
+        manage_header: !sendable_req.headers().contains_key(COOKIE),
+    });
+    let request_cookies = match &cookies {
+        Some(c) => c.apply(&mut sendable_req),
+        None => Vec::new(),
+    };
+
+    // Snapshot exactly what is about to go over the wire, so it can be inspected later
+    {
+        let mut r = response.lock().await;
+        r.request_cookies = request_cookies;
+        r.request_url = sendable_req.url().to_string();
+        r.request_headers = headers_to_vec(sendable_req.headers());
+        match sendable_req.body().and_then(|b| b.as_bytes()) {
+            Some(bytes) => {
+                r.request_content_length = Some(bytes.len() as i64);
+                if !bytes.is_empty() {
+                    let body_path = body_path(cb, &response_id, "request");
+                    if let Err(e) = write_request_body(&body_path, bytes).await {
+                        warn!("Failed to write request body {e:?}");
+                    } else {
+                        r.request_body_path = Some(body_path.to_string_lossy().to_string());
+                    }
+                }
+            }
+            None => {
+                // Streamed bodies (eg. files and multipart) can't be captured, so use their length
+                let body_length = sendable_req.body().and_then(|b| b.size_hint().exact());
+                r.request_content_length =
+                    body_length.and_then(|l| i64::try_from(l).ok()).or_else(|| {
+                        sendable_req
+                            .headers()
+                            .get(CONTENT_LENGTH)
+                            .and_then(|v| v.to_str().ok())
+                            .and_then(|v| v.parse().ok())
+                    });
+            }
+        }
+        *r = cb.update_response(&r).await?;
+    }
+
+    let (resp_tx, mut resp_rx) =
+        oneshot::channel::<(std::result::Result<Response, String>, Vec<HttpResponseRedirect>)>();
+    let (done_tx, done_rx) = oneshot::channel::<HttpResponse>();
+
+    let start = Instant::now();
+
+    let max_redirects = match options.follow_redirects {
+        true => Some(options.max_redirects),
+        false => None,
+    };
+
+    // Streamed bodies can't be sent again, so those event streams won't be reconnected to
+    let reconnect = match options.reconnect_event_streams {
+        true => sendable_req.try_clone().map(|request| EventStreamReconnect {
+            client: client.clone(),
+            request,
+        }),
+        false => None,
+    };
+    let execution = {
+        let options = options.clone();
+        let tls_recorder = tls_recorder.clone();
+        let timing_recorder = timing_recorder.clone();
+        tokio::spawn(async move {
+            let client_for =
+                |url: &Url| make_client(&options, url, &tls_recorder, &timing_recorder);
+            let result = execute_with_redirects(
+                &client,
+                client_for,
+                sendable_req,
+                max_redirects,
+                cookies.as_ref(),
+                &timing_recorder,
+            )
+            .await;
+            let _ = resp_tx.send(result);
+        })
+    };
+
+    // Wait for the response, persisting the progress of any body being uploaded meanwhile
+    let mut upload_ticks = tokio::time::interval(PROGRESS_INTERVAL);
+    let mut persisted_sent = 0;
+    let (raw_response, redirects) = loop {
+        tokio::select! {
+            r = &mut resp_rx => match r {
+                Ok(r) => break r,
+                Err(_) => {
+                    let error = "Request ended unexpectedly".to_string();
+                    return response_err(cb, &*response.lock().await, error).await;
+                }
+            },
+            _ = cancelled_rx.changed() => {
+                debug!("Request cancelled");
+                // Dropping the request stops the body upload, if it's still going
+                execution.abort();
+                return response_err(cb, &*response.lock().await, "Request was cancelled".to_string()).await;
+            }
+            _ = upload_ticks.tick() => {
+                let sent = upload.sent();
+                if sent != persisted_sent {
+                    persisted_sent = sent;
+                    let mut r = response.lock().await;
+                    r.request_bytes_sent = i64::try_from(sent).ok();
+                    // Keep waiting on the request, rather than leave it running unwatched
+                    match cb.update_response(&r).await {
+                        Ok(updated) => *r = updated,
+                        Err(e) => warn!("Failed to save upload progress {e}"),
+                    }
+                }
+            }
+        }
+    };
+    if upload.sent() != persisted_sent {
+        response.lock().await.request_bytes_sent = i64::try_from(upload.sent()).ok();
+    }
+
+    {
+        let cb = cb.clone();
+        let cancelled_rx = cancelled_rx.clone();
+        let response_id = response_id.clone();
+        let response = response.clone();
+        let assertions = request.assertions.clone();
+        let extractors = request.extractors.clone();
+        let charset = request.response_charset.clone();
+        tokio::spawn(async move {
+            response.lock().await.redirects = redirects;
+            match raw_response {
+                Ok(v) => {
+                    response.lock().await.tls = response_tls_info(&tls_recorder, &v);
+                    let result = read_response(
+                        &cb,
+                        v,
+                        &response,
+                        &response_id,
+                        start,
+                        &timing_recorder,
+                        reconnect,
+                        maybe_cookie_manager,
+                        cancelled_rx.clone(),
+                    )
+                    .await;
+                    if let Err(e) = result {
+                        let mut r = response.lock().await;
+                        if let Ok(updated) = response_err(&cb, &r, e.to_string()).await {
+                            *r = updated;
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to execute request {e}");
+                    // Most likely the handshake that failed, like for an expired certificate
+                    let mut r = response.lock().await;
+                    r.tls = tls_recorder.latest();
+                    // Keep the error on the response we hand back, not just the saved one
+                    if let Ok(updated) = response_err(&cb, &r, e).await {
+                        *r = updated;
+                    }
+                }
+            };
+
+            let check = assertions.iter().any(|a| a.enabled);
+            let extract = extractors.iter().any(|e| e.enabled);
+            if (check || extract) && !*cancelled_rx.borrow() {
+                let mut r = response.lock().await;
+                if check {
+                    r.assertion_results =
+                        check_assertions(&assertions, &r, charset.as_deref()).await;
+                }
+                // A failed send has nothing to extract
+                if extract && r.error.is_none() {
+                    r.extracted_values = extract_values(&extractors, &r, charset.as_deref()).await;
+                }
+                match cb.update_response(&r).await {
+                    Ok(updated) => *r = updated,
+                    Err(e) => warn!("Failed to save assertion and extractor results {e}"),
+                }
+            }
+
+            let r = response.lock().await.clone();
+            let _ = done_tx.send(r);
+        });
+    };
+
+    Ok(tokio::select! {
+        Ok(r) = done_rx => r,
+        _ = cancelled_rx.changed() => {
+            let mut r = response.lock().await.clone();
+            if response_id.is_empty() {
+                r.error = Some("Ephemeral request was cancelled".to_string());
+            }
+            r.state = HttpResponseState::Closed;
+            cb.update_response(&r).await?
+        }
+    })
+}
+
+/// Cookie jar state for a single send
+struct RequestCookies {
+    store: Arc<CookieStoreMutex>,
+    disabled: Vec<String>,
+    /// False if the user set a Cookie header themselves, in which case we leave it alone
+    manage_header: bool,
+}
+
+impl RequestCookies {
+    /// Set the Cookie header for the request's URL, returning the cookies that were added
+    fn apply(&self, req: &mut Request) -> Vec<HttpResponseCookie> {
+        if !self.manage_header {
+            return Vec::new();
+        }
+
+        let cookies = cookies_for_url(&self.store, req.url(), &self.disabled);
+        let headers = req.headers_mut();
+        headers.remove(COOKIE);
+        if let Some(value) = cookie_header_value(&cookies) {
+            match HeaderValue::from_str(&value) {
+                Ok(v) => {
+                    headers.insert(COOKIE, v);
+                }
+                Err(e) => warn!("Failed to create cookie header {e:?}"),
+            }
+        }
+
+        cookies
+    }
+}
+
+/// Execute the request, following redirects ourselves (when `max_redirects` is set) so that
+/// every hop can be recorded on the response. Redirects that were followed are returned
+/// even if sending ultimately fails. Hops to another host are sent with a client from
+/// `client_for`, since the client certificate is chosen for the host a client is made for.
+async fn execute_with_redirects(
+    client: &Client,
+    client_for: impl Fn(&Url) -> Result<Client>,
+    mut req: Request,
+    max_redirects: Option<usize>,
+    cookies: Option<&RequestCookies>,
+    timing_recorder: &TimingRecorder,
+) -> (std::result::Result<Response, String>, Vec<HttpResponseRedirect>) {
+    let mut client = client.clone();
+    let mut redirects = Vec::new();
+    loop {
+        let hop_start = Instant::now();
+        timing_recorder.request_started();
+        let method = req.method().clone();
+        let url = req.url().clone();
+        let request_headers = headers_to_vec(req.headers());
+
+        // Must clone before sending. Streamed bodies can't be sent again, so for those we keep
+        // a copy without the body, which can only follow redirects that switch to GET.
+        let next_req = req.try_clone().ok_or_else(|| without_body(&req));
+
+        let resp = match client.execute(req).await {
+            Ok(r) => r,
+            Err(e) => return (Err(format!("{e} → {e:?}")), redirects),
+        };
+        timing_recorder.headers_received();
+
+        if let Some(c) = cookies {
+            store_response_cookies(&c.store, resp.headers(), &url);
+        }
+
+        let status = resp.status();
+        let max_redirects = match max_redirects {
+            Some(m) if is_followable_redirect(status) => m,
+            _ => return (Ok(resp), redirects),
+        };
+
+        let location = resp.headers().get(LOCATION).and_then(|l| l.to_str().ok());
+        let next_url = match location.map(|l| url.join(l)) {
+            Some(Ok(u)) => u,
+            Some(Err(e)) => {
+                return (Err(format!("Invalid redirect location: {e}")), redirects);
+            }
+            None => return (Ok(resp), redirects),
+        };
+
+        // Browsers switch to GET (dropping the body) for these, so we do the same
+        let switch_to_get = match status {
+            StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND => method == Method::POST,
+            StatusCode::SEE_OTHER => method != Method::HEAD,
+            _ => false,
+        };
+
+        let mut next_req = match next_req {
+            Ok(r) => r,
+            Err(r) if switch_to_get => r,
+            Err(_) => return (Ok(resp), redirects),
+        };
+
+        redirects.push(HttpResponseRedirect {
+            url: url.to_string(),
+            method: method.to_string(),
+            status: status.as_u16() as i32,
+            status_reason: status.canonical_reason().map(|s| s.to_string()),
+            headers: headers_to_vec(resp.headers()),
+            request_headers,
+            elapsed: hop_start.elapsed().as_millis() as i32,
+        });
+
+        if redirects.len() > max_redirects {
+            return (Err(format!("Too many redirects (max {max_redirects})")), redirects);
+        }
+
+        debug!("Following {status} redirect to {next_url}");
+
+        if switch_to_get {
+            *next_req.method_mut() = Method::GET;
+            *next_req.body_mut() = None;
+            for h in [
+                CONTENT_TYPE,
+                CONTENT_LENGTH,
+                CONTENT_ENCODING,
+                TRANSFER_ENCODING,
+            ] {
+                next_req.headers_mut().remove(h);
+            }
+        }
+
+        // Don't leak credentials to a different origin
+        if next_url.origin() != url.origin() {
+            for h in [AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION, WWW_AUTHENTICATE] {
+                next_req.headers_mut().remove(h);
+            }
+        }
+
+        // Don't present this host's client certificate to another one
+        if (next_url.host_str(), next_url.port_or_known_default())
+            != (url.host_str(), url.port_or_known_default())
+        {
+            client = match client_for(&next_url) {
+                Ok(c) => c,
+                Err(e) => return (Err(e.to_string()), redirects),
+            };
+        }
+
+        *next_req.url_mut() = next_url;
+        if let Some(c) = cookies {
+            c.apply(&mut next_req);
+        }
+        req = next_req;
+    }
+}
+
+fn without_body(req: &Request) -> Request {
+    let mut copy = Request::new(req.method().clone(), req.url().clone());
+    *copy.headers_mut() = req.headers().clone();
+    *copy.timeout_mut() = req.timeout().copied();
+    *copy.version_mut() = req.version();
+    copy
+}
+
+/// TLS details of the connection a response came over, if it was HTTPS. reqwest doesn't expose
+/// the negotiated ALPN protocol, but HTTP/2 over TLS is only ever negotiated with it.
+fn response_tls_info(tls_recorder: &TlsRecorder, response: &Response) -> Option<TlsInfo> {
+    if response.url().scheme() != "https" {
+        return None;
+    }
+
+    let mut tls = tls_recorder.tls_info(response.url().host_str()?)?;
+    tls.alpn_protocol = match response.version() {
+        reqwest::Version::HTTP_2 => Some("h2".to_string()),
+        reqwest::Version::HTTP_11 => Some("http/1.1".to_string()),
+        _ => None,
+    };
+    Some(tls)
+}
+
+/// What's needed to send a request again when its event stream ends
+struct EventStreamReconnect {
+    client: Client,
+    request: Request,
+}
+
+impl EventStreamReconnect {
+    /// Wait as long as the server asked, then send the request again with the ID of the last
+    /// event received, like the browser's EventSource. Network errors are retried until the
+    /// send is cancelled. Returns None if cancelled or the server responded with 204 to say
+    /// the stream is over.
+    async fn reconnect(
+        &self,
+        parser: &EventStreamParser,
+        cancelled_rx: &mut Receiver<bool>,
+    ) -> std::result::Result<Option<Response>, String> {
+        let delay = match parser.reconnection_time() {
+            Some(ms) => Duration::from_millis(ms),
+            None => DEFAULT_RECONNECT_DELAY,
+        };
+
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {},
+                _ = cancelled_rx.changed() => return Ok(None),
+            };
+
+            // Cloning can't fail, since it already succeeded for the original
+            let mut req = match self.request.try_clone() {
+                Some(r) => r,
+                None => return Ok(None),
+            };
+            if let Ok(id) = HeaderValue::from_str(parser.last_event_id()) {
+                if !id.is_empty() {
+                    req.headers_mut().insert(LAST_EVENT_ID, id);
+                }
+            }
+
+            debug!("Reconnecting to event stream {}", req.url());
+            match self.client.execute(req).await {
+                Ok(r) if r.status() == StatusCode::NO_CONTENT => return Ok(None),
+                Ok(r) if r.status() == StatusCode::OK && is_event_stream(r.headers()) => {
+                    return Ok(Some(r))
+                }
+                Ok(r) => {
+                    return Err(format!("Failed to reconnect to event stream: {}", r.status()))
+                }
+                Err(e) => warn!("Failed to reconnect to event stream {e}"),
+            }
+        }
+    }
+}
+
+fn is_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<Mime>().ok())
+        .is_some_and(|m| m.essence_str() == "text/event-stream")
+}
+
+fn response_event(response: &HttpResponse, event: ServerSentEvent) -> HttpResponseEvent {
+    HttpResponseEvent {
+        workspace_id: response.workspace_id.clone(),
+        request_id: response.request_id.clone(),
+        response_id: response.id.clone(),
+        event_type: event.event_type,
+        data: event.data,
+        event_id: event.id,
+        retry: event.retry.and_then(|r| i32::try_from(r).ok()),
+        ..Default::default()
+    }
+}
+
+/// Path to store a response's body at. Ephemeral responses (no ID) get a random name.
+fn body_path<C: HttpSendCallback>(cb: &C, response_id: &str, suffix: &str) -> PathBuf {
+    let name = match response_id.is_empty() {
+        true => uuid::Uuid::new_v4().to_string(),
+        false => response_id.to_string(),
+    };
+    match suffix.is_empty() {
+        true => cb.responses_dir().join(name),
+        false => cb.responses_dir().join(format!("{name}.{suffix}")),
+    }
+}
+
+async fn write_request_body(body_path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(dir) = body_path.parent() {
+        create_dir_all(dir).await?;
+    }
+    fs::write(body_path, bytes).await?;
+    Ok(())
+}
+
+fn is_followable_redirect(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::MOVED_PERMANENTLY
+            | StatusCode::FOUND
+            | StatusCode::SEE_OTHER
+            | StatusCode::TEMPORARY_REDIRECT
+            | StatusCode::PERMANENT_REDIRECT
+    )
+}
+
+fn headers_to_vec(headers: &HeaderMap) -> Vec<HttpResponseHeader> {
+    headers
+        .iter()
+        .map(|(k, v)| HttpResponseHeader {
+            name: k.as_str().to_string(),
+            value: v.to_str().unwrap_or_default().to_string(),
+        })
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn read_response<C: HttpSendCallback>(
+    cb: &C,
+    mut v: Response,
+    response: &Mutex<HttpResponse>,
+    response_id: &str,
+    start: Instant,
+    timing_recorder: &TimingRecorder,
+    reconnect: Option<EventStreamReconnect>,
+    maybe_cookie_manager: Option<(Arc<CookieStoreMutex>, CookieJar)>,
+    mut cancelled_rx: Receiver<bool>,
+) -> Result<()> {
+    let mut content_length = v.content_length();
+    let response_headers = v.headers().clone();
+    let body_path = body_path(cb, response_id, "");
+    create_dir_all(cb.responses_dir()).await?;
+
+    {
+        let mut r = response.lock().await;
+        r.body_path = Some(body_path.to_str().unwrap().to_string());
+        r.elapsed_headers = start.elapsed().as_millis() as i32;
+        r.timing = timing_recorder.timing();
+        r.status = v.status().as_u16() as i32;
+        r.status_reason = v.status().canonical_reason().map(|s| s.to_string());
+        r.headers = headers_to_vec(&response_headers);
+        r.url = v.url().to_string();
+        r.remote_addr = v.remote_addr().map(|a| a.to_string());
+        r.version = match v.version() {
+            reqwest::Version::HTTP_09 => Some("HTTP/0.9".to_string()),
+            reqwest::Version::HTTP_10 => Some("HTTP/1.0".to_string()),
+            reqwest::Version::HTTP_11 => Some("HTTP/1.1".to_string()),
+            reqwest::Version::HTTP_2 => Some("HTTP/2".to_string()),
+            reqwest::Version::HTTP_3 => Some("HTTP/3".to_string()),
+            _ => None,
+        };
+
+        r.state = HttpResponseState::Connected;
+        *r = cb.update_response(&r).await?;
+    }
+
+    // Write body to FS
+    let mut f = File::options().create(true).truncate(true).write(true).open(&body_path).await?;
+
+    // Events are parsed as they arrive, rather than from the body once it's complete
+    let mut event_parser = is_event_stream(&response_headers).then(EventStreamParser::new);
+
+    let mut written_bytes: usize = 0;
+    let mut progress = ProgressThrottle::new(PROGRESS_INTERVAL, PROGRESS_BYTES);
+    loop {
+        let chunk = tokio::select! {
+            chunk = v.chunk() => chunk,
+            _ = progress_due(progress.deadline()) => {
+                persist_progress(cb, response, &mut f, start, written_bytes).await?;
+                progress.persisted();
+                continue;
+            }
+        };
+        if *cancelled_rx.borrow() {
+            // Request was canceled
+            return Ok(());
+        }
+        let error = match chunk {
+            Ok(Some(bytes)) => {
+                f.write_all(&bytes).await?;
+                written_bytes += bytes.len();
+                if let Some(parser) = event_parser.as_mut() {
+                    let r = response.lock().await;
+                    for event in parser.feed(&bytes) {
+                        cb.add_response_event(&response_event(&r, event)).await?;
+                    }
+                }
+                if progress.record(bytes.len()) {
+                    persist_progress(cb, response, &mut f, start, written_bytes).await?;
+                    progress.persisted();
+                }
+                continue;
+            }
+            Ok(None) => None,
+            Err(e) => Some(e.to_string()),
+        };
+
+        if let (Some(parser), Some(reconnect)) = (&event_parser, &reconnect) {
+            if let Some(e) = error {
+                warn!("Event stream failed {e}");
+            }
+            if progress.deadline().is_some() {
+                persist_progress(cb, response, &mut f, start, written_bytes).await?;
+                progress.persisted();
+            }
+            match reconnect.reconnect(parser, &mut cancelled_rx).await {
+                Ok(Some(next)) => {
+                    v = next;
+                    content_length = None;
+                    continue;
+                }
+                Ok(None) if *cancelled_rx.borrow() => return Ok(()),
+                Ok(None) => {}
+                Err(e) => response.lock().await.error = Some(e),
+            }
+        } else if let Some(e) = error {
+            response.lock().await.error = Some(e);
+        }
+        break;
+    }
+
+    // Set final content length
+    f.flush().await?;
+    timing_recorder.body_received();
+    {
+        let mut r = response.lock().await;
+        r.elapsed = start.elapsed().as_millis() as i32;
+        r.timing = timing_recorder.timing();
+        r.content_length = match content_length {
+            Some(l) => Some(l as i32),
+            None => Some(written_bytes as i32),
+        };
+        r.state = HttpResponseState::Closed;
+        *r = cb.update_response(&r).await?;
+    };
+
+    // Add cookie store if specified
+    if let Some((cookie_store, mut cookie_jar)) = maybe_cookie_manager {
+        cookie_jar.cookies = cookies_from_store(&cookie_store)?;
+        if let Err(e) = cb.update_cookie_jar(&cookie_jar).await {
+            error!("Failed to update cookie jar: {}", e);
+        };
+    }
+
+    Ok(())
+}
+
+/// Flush what's been written of the body and persist how much of it there is so far
+async fn persist_progress<C: HttpSendCallback>(
+    cb: &C,
+    response: &Mutex<HttpResponse>,
+    f: &mut File,
+    start: Instant,
+    written_bytes: usize,
+) -> Result<()> {
+    f.flush().await?;
+    let mut r = response.lock().await;
+    r.elapsed = start.elapsed().as_millis() as i32;
+    r.content_length = Some(written_bytes as i32);
+    *r = cb.update_response(&r).await?;
+    Ok(())
+}
+
+/// Wait until unpersisted progress is due, or forever if there isn't any
+async fn progress_due(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn apply_body(
+    mut request_builder: RequestBuilder,
+    request: &HttpRequest,
+    headers: &mut HeaderMap,
+    upload: &UploadCounter,
+) -> std::result::Result<RequestBuilder, String> {
+    let request_body = request.body.clone();
+    let body_type = match &request.body_type {
+        Some(t) => t,
+        None => return Ok(request_builder),
+    };
+
+    if body_type == "graphql" {
+        let query = get_str_h(&request_body, "query");
+        let variables = get_str_h(&request_body, "variables");
+        let body = if variables.trim().is_empty() {
+            format!(r#"{{"query":{}}}"#, serde_json::to_string(query).unwrap_or_default())
+        } else {
+            format!(
+                r#"{{"query":{},"variables":{variables}}}"#,
+                serde_json::to_string(query).unwrap_or_default()
+            )
+        };
+        request_builder = request_builder.body(body.to_owned());
+    } else if body_type == "application/x-www-form-urlencoded" && request_body.contains_key("form")
+    {
+        let mut form_params = Vec::new();
+        let form = request_body.get("form");
+        if let Some(f) = form {
+            match f.as_array() {
+                None => {}
+                Some(a) => {
+                    for p in a {
+                        let enabled = get_bool(p, "enabled", true);
+                        let name = get_str(p, "name");
+                        if !enabled || name.is_empty() {
+                            continue;
+                        }
+                        let value = get_str(p, "value");
+                        form_params.push((name, value));
+                    }
+                }
+            }
+        }
+        request_builder = request_builder.form(&form_params);
+    } else if body_type == "binary" && request_body.contains_key("filePath") {
+        let file_path = get_str_h(&request_body, "filePath");
+        let f = FileBody::open(file_path, upload).await.map_err(|e| e.to_string())?;
+        request_builder = request_builder.body(Body::wrap(f));
+    } else if body_type == "multipart/form-data" && request_body.contains_key("form") {
+        let mut multipart_form = multipart::Form::new();
+        if let Some(form_definition) = request_body.get("form") {
+            match form_definition.as_array() {
+                None => {}
+                Some(fd) => {
+                    for p in fd {
+                        let enabled = get_bool(p, "enabled", true);
+                        let name = get_str(p, "name").to_string();
+
+                        if !enabled || name.is_empty() {
+                            continue;
+                        }
+
+                        let file_path = get_str(p, "file").to_owned();
+                        let value = get_str(p, "value").to_owned();
+
+                        let mut part = if file_path.is_empty() {
+                            multipart::Part::text(value.clone())
+                        } else {
+                            let f = FileBody::open(&file_path, upload)
+                                .await
+                                .map_err(|e| e.to_string())?;
+                            let length = f.len();
+                            multipart::Part::stream_with_length(Body::wrap(f), length)
+                        };
+
+                        let content_type = get_str(p, "contentType");
+
+                        // Set or guess mimetype
+                        if !content_type.is_empty() {
+                            part = part
+                                .mime_str(content_type)
+                                .map_err(|e| format!("Invalid mime for multi-part entry {e:?}"))?;
+                        } else if !file_path.is_empty() {
+                            let default_mime = Mime::from_str("application/octet-stream").unwrap();
+                            let mime =
+                                mime_guess::from_path(file_path.clone()).first_or(default_mime);
+                            part = part
+                                .mime_str(mime.essence_str())
+                                .map_err(|e| format!("Invalid mime for multi-part entry {e:?}"))?;
+                        }
+
+                        // Set file path if not empty
+                        if !file_path.is_empty() {
+                            let filename = PathBuf::from(file_path)
+                                .file_name()
+                                .unwrap_or_default()
+                                .to_string_lossy()
+                                .to_string();
+                            part = part.file_name(filename);
+                        }
+
+                        multipart_form = multipart_form.part(name, part);
+                    }
+                }
+            }
+        }
+        headers.remove("Content-Type"); // reqwest will add this automatically
+        request_builder = request_builder.multipart(multipart_form);
+    } else if request_body.contains_key("text") {
+        let body = get_str_h(&request_body, "text");
+        request_builder = request_builder.body(body.to_owned());
+    } else {
+        warn!("Unsupported body type: {}", body_type);
+    }
+
+    Ok(request_builder)
+}
+
+async fn response_err<C: HttpSendCallback>(
+    cb: &C,
+    response: &HttpResponse,
+    error: String,
+) -> Result<HttpResponse> {
+    warn!("Failed to send request: {error:?}");
+    let mut response = response.clone();
+    response.state = HttpResponseState::Closed;
+    response.error = Some(error);
+    cb.update_response(&response).await
+}
+
+pub fn ensure_proto(url_str: &str) -> String {
+    if url_str.starts_with("http://") || url_str.starts_with("https://") {
+        return url_str.to_string();
+    }
+
+    // Url::from_str will fail without a proto, so add one
+    let parseable_url = format!("http://{}", url_str);
+    if let Ok(u) = Url::from_str(parseable_url.as_str()) {
+        match u.host() {
+            Some(host) => {
+                let h = host.to_string();
+                // These TLDs force HTTPS
+                if h.ends_with(".app") || h.ends_with(".dev") || h.ends_with(".page") {
+                    return format!("https://{url_str}");
+                }
+            }
+            None => {}
+        }
+    }
+
+    format!("http://{url_str}")
+}
+
+fn get_bool(v: &Value, key: &str, fallback: bool) -> bool {
+    match v.get(key) {
+        None => fallback,
+        Some(v) => v.as_bool().unwrap_or(fallback),
+    }
+}
+
+fn get_str<'a>(v: &'a Value, key: &str) -> &'a str {
+    match v.get(key) {
+        None => "",
+        Some(v) => v.as_str().unwrap_or_default(),
+    }
+}
+
+fn get_str_h<'a>(v: &'a BTreeMap<String, Value>, key: &str) -> &'a str {
+    match v.get(key) {
+        None => "",
+        Some(v) => v.as_str().unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod sender_tests {
+    use crate::client::{make_client, HttpConnectionOptions};
+    use crate::error::Result;
+    use crate::sender::{
+        execute_with_redirects, send_http_request, HttpSendCallback, PROGRESS_INTERVAL,
+    };
+    use crate::timing::TimingRecorder;
+    use reqwest::Url;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+    use yaak_models::models::{
+        CookieJar, HttpAssertion, HttpAssertionType, HttpRequest, HttpRequestHeader, HttpResponse,
+        HttpResponseEvent, HttpResponseHeader, HttpResponseState,
+    };
+    use yaak_tls::tls_info::TlsRecorder;
+
+    #[derive(Clone, Default)]
+    struct TestCallback {
+        events: Arc<std::sync::Mutex<Vec<HttpResponseEvent>>>,
+        updates: Arc<AtomicUsize>,
+    }
+
+    impl HttpSendCallback for TestCallback {
+        async fn update_response(&self, response: &HttpResponse) -> Result<HttpResponse> {
+            self.updates.fetch_add(1, Ordering::Relaxed);
+            Ok(response.clone())
+        }
+
+        fn responses_dir(&self) -> PathBuf {
+            std::env::temp_dir().join("yaak-http-tests")
+        }
+
+        async fn authenticate(
+            &self,
+            _request: &HttpRequest,
+            _method: &str,
+            _url: &str,
+            _headers: Vec<HttpResponseHeader>,
+        ) -> Result<Vec<HttpResponseHeader>> {
+            Ok(vec![HttpResponseHeader {
+                name: "Authorization".to_string(),
+                value: "Bearer abc".to_string(),
+            }])
+        }
+
+        async fn update_cookie_jar(&self, _cookie_jar: &CookieJar) -> Result<()> {
+            Ok(())
+        }
+
+        async fn add_response_event(&self, event: &HttpResponseEvent) -> Result<()> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    /// Accept one connection per canned response, replying to each in turn, and return the
+    /// raw requests that were received
+    async fn serve(raw_responses: &'static [&'static str]) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = tokio::spawn(async move {
+            let mut raw_requests = Vec::new();
+            for raw_response in raw_responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut raw_request = String::new();
+                let mut buf = vec![0; 8192];
+                while !request_complete(&raw_request) {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    raw_request.push_str(&String::from_utf8_lossy(&buf[..n]));
+                }
+                stream.write_all(raw_response.as_bytes()).await.unwrap();
+                raw_requests.push(raw_request);
+            }
+            raw_requests
+        });
+        (format!("http://{addr}"), handle)
+    }
+
+    /// Accept one connection and reply with a body of the given size, written in small chunks
+    async fn serve_body(size: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut raw_request = String::new();
+            let mut buf = vec![0; 8192];
+            while !request_complete(&raw_request) {
+                let n = stream.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    return;
+                }
+                raw_request.push_str(&String::from_utf8_lossy(&buf[..n]));
+            }
+            let head = format!("HTTP/1.1 200 OK\r\nContent-Length: {size}\r\n\r\n");
+            stream.write_all(head.as_bytes()).await.unwrap();
+            let chunk = vec![b'x'; 16 * 1024];
+            let mut remaining = size;
+            while remaining > 0 {
+                let n = remaining.min(chunk.len());
+                stream.write_all(&chunk[..n]).await.unwrap();
+                remaining -= n;
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    async fn download(size: usize) -> (HttpResponse, TestCallback) {
+        let request = HttpRequest {
+            url: serve_body(size).await,
+            method: "GET".to_string(),
+            ..Default::default()
+        };
+        let cb = TestCallback::default();
+        let response = send_with(&cb, &request, &options(), None).await;
+        (response, cb)
+    }
+
+    /// Send a request that won't be cancelled
+    async fn send(
+        request: &HttpRequest,
+        options: &HttpConnectionOptions,
+        cookie_jar: Option<CookieJar>,
+    ) -> HttpResponse {
+        send_with(&TestCallback::default(), request, options, cookie_jar).await
+    }
+
+    /// Like `send`, but with a callback the test can inspect afterwards
+    async fn send_with(
+        cb: &TestCallback,
+        request: &HttpRequest,
+        options: &HttpConnectionOptions,
+        cookie_jar: Option<CookieJar>,
+    ) -> HttpResponse {
+        let (_cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+        send_http_request(cb, request, &HttpResponse::new(), options, cookie_jar, &mut cancel_rx)
+            .await
+            .unwrap()
+    }
+
+    fn request_complete(raw: &str) -> bool {
+        let Some((head, body)) = raw.split_once("\r\n\r\n") else {
+            return false;
+        };
+        let content_length = head
+            .lines()
+            .filter_map(|l| l.split_once(": "))
+            .find(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, v)| v.parse::<usize>().ok())
+            .unwrap_or_default();
+        body.len() >= content_length
+    }
+
+    fn options() -> HttpConnectionOptions {
+        HttpConnectionOptions {
+            follow_redirects: false,
+            max_redirects: 0,
+            validate_certificates: true,
+            client_certificates: Vec::new(),
+            ca_certificates: Vec::new(),
+            certificate_pins: Vec::new(),
+            timeout: None,
+            proxy: None,
+            reconnect_event_streams: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn send_writes_body() {
+        let (url, server) =
+            serve(&["HTTP/1.1 200 OK\r\nContent-Length: 5\r\nX-Test: yes\r\n\r\nhello"]).await;
+        let request = HttpRequest {
+            url,
+            method: "GET".to_string(),
+            headers: vec![HttpRequestHeader {
+                enabled: true,
+                name: "X-Custom".to_string(),
+                value: "foo".to_string(),
+                id: None,
+            }],
+            ..Default::default()
+        };
+
+        let response = send(&request, &options(), None).await;
+
+        assert_eq!(response.error, None);
+        assert_eq!(response.status, 200);
+        assert!(matches!(response.state, HttpResponseState::Closed));
+        assert_eq!(response.content_length, Some(5));
+        assert!(response.headers.iter().any(|h| h.name == "x-test" && h.value == "yes"));
+        let body = tokio::fs::read_to_string(response.body_path.unwrap()).await.unwrap();
+        assert_eq!(body, "hello");
+
+        let raw_request = server.await.unwrap().remove(0);
+        assert!(raw_request.starts_with("GET / HTTP/1.1"));
+        assert!(raw_request.contains("x-custom: foo"));
+        assert!(raw_request.contains("user-agent: yaak"));
+    }
+
+    #[tokio::test]
+    async fn send_checks_assertions() {
+        let (url, _server) =
+            serve(&["HTTP/1.1 404 Not Found\r\nContent-Length: 8\r\n\r\n{\"id\":1}"]).await;
+        let assertion = |assertion_type, value: &str| HttpAssertion {
+            enabled: true,
+            assertion_type,
+            target: "$.id".to_string(),
+            value: value.to_string(),
+            id: None,
+        };
+        let request = HttpRequest {
+            url,
+            method: "GET".to_string(),
+            assertions: vec![
+                assertion(HttpAssertionType::Status, "2xx"),
+                assertion(HttpAssertionType::JsonPathEquals, "1"),
+            ],
+            ..Default::default()
+        };
+
+        let response = send(&request, &options(), None).await;
+
+        let passed: Vec<bool> = response.assertion_results.iter().map(|r| r.passed).collect();
+        assert_eq!(passed, [false, true]);
+        assert_eq!(response.assertion_results[0].actual.as_deref(), Some("404"));
+    }
+
+    #[tokio::test]
+    async fn send_records_timing() {
+        let (url, _server) = serve(&["HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello"]).await;
+        let request = HttpRequest {
+            // Use a name so there's something to resolve
+            url: url.replace("127.0.0.1", "localhost"),
+            method: "GET".to_string(),
+            ..Default::default()
+        };
+
+        let response = send(&request, &options(), None).await;
+
+        assert_eq!(response.error, None);
+        let timing = response.timing.unwrap();
+        assert_eq!(timing.redirect, 0.0);
+        assert!(timing.dns.is_some());
+        assert!(timing.connect.is_some());
+        assert_eq!(timing.tls, None);
+        assert!(timing.ttfb >= 0.0);
+        assert!(timing.download.is_some());
+    }
+
+    #[tokio::test]
+    async fn send_streams_and_reconnects_event_streams() {
+        let (url, server) = serve(&[
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n\
+             retry: 10\nid: 1\ndata: a\n\nevent: b\ndata: {}\n\n",
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n\
+             data: c\n\n",
+            "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n",
+        ])
+        .await;
+        let request = HttpRequest {
+            url,
+            method: "GET".to_string(),
+            ..Default::default()
+        };
+        let options = HttpConnectionOptions {
+            reconnect_event_streams: true,
+            ..options()
+        };
+
+        let cb = TestCallback::default();
+        let response = send_with(&cb, &request, &options, None).await;
+
+        assert_eq!(response.error, None);
+        assert!(matches!(response.state, HttpResponseState::Closed));
+        let events = cb.events.lock().unwrap().clone();
+        let data: Vec<_> = events.iter().map(|e| e.data.as_str()).collect();
+        assert_eq!(data, vec!["a", "{}", "c"]);
+        assert_eq!(events[0].event_id, Some("1".to_string()));
+        assert_eq!(events[0].retry, Some(10));
+        assert_eq!(events[1].event_type, "b");
+
+        // The last event ID is sent when reconnecting, until the server says to stop
+        let raw_requests = server.await.unwrap();
+        assert_eq!(raw_requests.len(), 3);
+        assert!(!raw_requests[0].contains("last-event-id"));
+        assert!(raw_requests[1].contains("last-event-id: 1"));
+        assert!(raw_requests[2].contains("last-event-id: 1"));
+    }
+
+    #[tokio::test]
+    async fn send_applies_authentication() {
+        let (url, server) = serve(&["HTTP/1.1 204 No Content\r\n\r\n"]).await;
+        let request = HttpRequest {
+            url,
+            method: "POST".to_string(),
+            authentication_type: Some("bearer".to_string()),
+            body_type: Some("text/plain".to_string()),
+            body: [("text".to_string(), "hi".into())].into(),
+            ..Default::default()
+        };
+
+        let response = send(&request, &options(), None).await;
+
+        assert_eq!(response.status, 204);
+        assert!(response
+            .request_headers
+            .iter()
+            .any(|h| h.name == "authorization" && h.value == "Bearer abc"));
+        assert_eq!(response.request_content_length, Some(2));
+        let request_body = tokio::fs::read_to_string(response.request_body_path.unwrap()).await;
+        assert_eq!(request_body.unwrap(), "hi");
+
+        let raw_request = server.await.unwrap().remove(0);
+        assert!(raw_request.contains("authorization: Bearer abc"));
+        assert!(raw_request.ends_with("\r\n\r\nhi"));
+    }
+
+    #[tokio::test]
+    async fn send_follows_redirects() {
+        let (url, server) = serve(&[
+            "HTTP/1.1 302 Found\r\nLocation: /next\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+        ])
+        .await;
+        let request = HttpRequest {
+            url: url.clone(),
+            method: "POST".to_string(),
+            body_type: Some("text/plain".to_string()),
+            body: [("text".to_string(), "hi".into())].into(),
+            ..Default::default()
+        };
+        let options = HttpConnectionOptions {
+            follow_redirects: true,
+            max_redirects: 10,
+            ..options()
+        };
+
+        let response = send(&request, &options, None).await;
+
+        assert_eq!(response.error, None);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.url, format!("{url}/next"));
+        assert_eq!(response.redirects.len(), 1);
+        // Each hop used a new connection, so the final one was timed from scratch
+        let timing = response.timing.unwrap();
+        assert!(timing.redirect > 0.0);
+        assert_eq!(timing.dns, None);
+        assert!(timing.connect.is_some());
+        let redirect = &response.redirects[0];
+        assert_eq!(redirect.url, format!("{url}/"));
+        assert_eq!(redirect.method, "POST");
+        assert_eq!(redirect.status, 302);
+        assert!(redirect.headers.iter().any(|h| h.name == "location" && h.value == "/next"));
+
+        // 302 switches a POST to a GET without a body
+        let raw_requests = server.await.unwrap();
+        assert!(raw_requests[0].starts_with("POST / HTTP/1.1"));
+        assert!(raw_requests[1].starts_with("GET /next HTTP/1.1"));
+        assert!(raw_requests[1].ends_with("\r\n\r\n"));
+    }
+
+    #[tokio::test]
+    async fn redirects_to_another_host_use_its_own_client() {
+        let (other_url, other_server) =
+            serve(&["HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok"]).await;
+        let redirect = format!(
+            "HTTP/1.1 302 Found\r\nLocation: {other_url}/done\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        );
+        let responses: &'static [&'static str] = Box::leak(Box::new([
+            "HTTP/1.1 302 Found\r\nLocation: /next\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            Box::leak(redirect.into_boxed_str()),
+        ]));
+        let (url, _server) = serve(responses).await;
+
+        let tls_recorder = TlsRecorder::default();
+        let timing_recorder = TimingRecorder::new(&tls_recorder);
+        let url = Url::parse(&url).unwrap();
+        let client = make_client(&options(), &url, &tls_recorder, &timing_recorder).unwrap();
+        let made_for = std::sync::Mutex::new(Vec::new());
+        let client_for = |url: &Url| {
+            made_for.lock().unwrap().push(url.to_string());
+            make_client(&options(), url, &tls_recorder, &timing_recorder)
+        };
+
+        let req = client.get(url).build().unwrap();
+        let (result, redirects) =
+            execute_with_redirects(&client, client_for, req, Some(10), None, &timing_recorder)
+                .await;
+
+        assert_eq!(result.unwrap().url().as_str(), format!("{other_url}/done"));
+        assert_eq!(redirects.len(), 2);
+        // Only the hop that changed host needed a new client
+        assert_eq!(*made_for.lock().unwrap(), vec![format!("{other_url}/done")]);
+        assert!(other_server.await.unwrap()[0].starts_with("GET /done HTTP/1.1"));
+    }
+
+    #[tokio::test]
+    async fn send_cookies_from_redirect() {
+        let (url, server) = serve(&[
+            "HTTP/1.1 302 Found\r\nLocation: /next\r\nSet-Cookie: a=1\r\nSet-Cookie: b=2\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ])
+        .await;
+        let request = HttpRequest {
+            url,
+            method: "GET".to_string(),
+            disabled_cookies: vec!["b".to_string()],
+            ..Default::default()
+        };
+        let options = HttpConnectionOptions {
+            follow_redirects: true,
+            max_redirects: 10,
+            ..options()
+        };
+
+        let response = send(&request, &options, Some(CookieJar::default())).await;
+
+        assert_eq!(response.status, 200);
+        assert!(response.request_cookies.is_empty());
+
+        let raw_requests = server.await.unwrap();
+        assert!(!raw_requests[0].contains("cookie:"));
+        assert!(raw_requests[1].contains("cookie: a=1\r\n"));
+    }
+
+    #[tokio::test]
+    async fn send_too_many_redirects() {
+        let (url, _server) = serve(&[
+            "HTTP/1.1 307 Temporary Redirect\r\nLocation: /a\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 307 Temporary Redirect\r\nLocation: /b\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ])
+        .await;
+        let request = HttpRequest {
+            url,
+            method: "GET".to_string(),
+            ..Default::default()
+        };
+        let options = HttpConnectionOptions {
+            follow_redirects: true,
+            max_redirects: 1,
+            ..options()
+        };
+
+        let response = send(&request, &options, None).await;
+
+        assert_eq!(response.error, Some("Too many redirects (max 1)".to_string()));
+        assert_eq!(response.redirects.len(), 2);
+        assert!(response.redirects[1].url.ends_with("/a"));
+    }
+
+    #[tokio::test]
+    async fn send_invalid_url() {
+        let request = HttpRequest {
+            url: "http://exa mple.com".to_string(),
+            method: "GET".to_string(),
+            ..Default::default()
+        };
+
+        let response = send(&request, &options(), None).await;
+
+        assert!(response.error.unwrap().starts_with("Failed to parse URL"));
+        assert!(matches!(response.state, HttpResponseState::Closed));
+    }
+
+    #[tokio::test]
+    async fn send_coalesces_progress_updates() {
+        let size = 8 * 1024 * 1024;
+        let start = Instant::now();
+        let (response, cb) = download(size).await;
+        let elapsed = start.elapsed();
+
+        assert_eq!(response.error, None);
+        assert_eq!(response.content_length, Some(size as i32));
+        let body = tokio::fs::read(response.body_path.unwrap()).await.unwrap();
+        assert_eq!(body.len(), size);
+
+        // Hundreds of chunks, but only updates for headers, closing, and each interval
+        let updates = cb.updates.load(Ordering::Relaxed) as u128;
+        let max_updates = 2 + elapsed.as_millis() / PROGRESS_INTERVAL.as_millis() + 1;
+        assert!(updates <= max_updates, "{updates} updates in {elapsed:?}");
+    }
+
+    /// Throughput of downloading a large body from a local server. Run with
+    /// `cargo test -p yaak-http --release bench_large_body -- --ignored --nocapture`
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore]
+    async fn bench_large_body() {
+        let size = 512 * 1024 * 1024;
+        let start = Instant::now();
+        let (response, cb) = download(size).await;
+        let elapsed = start.elapsed();
+
+        assert_eq!(response.content_length, Some(size as i32));
+        let _ = tokio::fs::remove_file(response.body_path.unwrap()).await;
+        println!(
+            "Downloaded {} MiB in {elapsed:?} ({:.0} MiB/s) with {} response updates",
+            size / 1024 / 1024,
+            size as f64 / 1024.0 / 1024.0 / elapsed.as_secs_f64(),
+            cb.updates.load(Ordering::Relaxed),
+        );
+    }
+
+    async fn temp_file(contents: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("yaak-upload-{}.txt", uuid::Uuid::new_v4()));
+        tokio::fs::write(&path, contents).await.unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn send_streams_file_bodies() {
+        let contents = "abcdefghij".repeat(20_000);
+        let path = temp_file(contents.as_bytes()).await;
+        let (url, server) = serve(&["HTTP/1.1 204 No Content\r\n\r\n"]).await;
+        let request = HttpRequest {
+            url,
+            method: "PUT".to_string(),
+            body_type: Some("binary".to_string()),
+            body: [("filePath".to_string(), path.to_string_lossy().into())].into(),
+            ..Default::default()
+        };
+
+        let response = send(&request, &options(), None).await;
+
+        assert_eq!(response.error, None);
+        assert_eq!(response.status, 204);
+        assert_eq!(response.request_content_length, Some(200_000));
+        assert_eq!(response.request_bytes_sent, Some(200_000));
+        assert_eq!(response.request_body_path, None);
+
+        let raw_request = server.await.unwrap().remove(0);
+        assert!(raw_request.contains("content-length: 200000\r\n"));
+        assert!(raw_request.ends_with(&format!("\r\n\r\n{contents}")));
+        let _ = tokio::fs::remove_file(path).await;
+    }
+
+    #[tokio::test]
+    async fn send_streams_multipart_files() {
+        let path = temp_file(b"file contents").await;
+        let (url, server) = serve(&["HTTP/1.1 204 No Content\r\n\r\n"]).await;
+        let form = serde_json::json!([
+            { "name": "a", "value": "text" },
+            { "name": "b", "file": path.to_string_lossy() },
+        ]);
+        let request = HttpRequest {
+            url,
+            method: "POST".to_string(),
+            body_type: Some("multipart/form-data".to_string()),
+            body: [("form".to_string(), form)].into(),
+            ..Default::default()
+        };
+
+        let response = send(&request, &options(), None).await;
+
+        assert_eq!(response.error, None);
+        assert_eq!(response.request_bytes_sent, Some(13));
+
+        let raw_request = server.await.unwrap().remove(0);
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        assert!(raw_request.contains(&format!("filename=\"{file_name}\"")));
+        assert!(raw_request.contains("Content-Type: text/plain\r\n\r\nfile contents\r\n"));
+        assert!(raw_request.contains("content-length: "));
+        let _ = tokio::fs::remove_file(path).await;
+    }
+
+    #[tokio::test]
+    async fn send_cancels_mid_upload() {
+        let path = temp_file(&vec![b'x'; 64 * 1024 * 1024]).await;
+
+        // Accept the connection but never read from it, so the upload stalls
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move { listener.accept().await.unwrap() });
+
+        let request = HttpRequest {
+            url,
+            method: "POST".to_string(),
+            body_type: Some("binary".to_string()),
+            body: [("filePath".to_string(), path.to_string_lossy().into())].into(),
+            ..Default::default()
+        };
+
+        let (cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+            let _ = cancel_tx.send(true);
+            // Keep the sender alive until the request notices
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        });
+
+        let cb = TestCallback::default();
+        let response = send_http_request(
+            &cb,
+            &request,
+            &HttpResponse::new(),
+            &options(),
+            None,
+            &mut cancel_rx,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(response.error, Some("Request was cancelled".to_string()));
+        assert_eq!(response.request_content_length, Some(64 * 1024 * 1024));
+        assert!(response.request_bytes_sent.unwrap_or_default() < 64 * 1024 * 1024);
+
+        // The connection was closed, rather than left uploading in the background
+        let (mut stream, _) = server.await.unwrap();
+        let drain = async {
+            let mut buf = vec![0; 1024 * 1024];
+            while let Ok(n) = stream.read(&mut buf).await {
+                if n == 0 {
+                    break;
+                }
+            }
+        };
+        let closed = tokio::time::timeout(std::time::Duration::from_secs(10), drain).await;
+        assert!(closed.is_ok(), "Upload continued after cancelling");
+        let _ = tokio::fs::remove_file(path).await;
+    }
+}
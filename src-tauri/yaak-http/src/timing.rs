@@ -0,0 +1,184 @@
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::net::lookup_host;
+use tower_layer::Layer;
+use tower_service::Service;
+use yaak_models::models::HttpResponseTiming;
+use yaak_tls::tls_info::TlsRecorder;
+
+/// Records when each phase of sending a request happened. reqwest only tells us when the
+/// response headers arrived, so this hooks into its DNS resolver and connector, and gets the
+/// start of the TLS handshake from the [`TlsRecorder`].
+#[derive(Debug, Clone, Default)]
+pub struct TimingRecorder {
+    times: Arc<Mutex<PhaseTimes>>,
+    tls_recorder: TlsRecorder,
+}
+
+#[derive(Debug, Clone, Default)]
+struct PhaseTimes {
+    start: Option<Instant>,
+    request_start: Option<Instant>,
+    dns_start: Option<Instant>,
+    dns_end: Option<Instant>,
+    connect_start: Option<Instant>,
+    tls_start: Option<Instant>,
+    connect_end: Option<Instant>,
+    headers: Option<Instant>,
+    body_end: Option<Instant>,
+}
+
+impl TimingRecorder {
+    pub fn new(tls_recorder: &TlsRecorder) -> Self {
+        Self {
+            times: Default::default(),
+            tls_recorder: tls_recorder.clone(),
+        }
+    }
+
+    /// Mark a request as about to be sent, forgetting the phases of any previous redirect hop
+    pub fn request_started(&self) {
+        let now = Instant::now();
+        let mut times = self.times.lock().unwrap();
+        *times = PhaseTimes {
+            start: times.start.or(Some(now)),
+            request_start: Some(now),
+            ..Default::default()
+        };
+    }
+
+    pub fn headers_received(&self) {
+        self.times.lock().unwrap().headers = Some(Instant::now());
+    }
+
+    pub fn body_received(&self) {
+        self.times.lock().unwrap().body_end = Some(Instant::now());
+    }
+
+    /// How long each phase took, once the response headers have arrived
+    pub fn timing(&self) -> Option<HttpResponseTiming> {
+        let times = self.times.lock().unwrap().clone();
+        let start = times.start?;
+        let request_start = times.request_start?;
+        let headers = times.headers?;
+
+        let dns = match (times.dns_start, times.dns_end) {
+            (Some(s), Some(e)) => Some(millis(e.saturating_duration_since(s))),
+            _ => None,
+        };
+
+        // hyper can start a new connection while waiting for a pooled one, in which case the
+        // new connection wasn't the one used for this request
+        let connect_end = times.connect_end.filter(|e| *e <= headers);
+        let (connect, tls) = match (times.connect_start, connect_end) {
+            (Some(connect_start), Some(connect_end)) => {
+                let tcp_start = times.dns_end.unwrap_or(connect_start);
+                let tcp_end = times.tls_start.unwrap_or(connect_end);
+                (
+                    Some(millis(tcp_end.saturating_duration_since(tcp_start))),
+                    times.tls_start.map(|s| millis(connect_end.saturating_duration_since(s))),
+                )
+            }
+            _ => (None, None),
+        };
+
+        let sent = connect_end.unwrap_or(request_start);
+        Some(HttpResponseTiming {
+            redirect: millis(request_start.saturating_duration_since(start)),
+            dns,
+            connect,
+            tls,
+            ttfb: millis(headers.saturating_duration_since(sent)),
+            download: times.body_end.map(|e| millis(e.saturating_duration_since(headers))),
+        })
+    }
+
+    /// Record a connection phase. Connections made after the response headers arrived are for
+    /// reconnecting to an event stream, which isn't timed.
+    fn record(&self, f: impl FnOnce(&mut PhaseTimes)) {
+        let mut times = self.times.lock().unwrap();
+        if times.headers.is_none() {
+            f(&mut times)
+        }
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Resolves names the same way reqwest does by default, while recording how long it took
+#[derive(Debug, Clone)]
+pub(crate) struct TimingResolver(pub(crate) TimingRecorder);
+
+impl Resolve for TimingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let recorder = self.0.clone();
+        Box::pin(async move {
+            recorder.record(|t| t.dns_start = Some(Instant::now()));
+            // Collected, since the lookup borrows the name
+            let addrs: Vec<SocketAddr> = lookup_host((name.as_str(), 0)).await?.collect();
+            recorder.record(|t| t.dns_end = Some(Instant::now()));
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Wraps reqwest's connector, which resolves the host, connects, and does the TLS handshake
+#[derive(Debug, Clone)]
+pub(crate) struct TimingLayer(pub(crate) TimingRecorder);
+
+impl<S> Layer<S> for TimingLayer {
+    type Service = TimingConnector<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimingConnector {
+            inner,
+            recorder: self.0.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct TimingConnector<S> {
+    inner: S,
+    recorder: TimingRecorder,
+}
+
+impl<S, R> Service<R> for TimingConnector<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        let connect_start = Instant::now();
+        self.recorder.record(|t| t.connect_start = Some(connect_start));
+
+        let recorder = self.recorder.clone();
+        let connecting = self.inner.call(req);
+        Box::pin(async move {
+            let conn = connecting.await?;
+            // Only count a handshake that was made for this connection
+            let tls_start =
+                recorder.tls_recorder.handshake_started().filter(|s| *s >= connect_start);
+            recorder.record(|t| {
+                t.tls_start = tls_start;
+                t.connect_end = Some(Instant::now());
+            });
+            Ok(conn)
+        })
+    }
+}
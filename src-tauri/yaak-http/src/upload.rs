@@ -0,0 +1,126 @@
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use tokio::fs::File;
+use tokio::io::{AsyncRead, ReadBuf};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Counts the bytes of request bodies streamed from disk as they're sent
+#[derive(Debug, Clone, Default)]
+pub struct UploadCounter(Arc<AtomicU64>);
+
+impl UploadCounter {
+    pub fn sent(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn add(&self, bytes: usize) {
+        self.0.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+/// A request body that's read from a file as it's sent, rather than loaded into memory first
+#[derive(Debug)]
+pub struct FileBody {
+    file: File,
+    buf: Box<[u8]>,
+    remaining: u64,
+    counter: UploadCounter,
+}
+
+impl FileBody {
+    pub async fn open(path: impl AsRef<Path>, counter: &UploadCounter) -> io::Result<Self> {
+        let file = File::open(path).await?;
+        let remaining = file.metadata().await?.len();
+        Ok(Self {
+            file,
+            buf: vec![0; CHUNK_SIZE].into_boxed_slice(),
+            remaining,
+            counter: counter.clone(),
+        })
+    }
+
+    /// Bytes left to send
+    pub fn len(&self) -> u64 {
+        self.remaining
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+impl Body for FileBody {
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        if this.remaining == 0 {
+            return Poll::Ready(None);
+        }
+
+        let max = this.buf.len().min(usize::try_from(this.remaining).unwrap_or(usize::MAX));
+        let mut buf = ReadBuf::new(&mut this.buf[..max]);
+        ready!(Pin::new(&mut this.file).poll_read(cx, &mut buf))?;
+
+        let n = buf.filled().len();
+        if n == 0 {
+            // The length was already sent in the headers, so a truncated body can't be fixed up
+            let error = io::Error::new(io::ErrorKind::UnexpectedEof, "File shrank while sending");
+            return Poll::Ready(Some(Err(error)));
+        }
+
+        this.remaining -= n as u64;
+        this.counter.add(n);
+        Poll::Ready(Some(Ok(Frame::data(Bytes::copy_from_slice(buf.filled())))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.remaining == 0
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.remaining)
+    }
+}
+
+#[cfg(test)]
+mod upload_tests {
+    use crate::upload::{FileBody, UploadCounter};
+    use http_body::Body;
+    use std::future::poll_fn;
+    use std::pin::Pin;
+
+    #[tokio::test]
+    async fn streams_file_in_chunks() {
+        let path = std::env::temp_dir().join(format!("yaak-upload-{}", uuid::Uuid::new_v4()));
+        let contents: Vec<u8> = (0..200_000).map(|i| (i % 251) as u8).collect();
+        tokio::fs::write(&path, &contents).await.unwrap();
+
+        let counter = UploadCounter::default();
+        let mut body = FileBody::open(&path, &counter).await.unwrap();
+        assert_eq!(body.len(), 200_000);
+        assert_eq!(body.size_hint().exact(), Some(200_000));
+
+        let mut received = Vec::new();
+        while let Some(frame) = poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await {
+            let data = frame.unwrap().into_data().unwrap();
+            received.extend_from_slice(&data);
+            assert_eq!(counter.sent(), received.len() as u64);
+        }
+
+        assert_eq!(received, contents);
+        assert!(body.is_end_stream());
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+}
@@ -1,24 +1,33 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type AnyModel = CookieJar | Environment | Folder | GrpcConnection | GrpcEvent | GrpcRequest | HttpRequest | HttpResponse | Plugin | Settings | KeyValue | Workspace | WorkspaceMeta | WebsocketConnection | WebsocketEvent | WebsocketRequest;
+export type AnyModel = CookieJar | Environment | Folder | GrpcConnection | GrpcEvent | GrpcRequest | HttpRequest | HttpResponse | HttpResponseEvent | Plugin | Settings | KeyValue | Workspace | WorkspaceMeta | WebsocketConnection | WebsocketEvent | WebsocketRequest;
 
-export type Cookie = { raw_cookie: string, domain: CookieDomain, expires: CookieExpires, path: [string, boolean], };
+export type CaCertificate = { enabled: boolean, path: string, };
 
-export type CookieDomain = { "HostOnly": string } | { "Suffix": string } | "NotPresent" | "Empty";
+export type CertificatePin = { enabled: boolean, host: string, pin: string, };
 
-export type CookieExpires = { "AtUtc": string } | "SessionEnd";
+export type ClientCertificate = { enabled: boolean, host: string, certPath: string | null, keyPath: string | null, pfxPath: string | null, };
+
+export type Cookie = { name: string, value: string, domain: string, hostOnly: boolean, path: string, expires: string | null, secure: boolean, httpOnly: boolean, sameSite: CookieSameSite | null, };
 
 export type CookieJar = { model: "cookie_jar", id: string, createdAt: string, updatedAt: string, workspaceId: string, cookies: Array<Cookie>, name: string, };
 
+export type CookieSameSite = "strict" | "lax" | "none";
+
 export type EditorKeymap = "default" | "vim" | "vscode" | "emacs";
 
 export type Environment = { model: "environment", id: string, workspaceId: string, environmentId: string | null, createdAt: string, updatedAt: string, name: string, variables: Array<EnvironmentVariable>, };
 
 export type EnvironmentVariable = { enabled?: boolean, name: string, value: string, id?: string, };
 
-export type Folder = { model: "folder", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, name: string, description: string, sortPriority: number, };
+export type Folder = { model: "folder", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, name: string, description: string, preRequestScript: string, postResponseScript: string, headers: Array<HttpRequestHeader>, authentication: Record<string, any>, authenticationType: string | null, variables: Array<EnvironmentVariable>, sortPriority: number, };
+
+/**
+ * What a garbage collection run deleted
+ */
+export type GarbageCollectionReport = { responsesDeleted: number, connectionsDeleted: number, eventsDeleted: number, filesDeleted: number, bytesReclaimed: number, };
 
-export type GrpcConnection = { model: "grpc_connection", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, elapsed: number, error: string | null, method: string, service: string, status: number, state: GrpcConnectionState, trailers: { [key in string]?: string }, url: string, };
+export type GrpcConnection = { model: "grpc_connection", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, elapsed: number, error: string | null, method: string, service: string, status: number, state: GrpcConnectionState, tls: TlsInfo | null, trailers: { [key in string]?: string }, url: string, };
 
 export type GrpcConnectionState = "initialized" | "connected" | "closed";
 
@@ -30,16 +39,91 @@ export type GrpcMetadataEntry = { enabled?: boolean, name: string, value: string
 
 export type GrpcRequest = { model: "grpc_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authenticationType: string | null, authentication: Record<string, any>, description: string, message: string, metadata: Array<GrpcMetadataEntry>, method: string | null, name: string, service: string | null, sortPriority: number, url: string, };
 
-export type HttpRequest = { model: "http_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, body: Record<string, any>, bodyType: string | null, description: string, headers: Array<HttpRequestHeader>, method: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
+/**
+ * A check run against each response to a request once it's finished
+ */
+export type HttpAssertion = { enabled?: boolean, assertionType: HttpAssertionType, target: string, value: string, id?: string, };
+
+/**
+ * The outcome of checking a response against one of its request's assertions
+ */
+export type HttpAssertionResult = { assertion: HttpAssertion, passed: boolean, actual: string | null, error: string | null, };
+
+export type HttpAssertionType = "status" | "header" | "json_path_equals" | "json_path_exists" | "body_matches" | "response_time_under" | "json_schema" | "script";
+
+/**
+ * A value an extractor pulled out of a response, and the variable it was saved as
+ */
+export type HttpExtractedValue = { extractor: HttpExtractor, value: string | null, error: string | null, };
+
+/**
+ * Pulls a value out of each successful response to a request and saves it as a variable, so
+ * later requests can use it
+ */
+export type HttpExtractor = { enabled?: boolean, extractorType: HttpExtractorType, source: string, variable: string, environmentId: string | null, id?: string, };
+
+export type HttpExtractorType = "json_path" | "header" | "regex" | "cookie";
+
+export type HttpRequest = { model: "http_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, body: Record<string, any>, bodyType: string | null, description: string, disabledCookies: Array<string>, extractors: Array<HttpExtractor>, headers: Array<HttpRequestHeader>, method: string, name: string, assertions: Array<HttpAssertion>, preRequestScript: string, postResponseScript: string, responseCharset: string | null, historyMaxCount: number | null, historyMaxAgeDays: number | null, historyMaxBytes: number | null, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
 
 export type HttpRequestHeader = { enabled?: boolean, name: string, value: string, id?: string, };
 
-export type HttpResponse = { model: "http_response", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, bodyPath: string | null, contentLength: number | null, elapsed: number, elapsedHeaders: number, error: string | null, headers: Array<HttpResponseHeader>, remoteAddr: string | null, status: number, statusReason: string | null, state: HttpResponseState, url: string, version: string | null, };
+export type HttpResponse = { model: "http_response", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, assertionResults: Array<HttpAssertionResult>, bodyPath: string | null, contentLength: number | null, elapsed: number, elapsedHeaders: number, error: string | null, extractedValues: Array<HttpExtractedValue>, headers: Array<HttpResponseHeader>, redirects: Array<HttpResponseRedirect>, remoteAddr: string | null, requestBodyPath: string | null, requestBytesSent: number | null, requestContentLength: number | null, requestCookies: Array<HttpResponseCookie>, requestHeaders: Array<HttpResponseHeader>, requestUrl: string, status: number, statusReason: string | null, state: HttpResponseState, timing: HttpResponseTiming | null, tls: TlsInfo | null, url: string, version: string | null, };
+
+/**
+ * Part of a response body, decoded to text
+ */
+export type HttpResponseBodyChunk = { offset: number, length: number, totalLength: number, charset: string, text: string, };
+
+/**
+ * Sixteen bytes of a body, like a row of `hexdump -C`
+ */
+export type HttpResponseBodyHexdumpRow = { offset: number, hex: string, ascii: string, };
+
+/**
+ * A range of lines of a response body
+ */
+export type HttpResponseBodyLines = { startLine: number, lines: Array<string>, offset: number, endOffset: number, eof: boolean, };
+
+export type HttpResponseBodyMatch = { offset: number, length: number, line: number, preview: string, };
+
+export type HttpResponseBodySearch = { matches: Array<HttpResponseBodyMatch>, nextOffset: number | null, nextLine: number | null, };
+
+/**
+ * A cookie that was sent with a request
+ */
+export type HttpResponseCookie = { name: string, value: string, domain: string | null, path: string, };
+
+/**
+ * A Server-Sent Event received on a `text/event-stream` response
+ */
+export type HttpResponseEvent = { model: "http_response_event", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, responseId: string, eventType: string, data: string, eventId: string | null, retry: number | null, };
 
 export type HttpResponseHeader = { name: string, value: string, };
 
+/**
+ * A single redirect that was followed before arriving at the final response
+ */
+export type HttpResponseRedirect = { url: string, method: string, status: number, statusReason: string | null, headers: Array<HttpResponseHeader>, requestHeaders: Array<HttpResponseHeader>, elapsed: number, };
+
 export type HttpResponseState = "initialized" | "connected" | "closed";
 
+/**
+ * Where the time for a response went, in milliseconds. Connection phases are for the final
+ * request, and are null when it reused an existing connection.
+ */
+export type HttpResponseTiming = { redirect: number, dns: number | null, connect: number | null, tls: number | null, ttfb: number, download: number | null, };
+
+/**
+ * The outcome of running every HTTP request in a folder or workspace, one after another
+ */
+export type HttpRunReport = { name: string, workspaceId: string, folderId: string | null, environmentId: string | null, startedAt: string, elapsed: number, results: Array<HttpRunResult>, };
+
+/**
+ * How one request of a run went
+ */
+export type HttpRunResult = { requestId: string, name: string, iteration: number | null, passed: boolean, response: HttpResponse | null, };
+
 export type HttpUrlParameter = { enabled?: boolean, name: string, value: string, id?: string, };
 
 export type KeyValue = { model: "key_value", createdAt: string, updatedAt: string, key: string, namespace: string, value: string, };
@@ -54,15 +138,27 @@ export type ProxySetting = { "type": "enabled", http: string, https: string, aut
 
 export type ProxySettingAuth = { user: string, password: string, };
 
+/**
+ * The value a variable ends up with, and the name of the layer it came from
+ */
+export type ResolvedVariable = { name: string, value: string, source: string, };
+
 export type Settings = { model: "settings", id: string, createdAt: string, updatedAt: string, appearance: string, editorFontSize: number, editorSoftWrap: boolean, interfaceFontSize: number, interfaceScale: number, openWorkspaceNewWindow: boolean | null, proxy: ProxySetting | null, theme: string, themeDark: string, themeLight: string, updateChannel: string, editorKeymap: EditorKeymap, };
 
 export type SyncHistory = { model: "sync_history", id: string, workspaceId: string, createdAt: string, states: Array<SyncState>, checksum: string, relPath: string, syncDir: string, };
 
 export type SyncState = { model: "sync_state", id: string, workspaceId: string, createdAt: string, updatedAt: string, flushedAt: string, modelId: string, checksum: string, relPath: string, syncDir: string, };
 
+export type TlsCertificate = { subject: string, issuer: string, subjectAltNames: Array<string>, serialNumber: string, notBefore: string | null, notAfter: string | null, fingerprintSha256: string, };
+
+/**
+ * Details of the TLS handshake made for a connection
+ */
+export type TlsInfo = { version: string | null, cipherSuite: string | null, alpnProtocol: string | null, certificates: Array<TlsCertificate>, };
+
 export type UpdateSource = "sync" | "window" | "plugin" | "background" | "import";
 
-export type WebsocketConnection = { model: "websocket_connection", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, elapsed: number, error: string | null, headers: Array<HttpResponseHeader>, state: WebsocketConnectionState, status: number, url: string, };
+export type WebsocketConnection = { model: "websocket_connection", id: string, createdAt: string, updatedAt: string, workspaceId: string, requestId: string, elapsed: number, error: string | null, headers: Array<HttpResponseHeader>, state: WebsocketConnectionState, status: number, tls: TlsInfo | null, url: string, };
 
 export type WebsocketConnectionState = "initialized" | "connected" | "closed";
 
@@ -74,6 +170,6 @@ export type WebsocketMessageType = "text" | "binary";
 
 export type WebsocketRequest = { model: "websocket_request", id: string, createdAt: string, updatedAt: string, workspaceId: string, folderId: string | null, authentication: Record<string, any>, authenticationType: string | null, description: string, headers: Array<HttpRequestHeader>, message: string, name: string, sortPriority: number, url: string, urlParameters: Array<HttpUrlParameter>, };
 
-export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, name: string, description: string, settingValidateCertificates: boolean, settingFollowRedirects: boolean, settingRequestTimeout: number, };
+export type Workspace = { model: "workspace", id: string, createdAt: string, updatedAt: string, name: string, description: string, preRequestScript: string, postResponseScript: string, headers: Array<HttpRequestHeader>, authentication: Record<string, any>, authenticationType: string | null, settingClientCertificates: Array<ClientCertificate>, settingCaCertificates: Array<CaCertificate>, settingCertificatePins: Array<CertificatePin>, settingValidateCertificates: boolean, settingFollowRedirects: boolean, settingMaxRedirects: number, settingReconnectEventStreams: boolean, settingRequestTimeout: number, settingHistoryMaxCount: number, settingHistoryMaxAgeDays: number, settingHistoryMaxBytes: number, };
 
-export type WorkspaceMeta = { model: "workspace_meta", id: string, workspaceId: string, createdAt: string, updatedAt: string, settingSyncDir: string | null, };
+export type WorkspaceMeta = { model: "workspace_meta", id: string, workspaceId: string, createdAt: string, updatedAt: string, settingSyncDir: string | null, settingClientCertificatePassphrases: { [key in string]?: string }, settingTrustScripts: boolean, };
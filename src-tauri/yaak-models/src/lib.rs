@@ -4,3 +4,4 @@ pub mod queries;
 
 pub mod plugin;
 pub mod render;
+pub mod retention;
@@ -8,6 +8,10 @@ use std::fmt::Display;
 use std::str::FromStr;
 use ts_rs::TS;
 
+/// Authentication type of a request or folder that uses whatever its parent folder or workspace
+/// has. A workspace set to inherit has no authentication.
+pub const AUTHENTICATION_TYPE_INHERIT: &str = "inherit";
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase", tag = "type")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -144,6 +148,58 @@ impl<'s> TryFrom<&Row<'s>> for Settings {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct ClientCertificate {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    // Host to present the certificate to, like "api.example.com", "*.example.com", or
+    // "localhost:8443". Empty or "*" matches every host.
+    pub host: String,
+    // PEM certificate chain, which may also contain the private key
+    pub cert_path: Option<String>,
+    // PEM private key, if it's not in the certificate file
+    pub key_path: Option<String>,
+    // PKCS#12 bundle, used instead of the PEM files
+    pub pfx_path: Option<String>,
+    // Decrypts the PKCS#12 bundle or an encrypted PKCS#8 key. It's kept in the workspace meta,
+    // and only filled in when connecting, so it never ends up in synced or exported files.
+    #[serde(skip)]
+    pub passphrase: Option<String>,
+}
+
+impl ClientCertificate {
+    /// The file the passphrase decrypts, which the passphrase is stored under
+    pub fn passphrase_key(&self) -> Option<&str> {
+        [&self.pfx_path, &self.key_path, &self.cert_path]
+            .into_iter()
+            .find_map(|p| p.as_deref().filter(|p| !p.is_empty()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct CaCertificate {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    // PEM bundle or DER certificate to trust in addition to the platform roots
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct CertificatePin {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    // Host the pin applies to, matched the same way as client certificate hosts
+    pub host: String,
+    // SHA-256 of the server's SubjectPublicKeyInfo, like "sha256/<base64>"
+    pub pin: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -155,13 +211,34 @@ pub struct Workspace {
     pub updated_at: NaiveDateTime,
     pub name: String,
     pub description: String,
+    // Scripts run around every request in the workspace, before those of its folders
+    pub pre_request_script: String,
+    pub post_response_script: String,
+    // Headers and authentication that every request in the workspace inherits
+    pub headers: Vec<HttpRequestHeader>,
+    #[ts(type = "Record<string, any>")]
+    pub authentication: BTreeMap<String, Value>,
+    pub authentication_type: Option<String>,
 
     // Settings
+    pub setting_client_certificates: Vec<ClientCertificate>,
+    pub setting_ca_certificates: Vec<CaCertificate>,
+    pub setting_certificate_pins: Vec<CertificatePin>,
     #[serde(default = "default_true")]
     pub setting_validate_certificates: bool,
     #[serde(default = "default_true")]
     pub setting_follow_redirects: bool,
+    #[serde(default = "default_max_redirects")]
+    pub setting_max_redirects: i32,
+    // Reconnect when an event stream ends, like the browser's EventSource does
+    pub setting_reconnect_event_streams: bool,
     pub setting_request_timeout: i32,
+    // Response history to keep for each request. Zero means no limit.
+    #[serde(default = "default_history_max_count")]
+    pub setting_history_max_count: i32,
+    pub setting_history_max_age_days: i32,
+    #[ts(type = "number")]
+    pub setting_history_max_bytes: i64,
 }
 
 #[derive(Iden)]
@@ -173,9 +250,22 @@ pub enum WorkspaceIden {
     CreatedAt,
     UpdatedAt,
 
+    Authentication,
+    AuthenticationType,
     Description,
+    Headers,
     Name,
+    PostResponseScript,
+    PreRequestScript,
+    SettingCaCertificates,
+    SettingCertificatePins,
+    SettingClientCertificates,
     SettingFollowRedirects,
+    SettingHistoryMaxAgeDays,
+    SettingHistoryMaxBytes,
+    SettingHistoryMaxCount,
+    SettingMaxRedirects,
+    SettingReconnectEventStreams,
     SettingRequestTimeout,
     SettingValidateCertificates,
 }
@@ -184,6 +274,11 @@ impl<'s> TryFrom<&Row<'s>> for Workspace {
     type Error = rusqlite::Error;
 
     fn try_from(r: &Row<'s>) -> Result<Self, Self::Error> {
+        let setting_client_certificates: String = r.get("setting_client_certificates")?;
+        let setting_ca_certificates: String = r.get("setting_ca_certificates")?;
+        let setting_certificate_pins: String = r.get("setting_certificate_pins")?;
+        let headers: String = r.get("headers")?;
+        let authentication: String = r.get("authentication")?;
         Ok(Self {
             id: r.get("id")?,
             model: r.get("model")?,
@@ -191,7 +286,23 @@ impl<'s> TryFrom<&Row<'s>> for Workspace {
             updated_at: r.get("updated_at")?,
             name: r.get("name")?,
             description: r.get("description")?,
+            pre_request_script: r.get("pre_request_script")?,
+            post_response_script: r.get("post_response_script")?,
+            headers: serde_json::from_str(&headers).unwrap_or_default(),
+            authentication: serde_json::from_str(&authentication).unwrap_or_default(),
+            authentication_type: r.get("authentication_type")?,
+            setting_client_certificates: serde_json::from_str(&setting_client_certificates)
+                .unwrap_or_default(),
+            setting_ca_certificates: serde_json::from_str(&setting_ca_certificates)
+                .unwrap_or_default(),
+            setting_certificate_pins: serde_json::from_str(&setting_certificate_pins)
+                .unwrap_or_default(),
             setting_follow_redirects: r.get("setting_follow_redirects")?,
+            setting_history_max_age_days: r.get("setting_history_max_age_days")?,
+            setting_history_max_bytes: r.get("setting_history_max_bytes")?,
+            setting_history_max_count: r.get("setting_history_max_count")?,
+            setting_max_redirects: r.get("setting_max_redirects")?,
+            setting_reconnect_event_streams: r.get("setting_reconnect_event_streams")?,
             setting_request_timeout: r.get("setting_request_timeout")?,
             setting_validate_certificates: r.get("setting_validate_certificates")?,
         })
@@ -205,6 +316,8 @@ impl Workspace {
             model: "workspace".to_string(),
             setting_validate_certificates: true,
             setting_follow_redirects: true,
+            setting_max_redirects: default_max_redirects(),
+            setting_history_max_count: default_history_max_count(),
             ..Default::default()
         }
     }
@@ -221,6 +334,30 @@ pub struct WorkspaceMeta {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub setting_sync_dir: Option<String>,
+    // Client certificate passphrases by the file they decrypt. These live here, rather than on
+    // the workspace, because workspace meta is never synced.
+    pub setting_client_certificate_passphrases: BTreeMap<String, String>,
+    // Whether the user agreed to run the workspace's scripts on this machine
+    pub setting_trust_scripts: bool,
+}
+
+impl WorkspaceMeta {
+    /// The given client certificates with their passphrases filled in
+    pub fn client_certificates(
+        &self,
+        certificates: &[ClientCertificate],
+    ) -> Vec<ClientCertificate> {
+        certificates
+            .iter()
+            .map(|c| ClientCertificate {
+                passphrase: c
+                    .passphrase_key()
+                    .and_then(|k| self.setting_client_certificate_passphrases.get(k))
+                    .cloned(),
+                ..c.clone()
+            })
+            .collect()
+    }
 }
 
 #[derive(Iden)]
@@ -233,13 +370,17 @@ pub enum WorkspaceMetaIden {
     CreatedAt,
     UpdatedAt,
 
+    SettingClientCertificatePassphrases,
     SettingSyncDir,
+    SettingTrustScripts,
 }
 
 impl<'s> TryFrom<&Row<'s>> for WorkspaceMeta {
     type Error = rusqlite::Error;
 
     fn try_from(r: &Row<'s>) -> Result<Self, Self::Error> {
+        let setting_client_certificate_passphrases: String =
+            r.get("setting_client_certificate_passphrases")?;
         Ok(Self {
             id: r.get("id")?,
             workspace_id: r.get("workspace_id")?,
@@ -247,33 +388,153 @@ impl<'s> TryFrom<&Row<'s>> for WorkspaceMeta {
             created_at: r.get("created_at")?,
             updated_at: r.get("updated_at")?,
             setting_sync_dir: r.get("setting_sync_dir")?,
+            setting_client_certificate_passphrases: serde_json::from_str(
+                &setting_client_certificate_passphrases,
+            )
+            .unwrap_or_default(),
+            setting_trust_scripts: r.get("setting_trust_scripts")?,
         })
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
 #[ts(export, export_to = "gen_models.ts")]
-enum CookieDomain {
-    HostOnly(String),
-    Suffix(String),
-    NotPresent,
-    Empty,
+pub enum CookieSameSite {
+    Strict,
+    Lax,
+    None,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Default, TS)]
+#[serde(rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
-enum CookieExpires {
-    AtUtc(String),
-    SessionEnd,
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    // Only send to `domain` itself, not its subdomains (ie. no Domain attribute was set)
+    pub host_only: bool,
+    pub path: String,
+    // UTC expiry, or `None` for a session cookie
+    pub expires: Option<NaiveDateTime>,
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: Option<CookieSameSite>,
+}
+
+impl Cookie {
+    /// Whether both cookies occupy the same slot in a jar. A jar can only hold one cookie
+    /// for each domain, path, and name.
+    pub fn same_key(&self, other: &Cookie) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
-#[ts(export, export_to = "gen_models.ts")]
-pub struct Cookie {
-    raw_cookie: String,
-    domain: CookieDomain,
-    expires: CookieExpires,
-    path: (String, bool),
+impl<'de> Deserialize<'de> for Cookie {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize, Default)]
+        #[serde(default, rename_all = "camelCase")]
+        struct CookieFields {
+            name: String,
+            value: String,
+            domain: String,
+            host_only: bool,
+            path: String,
+            expires: Option<NaiveDateTime>,
+            secure: bool,
+            http_only: bool,
+            same_site: Option<CookieSameSite>,
+        }
+
+        // Jars used to store cookie_store's serialized cookies as-is
+        #[derive(Deserialize)]
+        struct LegacyCookie {
+            raw_cookie: String,
+            domain: LegacyCookieDomain,
+            expires: LegacyCookieExpires,
+            path: (String, bool),
+        }
+
+        #[derive(Deserialize)]
+        enum LegacyCookieDomain {
+            HostOnly(String),
+            Suffix(String),
+            NotPresent,
+            Empty,
+        }
+
+        #[derive(Deserialize)]
+        enum LegacyCookieExpires {
+            AtUtc(String),
+            SessionEnd,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum CookieRepr {
+            Legacy(LegacyCookie),
+            Current(CookieFields),
+        }
+
+        Ok(match CookieRepr::deserialize(deserializer)? {
+            CookieRepr::Current(c) => Cookie {
+                name: c.name,
+                value: c.value,
+                domain: c.domain,
+                host_only: c.host_only,
+                path: c.path,
+                expires: c.expires,
+                secure: c.secure,
+                http_only: c.http_only,
+                same_site: c.same_site,
+            },
+            CookieRepr::Legacy(c) => {
+                let mut parts = c.raw_cookie.split(';');
+                let (name, value) =
+                    parts.next().unwrap_or_default().split_once('=').unwrap_or_default();
+                let mut cookie = Cookie {
+                    name: name.trim().to_string(),
+                    value: value.trim().to_string(),
+                    path: c.path.0,
+                    ..Default::default()
+                };
+                (cookie.domain, cookie.host_only) = match c.domain {
+                    LegacyCookieDomain::HostOnly(d) => (d, true),
+                    LegacyCookieDomain::Suffix(d) => (d, false),
+                    LegacyCookieDomain::NotPresent | LegacyCookieDomain::Empty => {
+                        (String::new(), true)
+                    }
+                };
+                cookie.expires = match c.expires {
+                    LegacyCookieExpires::AtUtc(d) => {
+                        chrono::DateTime::parse_from_rfc3339(&d).map(|d| d.naive_utc()).ok()
+                    }
+                    LegacyCookieExpires::SessionEnd => None,
+                };
+                for attr in parts {
+                    let (k, v) = attr.split_once('=').unwrap_or((attr, ""));
+                    match k.trim().to_lowercase().as_str() {
+                        "secure" => cookie.secure = true,
+                        "httponly" => cookie.http_only = true,
+                        "samesite" => {
+                            cookie.same_site = match v.trim().to_lowercase().as_str() {
+                                "strict" => Some(CookieSameSite::Strict),
+                                "lax" => Some(CookieSameSite::Lax),
+                                "none" => Some(CookieSameSite::None),
+                                _ => None,
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                cookie
+            }
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
@@ -398,6 +659,19 @@ pub struct Folder {
 
     pub name: String,
     pub description: String,
+    // Scripts run around every request in the folder, before those of the folders inside it
+    pub pre_request_script: String,
+    pub post_response_script: String,
+    // Headers and authentication that every request in the folder inherits, on top of those of
+    // the folders and workspace it's in
+    pub headers: Vec<HttpRequestHeader>,
+    #[ts(type = "Record<string, any>")]
+    pub authentication: BTreeMap<String, Value>,
+    #[serde(default = "default_authentication_type_inherit")]
+    pub authentication_type: Option<String>,
+    // Variables for every request in the folder, overriding the environment's and those of the
+    // folders it's in
+    pub variables: Vec<EnvironmentVariable>,
     pub sort_priority: f32,
 }
 
@@ -414,13 +688,22 @@ pub enum FolderIden {
 
     Name,
     Description,
+    Authentication,
+    AuthenticationType,
+    Headers,
+    PostResponseScript,
+    PreRequestScript,
     SortPriority,
+    Variables,
 }
 
 impl<'s> TryFrom<&Row<'s>> for Folder {
     type Error = rusqlite::Error;
 
     fn try_from(r: &Row<'s>) -> Result<Self, Self::Error> {
+        let headers: String = r.get("headers")?;
+        let authentication: String = r.get("authentication")?;
+        let variables: String = r.get("variables")?;
         Ok(Self {
             id: r.get("id")?,
             model: r.get("model")?,
@@ -431,6 +714,12 @@ impl<'s> TryFrom<&Row<'s>> for Folder {
             folder_id: r.get("folder_id")?,
             name: r.get("name")?,
             description: r.get("description")?,
+            pre_request_script: r.get("pre_request_script")?,
+            post_response_script: r.get("post_response_script")?,
+            headers: serde_json::from_str(&headers).unwrap_or_default(),
+            authentication: serde_json::from_str(&authentication).unwrap_or_default(),
+            authentication_type: r.get("authentication_type")?,
+            variables: serde_json::from_str(&variables).unwrap_or_default(),
         })
     }
 }
@@ -461,6 +750,90 @@ pub struct HttpUrlParameter {
     pub id: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum HttpAssertionType {
+    // Status code equals the value, which can be a class like `2xx`
+    Status,
+    // Header named by the target has the value, or exists if the value is empty
+    Header,
+    // Value selected by the target's JSONPath equals the value, parsed as JSON if possible
+    JsonPathEquals,
+    // Target's JSONPath selects something
+    JsonPathExists,
+    // Body matches the value as a regex
+    BodyMatches,
+    // Response took less than the value in milliseconds
+    ResponseTimeUnder,
+    // Body validates against the value as a JSON Schema
+    JsonSchema,
+    // Test named by the target, run by a post-response script rather than checked here
+    Script,
+}
+
+impl Default for HttpAssertionType {
+    fn default() -> Self {
+        Self::Status
+    }
+}
+
+/// A check run against each response to a request once it's finished
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpAssertion {
+    #[serde(default = "default_true")]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    pub assertion_type: HttpAssertionType,
+    // Header name or JSONPath expression, for the types that need one
+    pub target: String,
+    pub value: String,
+    #[ts(optional, as = "Option<String>")]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum HttpExtractorType {
+    // Value selected by the source as a JSONPath expression
+    JsonPath,
+    // Value of the response header named by the source
+    Header,
+    // First capture group of the source regex in the body, or the whole match if it has none
+    Regex,
+    // Value of the cookie named by the source, as set by the response
+    Cookie,
+}
+
+impl Default for HttpExtractorType {
+    fn default() -> Self {
+        Self::JsonPath
+    }
+}
+
+/// Pulls a value out of each successful response to a request and saves it as a variable, so
+/// later requests can use it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpExtractor {
+    #[serde(default = "default_true")]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    pub extractor_type: HttpExtractorType,
+    // JSONPath expression, header name, regex, or cookie name
+    pub source: String,
+    // Name of the variable to set
+    pub variable: String,
+    // Environment to save the variable to. Without one, it's only set for the rest of a run.
+    pub environment_id: Option<String>,
+    #[ts(optional, as = "Option<String>")]
+    pub id: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -480,10 +853,24 @@ pub struct HttpRequest {
     pub body: BTreeMap<String, Value>,
     pub body_type: Option<String>,
     pub description: String,
+    pub disabled_cookies: Vec<String>,
+    pub extractors: Vec<HttpExtractor>,
     pub headers: Vec<HttpRequestHeader>,
     #[serde(default = "default_http_method")]
     pub method: String,
     pub name: String,
+    pub assertions: Vec<HttpAssertion>,
+    // JavaScript run by the plugin runtime before the request is rendered, and after its
+    // response is stored
+    pub pre_request_script: String,
+    pub post_response_script: String,
+    // Decode response bodies with this charset instead of detecting it
+    pub response_charset: Option<String>,
+    // Override the workspace's response history settings
+    pub history_max_count: Option<i32>,
+    pub history_max_age_days: Option<i32>,
+    #[ts(type = "number | null")]
+    pub history_max_bytes: Option<i64>,
     pub sort_priority: f32,
     pub url: String,
     pub url_parameters: Vec<HttpUrlParameter>,
@@ -500,14 +887,23 @@ pub enum HttpRequestIden {
     WorkspaceId,
     FolderId,
 
+    Assertions,
     Authentication,
     AuthenticationType,
     Body,
     BodyType,
     Description,
+    DisabledCookies,
+    Extractors,
     Headers,
+    HistoryMaxAgeDays,
+    HistoryMaxBytes,
+    HistoryMaxCount,
     Method,
     Name,
+    PostResponseScript,
+    PreRequestScript,
+    ResponseCharset,
     SortPriority,
     Url,
     UrlParameters,
@@ -520,7 +916,10 @@ impl<'s> TryFrom<&Row<'s>> for HttpRequest {
         let url_parameters: String = r.get("url_parameters")?;
         let body: String = r.get("body")?;
         let authentication: String = r.get("authentication")?;
+        let disabled_cookies: String = r.get("disabled_cookies")?;
         let headers: String = r.get("headers")?;
+        let assertions: String = r.get("assertions")?;
+        let extractors: String = r.get("extractors")?;
         Ok(Self {
             id: r.get("id")?,
             model: r.get("model")?,
@@ -536,9 +935,18 @@ impl<'s> TryFrom<&Row<'s>> for HttpRequest {
             description: r.get("description")?,
             authentication: serde_json::from_str(authentication.as_str()).unwrap_or_default(),
             authentication_type: r.get("authentication_type")?,
+            disabled_cookies: serde_json::from_str(disabled_cookies.as_str()).unwrap_or_default(),
+            extractors: serde_json::from_str(extractors.as_str()).unwrap_or_default(),
             headers: serde_json::from_str(headers.as_str()).unwrap_or_default(),
             folder_id: r.get("folder_id")?,
             name: r.get("name")?,
+            assertions: serde_json::from_str(assertions.as_str()).unwrap_or_default(),
+            pre_request_script: r.get("pre_request_script")?,
+            post_response_script: r.get("post_response_script")?,
+            response_charset: r.get("response_charset")?,
+            history_max_count: r.get("history_max_count")?,
+            history_max_age_days: r.get("history_max_age_days")?,
+            history_max_bytes: r.get("history_max_bytes")?,
         })
     }
 }
@@ -575,6 +983,7 @@ pub struct WebsocketConnection {
     pub headers: Vec<HttpResponseHeader>,
     pub state: WebsocketConnectionState,
     pub status: i32,
+    pub tls: Option<TlsInfo>,
     pub url: String,
 }
 
@@ -594,6 +1003,7 @@ pub enum WebsocketConnectionIden {
     Headers,
     State,
     Status,
+    Tls,
     Url,
 }
 
@@ -603,6 +1013,7 @@ impl<'s> TryFrom<&Row<'s>> for WebsocketConnection {
     fn try_from(r: &Row<'s>) -> Result<Self, Self::Error> {
         let headers: String = r.get("headers")?;
         let state: String = r.get("state")?;
+        let tls: Option<String> = r.get("tls")?;
         Ok(Self {
             id: r.get("id")?,
             model: r.get("model")?,
@@ -616,6 +1027,7 @@ impl<'s> TryFrom<&Row<'s>> for WebsocketConnection {
             error: r.get("error")?,
             state: serde_json::from_str(format!(r#""{state}""#).as_str()).unwrap(),
             status: r.get("status")?,
+            tls: tls.and_then(|t| serde_json::from_str(&t).ok()),
         })
     }
 }
@@ -788,6 +1200,74 @@ pub struct HttpResponseHeader {
     pub value: String,
 }
 
+/// A cookie that was sent with a request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: Option<String>,
+    pub path: String,
+}
+
+/// A single redirect that was followed before arriving at the final response
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseRedirect {
+    pub url: String,
+    pub method: String,
+    pub status: i32,
+    pub status_reason: Option<String>,
+    pub headers: Vec<HttpResponseHeader>,
+    pub request_headers: Vec<HttpResponseHeader>,
+    pub elapsed: i32,
+}
+
+/// Where the time for a response went, in milliseconds. Connection phases are for the final
+/// request, and are null when it reused an existing connection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseTiming {
+    // Following redirects before the final request was sent
+    pub redirect: f64,
+    // Null for IP addresses, which don't need resolving
+    pub dns: Option<f64>,
+    pub connect: Option<f64>,
+    pub tls: Option<f64>,
+    // From the request being sent to the response headers arriving
+    pub ttfb: f64,
+    // Null until the body has been read
+    pub download: Option<f64>,
+}
+
+/// Details of the TLS handshake made for a connection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct TlsInfo {
+    pub version: Option<String>,
+    pub cipher_suite: Option<String>,
+    pub alpn_protocol: Option<String>,
+    // Chain presented by the server, leaf first. Recorded even when it fails to validate.
+    pub certificates: Vec<TlsCertificate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct TlsCertificate {
+    pub subject: String,
+    pub issuer: String,
+    pub subject_alt_names: Vec<String>,
+    pub serial_number: String,
+    pub not_before: Option<NaiveDateTime>,
+    pub not_after: Option<NaiveDateTime>,
+    pub fingerprint_sha256: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -803,6 +1283,61 @@ impl Default for HttpResponseState {
     }
 }
 
+/// The outcome of checking a response against one of its request's assertions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpAssertionResult {
+    // As it was when checked, since the request's can change afterward
+    pub assertion: HttpAssertion,
+    pub passed: bool,
+    // What was found, to show next to what was expected
+    pub actual: Option<String>,
+    // Why the assertion couldn't be checked, like the body not being JSON
+    pub error: Option<String>,
+}
+
+/// A value an extractor pulled out of a response, and the variable it was saved as
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpExtractedValue {
+    // As it was when extracted, since the request's can change afterward
+    pub extractor: HttpExtractor,
+    pub value: Option<String>,
+    // Why nothing was extracted, like the body not being JSON
+    pub error: Option<String>,
+}
+
+/// The outcome of running every HTTP request in a folder or workspace, one after another
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpRunReport {
+    // Name of the folder or workspace that was run
+    pub name: String,
+    pub workspace_id: String,
+    pub folder_id: Option<String>,
+    pub environment_id: Option<String>,
+    pub started_at: NaiveDateTime,
+    pub elapsed: i32,
+    pub results: Vec<HttpRunResult>,
+}
+
+/// How one request of a run went
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpRunResult {
+    pub request_id: String,
+    pub name: String,
+    // Index of the data file row the request was sent with, if the run had one
+    pub iteration: Option<i32>,
+    pub passed: bool,
+    // Missing if the request was skipped because an earlier one failed
+    pub response: Option<HttpResponse>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -815,16 +1350,30 @@ pub struct HttpResponse {
     pub workspace_id: String,
     pub request_id: String,
 
+    pub assertion_results: Vec<HttpAssertionResult>,
     pub body_path: Option<String>,
     pub content_length: Option<i32>,
     pub elapsed: i32,
     pub elapsed_headers: i32,
     pub error: Option<String>,
+    pub extracted_values: Vec<HttpExtractedValue>,
     pub headers: Vec<HttpResponseHeader>,
+    pub redirects: Vec<HttpResponseRedirect>,
     pub remote_addr: Option<String>,
+    pub request_body_path: Option<String>,
+    // Bytes of the request body sent so far, for showing upload progress
+    #[ts(type = "number | null")]
+    pub request_bytes_sent: Option<i64>,
+    #[ts(type = "number | null")]
+    pub request_content_length: Option<i64>,
+    pub request_cookies: Vec<HttpResponseCookie>,
+    pub request_headers: Vec<HttpResponseHeader>,
+    pub request_url: String,
     pub status: i32,
     pub status_reason: Option<String>,
     pub state: HttpResponseState,
+    pub timing: Option<HttpResponseTiming>,
+    pub tls: Option<TlsInfo>,
     pub url: String,
     pub version: Option<String>,
 }
@@ -840,16 +1389,27 @@ pub enum HttpResponseIden {
     WorkspaceId,
     RequestId,
 
+    AssertionResults,
     BodyPath,
     ContentLength,
     Elapsed,
     ElapsedHeaders,
     Error,
+    ExtractedValues,
     Headers,
+    Redirects,
     RemoteAddr,
+    RequestBodyPath,
+    RequestBytesSent,
+    RequestContentLength,
+    RequestCookies,
+    RequestHeaders,
+    RequestUrl,
     Status,
     StatusReason,
     State,
+    Timing,
+    Tls,
     Url,
     Version,
 }
@@ -858,8 +1418,15 @@ impl<'s> TryFrom<&Row<'s>> for HttpResponse {
     type Error = rusqlite::Error;
 
     fn try_from(r: &Row<'s>) -> Result<Self, Self::Error> {
+        let assertion_results: String = r.get("assertion_results")?;
+        let extracted_values: String = r.get("extracted_values")?;
         let headers: String = r.get("headers")?;
+        let redirects: String = r.get("redirects")?;
+        let request_cookies: String = r.get("request_cookies")?;
+        let request_headers: String = r.get("request_headers")?;
         let state: String = r.get("state")?;
+        let timing: Option<String> = r.get("timing")?;
+        let tls: Option<String> = r.get("tls")?;
         Ok(Self {
             id: r.get("id")?,
             model: r.get("model")?,
@@ -878,7 +1445,18 @@ impl<'s> TryFrom<&Row<'s>> for HttpResponse {
             status_reason: r.get("status_reason")?,
             state: serde_json::from_str(format!(r#""{state}""#).as_str()).unwrap(),
             body_path: r.get("body_path")?,
+            assertion_results: serde_json::from_str(assertion_results.as_str()).unwrap_or_default(),
+            extracted_values: serde_json::from_str(extracted_values.as_str()).unwrap_or_default(),
             headers: serde_json::from_str(headers.as_str()).unwrap_or_default(),
+            redirects: serde_json::from_str(redirects.as_str()).unwrap_or_default(),
+            request_body_path: r.get("request_body_path")?,
+            request_bytes_sent: r.get("request_bytes_sent")?,
+            request_content_length: r.get("request_content_length")?,
+            request_cookies: serde_json::from_str(request_cookies.as_str()).unwrap_or_default(),
+            request_headers: serde_json::from_str(request_headers.as_str()).unwrap_or_default(),
+            request_url: r.get("request_url")?,
+            timing: timing.and_then(|t| serde_json::from_str(&t).ok()),
+            tls: tls.and_then(|t| serde_json::from_str(&t).ok()),
         })
     }
 }
@@ -892,6 +1470,134 @@ impl HttpResponse {
     }
 }
 
+/// A Server-Sent Event received on a `text/event-stream` response
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseEvent {
+    #[ts(type = "\"http_response_event\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+    pub request_id: String,
+    pub response_id: String,
+
+    pub event_type: String,
+    pub data: String,
+    // The event's `id` field, if it had one
+    pub event_id: Option<String>,
+    pub retry: Option<i32>,
+}
+
+#[derive(Iden)]
+pub enum HttpResponseEventIden {
+    #[iden = "http_response_events"]
+    Table,
+    Model,
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    WorkspaceId,
+    RequestId,
+    ResponseId,
+
+    EventType,
+    Data,
+    EventId,
+    Retry,
+}
+
+impl<'s> TryFrom<&Row<'s>> for HttpResponseEvent {
+    type Error = rusqlite::Error;
+
+    fn try_from(r: &Row<'s>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: r.get("id")?,
+            model: r.get("model")?,
+            workspace_id: r.get("workspace_id")?,
+            request_id: r.get("request_id")?,
+            response_id: r.get("response_id")?,
+            created_at: r.get("created_at")?,
+            updated_at: r.get("updated_at")?,
+            event_type: r.get("event_type")?,
+            data: r.get("data")?,
+            event_id: r.get("event_id")?,
+            retry: r.get("retry")?,
+        })
+    }
+}
+
+/// Part of a response body, decoded to text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseBodyChunk {
+    #[ts(type = "number")]
+    pub offset: u64,
+    // Can be less than asked for, so the chunk doesn't end partway through a character
+    #[ts(type = "number")]
+    pub length: u64,
+    #[ts(type = "number")]
+    pub total_length: u64,
+    pub charset: String,
+    pub text: String,
+}
+
+/// A range of lines of a response body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseBodyLines {
+    #[ts(type = "number")]
+    pub start_line: u64,
+    pub lines: Vec<String>,
+    // Byte offsets of the first line and of the line after the last one
+    #[ts(type = "number")]
+    pub offset: u64,
+    #[ts(type = "number")]
+    pub end_offset: u64,
+    pub eof: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseBodyMatch {
+    #[ts(type = "number")]
+    pub offset: u64,
+    #[ts(type = "number")]
+    pub length: u64,
+    #[ts(type = "number")]
+    pub line: u64,
+    // Text around the match, from the same line
+    pub preview: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseBodySearch {
+    pub matches: Vec<HttpResponseBodyMatch>,
+    // Where to continue from if the search stopped early because it found too many matches
+    #[ts(type = "number | null")]
+    pub next_offset: Option<u64>,
+    #[ts(type = "number | null")]
+    pub next_line: Option<u64>,
+}
+
+/// Sixteen bytes of a body, like a row of `hexdump -C`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseBodyHexdumpRow {
+    #[ts(type = "number")]
+    pub offset: u64,
+    pub hex: String,
+    pub ascii: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -1013,6 +1719,7 @@ pub struct GrpcConnection {
     pub service: String,
     pub status: i32,
     pub state: GrpcConnectionState,
+    pub tls: Option<TlsInfo>,
     pub trailers: BTreeMap<String, String>,
     pub url: String,
 }
@@ -1034,6 +1741,7 @@ pub enum GrpcConnectionIden {
     Service,
     State,
     Status,
+    Tls,
     Trailers,
     Url,
 }
@@ -1044,6 +1752,7 @@ impl<'s> TryFrom<&Row<'s>> for GrpcConnection {
     fn try_from(r: &Row<'s>) -> Result<Self, Self::Error> {
         let trailers: String = r.get("trailers")?;
         let state: String = r.get("state")?;
+        let tls: Option<String> = r.get("tls")?;
         Ok(Self {
             id: r.get("id")?,
             model: r.get("model")?,
@@ -1058,6 +1767,7 @@ impl<'s> TryFrom<&Row<'s>> for GrpcConnection {
             status: r.get("status")?,
             url: r.get("url")?,
             error: r.get("error")?,
+            tls: tls.and_then(|t| serde_json::from_str(&t).ok()),
             trailers: serde_json::from_str(trailers.as_str()).unwrap_or_default(),
         })
     }
@@ -1349,10 +2059,22 @@ fn default_true() -> bool {
     true
 }
 
+fn default_max_redirects() -> i32 {
+    10
+}
+
+fn default_history_max_count() -> i32 {
+    20
+}
+
 fn default_http_method() -> String {
     "GET".to_string()
 }
 
+fn default_authentication_type_inherit() -> Option<String> {
+    Some(AUTHENTICATION_TYPE_INHERIT.to_string())
+}
+
 pub enum ModelType {
     TypeCookieJar,
     TypeEnvironment,
@@ -1362,6 +2084,7 @@ pub enum ModelType {
     TypeGrpcRequest,
     TypeHttpRequest,
     TypeHttpResponse,
+    TypeHttpResponseEvent,
     TypePlugin,
     TypeSyncState,
     TypeWebSocketConnection,
@@ -1382,6 +2105,7 @@ impl ModelType {
             ModelType::TypeGrpcRequest => "gr",
             ModelType::TypeHttpRequest => "rq",
             ModelType::TypeHttpResponse => "rs",
+            ModelType::TypeHttpResponseEvent => "re",
             ModelType::TypePlugin => "pg",
             ModelType::TypeWorkspace => "wk",
             ModelType::TypeWorkspaceMeta => "wm",
@@ -1406,6 +2130,7 @@ pub enum AnyModel {
     GrpcRequest(GrpcRequest),
     HttpRequest(HttpRequest),
     HttpResponse(HttpResponse),
+    HttpResponseEvent(HttpResponseEvent),
     Plugin(Plugin),
     Settings(Settings),
     KeyValue(KeyValue),
@@ -1462,3 +2187,36 @@ impl<'de> Deserialize<'de> for AnyModel {
         Ok(model)
     }
 }
+
+#[cfg(test)]
+mod models_tests {
+    use crate::models::{ClientCertificate, Workspace, WorkspaceMeta};
+
+    #[test]
+    fn keeps_passphrases_out_of_workspaces() {
+        let certificate = ClientCertificate {
+            pfx_path: Some("client.p12".to_string()),
+            passphrase: Some("secret".to_string()),
+            ..Default::default()
+        };
+        let workspace = Workspace {
+            setting_client_certificates: vec![certificate.clone()],
+            ..Default::default()
+        };
+        assert!(!serde_json::to_string(&workspace).unwrap().contains("secret"));
+
+        let meta = WorkspaceMeta {
+            setting_client_certificate_passphrases: [
+                ("client.p12".to_string(), "secret".to_string()),
+                ("other.key".to_string(), "other".to_string()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let stored = ClientCertificate {
+            passphrase: None,
+            ..certificate.clone()
+        };
+        assert_eq!(meta.client_certificates(&[stored]), vec![certificate]);
+    }
+}
@@ -1,16 +1,18 @@
 use crate::error::Error::ModelNotFound;
 use crate::error::Result;
 use crate::models::{
-    AnyModel, CookieJar, CookieJarIden, Environment, EnvironmentIden, Folder, FolderIden,
-    GrpcConnection, GrpcConnectionIden, GrpcConnectionState, GrpcEvent, GrpcEventIden, GrpcRequest,
-    GrpcRequestIden, HttpRequest, HttpRequestIden, HttpResponse, HttpResponseHeader,
-    HttpResponseIden, HttpResponseState, KeyValue, KeyValueIden, ModelType, Plugin, PluginIden,
-    PluginKeyValue, PluginKeyValueIden, Settings, SettingsIden, SyncState, SyncStateIden,
-    WebsocketConnection, WebsocketConnectionIden, WebsocketEvent, WebsocketEventIden,
-    WebsocketRequest, WebsocketRequestIden, Workspace, WorkspaceIden, WorkspaceMeta,
-    WorkspaceMetaIden,
+    AnyModel, ClientCertificate, CookieJar, CookieJarIden, Environment, EnvironmentIden, Folder,
+    FolderIden, GrpcConnection, GrpcConnectionIden, GrpcConnectionState, GrpcEvent, GrpcEventIden,
+    GrpcRequest, GrpcRequestIden, HttpRequest, HttpRequestIden, HttpResponse, HttpResponseEvent,
+    HttpResponseEventIden, HttpResponseHeader, HttpResponseIden, HttpResponseState, KeyValue,
+    KeyValueIden, ModelType, Plugin, PluginIden, PluginKeyValue, PluginKeyValueIden, Settings,
+    SettingsIden, SyncState, SyncStateIden, WebsocketConnection, WebsocketConnectionIden,
+    WebsocketConnectionState, WebsocketEvent, WebsocketEventIden, WebsocketRequest,
+    WebsocketRequestIden, Workspace, WorkspaceIden, WorkspaceMeta, WorkspaceMetaIden,
 };
 use crate::plugin::SqliteConnection;
+use crate::render::Inherited;
+use crate::retention::{prune_grpc_connections, prune_http_responses, prune_websocket_connections};
 use chrono::{NaiveDateTime, Utc};
 use log::{debug, error, info, warn};
 use nanoid::nanoid;
@@ -25,8 +27,6 @@ use std::path::Path;
 use tauri::{AppHandle, Emitter, Listener, Manager, Runtime, WebviewWindow};
 use ts_rs::TS;
 
-const MAX_HISTORY_ITEMS: usize = 20;
-
 pub async fn set_key_value_string<R: Runtime>(
     mgr: &WebviewWindow<R>,
     namespace: &str,
@@ -302,6 +302,18 @@ pub async fn get_workspace_meta<R: Runtime>(
     Ok(stmt.query_row(&*params.as_params(), |row| row.try_into()).optional()?)
 }
 
+/// The workspace's client certificates, with the passphrases kept in its workspace meta
+pub async fn get_client_certificates<R: Runtime>(
+    mgr: &impl Manager<R>,
+    workspace: &Workspace,
+) -> Result<Vec<ClientCertificate>> {
+    let certificates = &workspace.setting_client_certificates;
+    Ok(match get_workspace_meta(mgr, workspace).await? {
+        Some(m) => m.client_certificates(certificates),
+        None => certificates.clone(),
+    })
+}
+
 pub async fn get_or_create_workspace_meta<R: Runtime>(
     window: &WebviewWindow<R>,
     workspace: &Workspace,
@@ -356,8 +368,21 @@ pub async fn upsert_workspace<R: Runtime>(
             WorkspaceIden::CreatedAt,
             WorkspaceIden::UpdatedAt,
             WorkspaceIden::Name,
+            WorkspaceIden::Authentication,
+            WorkspaceIden::AuthenticationType,
             WorkspaceIden::Description,
+            WorkspaceIden::Headers,
+            WorkspaceIden::PostResponseScript,
+            WorkspaceIden::PreRequestScript,
+            WorkspaceIden::SettingCaCertificates,
+            WorkspaceIden::SettingCertificatePins,
+            WorkspaceIden::SettingClientCertificates,
             WorkspaceIden::SettingFollowRedirects,
+            WorkspaceIden::SettingHistoryMaxAgeDays,
+            WorkspaceIden::SettingHistoryMaxBytes,
+            WorkspaceIden::SettingHistoryMaxCount,
+            WorkspaceIden::SettingMaxRedirects,
+            WorkspaceIden::SettingReconnectEventStreams,
             WorkspaceIden::SettingRequestTimeout,
             WorkspaceIden::SettingValidateCertificates,
         ])
@@ -366,8 +391,21 @@ pub async fn upsert_workspace<R: Runtime>(
             timestamp_for_upsert(update_source, workspace.created_at).into(),
             timestamp_for_upsert(update_source, workspace.updated_at).into(),
             trimmed_name.into(),
+            serde_json::to_string(&workspace.authentication)?.into(),
+            workspace.authentication_type.as_ref().map(|s| s.as_str()).into(),
             workspace.description.into(),
+            serde_json::to_string(&workspace.headers)?.into(),
+            workspace.post_response_script.into(),
+            workspace.pre_request_script.into(),
+            serde_json::to_string(&workspace.setting_ca_certificates)?.into(),
+            serde_json::to_string(&workspace.setting_certificate_pins)?.into(),
+            serde_json::to_string(&workspace.setting_client_certificates)?.into(),
             workspace.setting_follow_redirects.into(),
+            workspace.setting_history_max_age_days.into(),
+            workspace.setting_history_max_bytes.into(),
+            workspace.setting_history_max_count.into(),
+            workspace.setting_max_redirects.into(),
+            workspace.setting_reconnect_event_streams.into(),
             workspace.setting_request_timeout.into(),
             workspace.setting_validate_certificates.into(),
         ])
@@ -376,9 +414,22 @@ pub async fn upsert_workspace<R: Runtime>(
                 .update_columns([
                     WorkspaceIden::UpdatedAt,
                     WorkspaceIden::Name,
+                    WorkspaceIden::Authentication,
+                    WorkspaceIden::AuthenticationType,
                     WorkspaceIden::Description,
+                    WorkspaceIden::Headers,
+                    WorkspaceIden::PostResponseScript,
+                    WorkspaceIden::PreRequestScript,
+                    WorkspaceIden::SettingCaCertificates,
+                    WorkspaceIden::SettingCertificatePins,
+                    WorkspaceIden::SettingClientCertificates,
                     WorkspaceIden::SettingRequestTimeout,
                     WorkspaceIden::SettingFollowRedirects,
+                    WorkspaceIden::SettingHistoryMaxAgeDays,
+                    WorkspaceIden::SettingHistoryMaxBytes,
+                    WorkspaceIden::SettingHistoryMaxCount,
+                    WorkspaceIden::SettingMaxRedirects,
+                    WorkspaceIden::SettingReconnectEventStreams,
                     WorkspaceIden::SettingRequestTimeout,
                     WorkspaceIden::SettingValidateCertificates,
                 ])
@@ -414,20 +465,26 @@ pub async fn upsert_workspace_meta<R: Runtime>(
             WorkspaceMetaIden::WorkspaceId,
             WorkspaceMetaIden::CreatedAt,
             WorkspaceMetaIden::UpdatedAt,
+            WorkspaceMetaIden::SettingClientCertificatePassphrases,
             WorkspaceMetaIden::SettingSyncDir,
+            WorkspaceMetaIden::SettingTrustScripts,
         ])
         .values_panic([
             id.as_str().into(),
             workspace_meta.workspace_id.into(),
             timestamp_for_upsert(update_source, workspace_meta.created_at).into(),
             timestamp_for_upsert(update_source, workspace_meta.updated_at).into(),
+            serde_json::to_string(&workspace_meta.setting_client_certificate_passphrases)?.into(),
             workspace_meta.setting_sync_dir.into(),
+            workspace_meta.setting_trust_scripts.into(),
         ])
         .on_conflict(
             OnConflict::column(GrpcRequestIden::Id)
                 .update_columns([
                     WorkspaceMetaIden::UpdatedAt,
+                    WorkspaceMetaIden::SettingClientCertificatePassphrases,
                     WorkspaceMetaIden::SettingSyncDir,
+                    WorkspaceMetaIden::SettingTrustScripts,
                 ])
                 .to_owned(),
         )
@@ -666,12 +723,14 @@ pub async fn upsert_grpc_connection<R: Runtime>(
     connection: &GrpcConnection,
     update_source: &UpdateSource,
 ) -> Result<GrpcConnection> {
-    let connections =
-        list_grpc_connections_for_request(window, connection.request_id.as_str()).await?;
-    for c in connections.iter().skip(MAX_HISTORY_ITEMS - 1) {
-        debug!("Deleting old grpc connection {}", c.id);
-        delete_grpc_connection(window, c.id.as_str(), update_source).await?;
-    }
+    prune_grpc_connections(
+        window,
+        &connection.workspace_id,
+        &connection.request_id,
+        1,
+        update_source,
+    )
+    .await?;
 
     let id = match connection.id.as_str() {
         "" => generate_model_id(ModelType::TypeGrpcConnection),
@@ -693,6 +752,7 @@ pub async fn upsert_grpc_connection<R: Runtime>(
             GrpcConnectionIden::State,
             GrpcConnectionIden::Status,
             GrpcConnectionIden::Error,
+            GrpcConnectionIden::Tls,
             GrpcConnectionIden::Trailers,
             GrpcConnectionIden::Url,
         ])
@@ -708,6 +768,7 @@ pub async fn upsert_grpc_connection<R: Runtime>(
             serde_json::to_value(&connection.state)?.as_str().into(),
             connection.status.into(),
             connection.error.as_ref().map(|s| s.as_str()).into(),
+            connection.tls.as_ref().map(serde_json::to_string).transpose()?.into(),
             serde_json::to_string(&connection.trailers)?.into(),
             connection.url.as_str().into(),
         ])
@@ -721,6 +782,7 @@ pub async fn upsert_grpc_connection<R: Runtime>(
                     GrpcConnectionIden::Status,
                     GrpcConnectionIden::State,
                     GrpcConnectionIden::Error,
+                    GrpcConnectionIden::Tls,
                     GrpcConnectionIden::Trailers,
                     GrpcConnectionIden::Url,
                 ])
@@ -1183,12 +1245,14 @@ pub async fn upsert_websocket_connection<R: Runtime>(
     connection: &WebsocketConnection,
     update_source: &UpdateSource,
 ) -> Result<WebsocketConnection> {
-    let connections =
-        list_websocket_connections_for_request(window, connection.request_id.as_str()).await?;
-    for c in connections.iter().skip(MAX_HISTORY_ITEMS - 1) {
-        debug!("Deleting old websocket connection {}", c.id);
-        delete_websocket_connection(window, c.id.as_str(), update_source).await?;
-    }
+    prune_websocket_connections(
+        window,
+        &connection.workspace_id,
+        &connection.request_id,
+        1,
+        update_source,
+    )
+    .await?;
 
     let id = match connection.id.as_str() {
         "" => generate_model_id(ModelType::TypeWebSocketConnection),
@@ -1209,6 +1273,7 @@ pub async fn upsert_websocket_connection<R: Runtime>(
             WebsocketConnectionIden::Headers,
             WebsocketConnectionIden::State,
             WebsocketConnectionIden::Status,
+            WebsocketConnectionIden::Tls,
             WebsocketConnectionIden::Url,
         ])
         .values_panic([
@@ -1222,6 +1287,7 @@ pub async fn upsert_websocket_connection<R: Runtime>(
             serde_json::to_string(&connection.headers)?.into(),
             serde_json::to_value(&connection.state)?.as_str().into(),
             connection.status.into(),
+            connection.tls.as_ref().map(serde_json::to_string).transpose()?.into(),
             connection.url.as_str().into(),
         ])
         .on_conflict(
@@ -1233,6 +1299,7 @@ pub async fn upsert_websocket_connection<R: Runtime>(
                     WebsocketConnectionIden::Headers,
                     WebsocketConnectionIden::State,
                     WebsocketConnectionIden::Status,
+                    WebsocketConnectionIden::Tls,
                     WebsocketConnectionIden::Url,
                 ])
                 .to_owned(),
@@ -1713,6 +1780,33 @@ pub async fn list_folders<R: Runtime>(
     Ok(items.map(|v| v.unwrap()).collect())
 }
 
+/// The folder and every folder it's nested in, outermost first
+pub async fn list_folder_ancestors<R: Runtime>(
+    mgr: &impl Manager<R>,
+    folder_id: Option<&str>,
+) -> Result<Vec<Folder>> {
+    let mut folders = Vec::new();
+    let mut next_id = folder_id.map(|id| id.to_string());
+    while let Some(id) = next_id {
+        let folder = get_folder(mgr, &id).await?;
+        next_id = folder.folder_id.clone();
+        folders.insert(0, folder);
+    }
+    Ok(folders)
+}
+
+/// Headers and authentication that requests in the given folder (or at the top of the
+/// workspace) inherit
+pub async fn get_inherited<R: Runtime>(
+    mgr: &impl Manager<R>,
+    workspace_id: &str,
+    folder_id: Option<&str>,
+) -> Result<Inherited> {
+    let workspace = get_workspace(mgr, workspace_id).await?;
+    let folders = list_folder_ancestors(mgr, folder_id).await?;
+    Ok(Inherited::new(&workspace, &folders))
+}
+
 pub async fn delete_folder<R: Runtime>(
     window: &WebviewWindow<R>,
     id: &str,
@@ -1759,7 +1853,13 @@ pub async fn upsert_folder<R: Runtime>(
             FolderIden::FolderId,
             FolderIden::Name,
             FolderIden::Description,
+            FolderIden::Authentication,
+            FolderIden::AuthenticationType,
+            FolderIden::Headers,
+            FolderIden::PostResponseScript,
+            FolderIden::PreRequestScript,
             FolderIden::SortPriority,
+            FolderIden::Variables,
         ])
         .values_panic([
             id.as_str().into(),
@@ -1769,7 +1869,13 @@ pub async fn upsert_folder<R: Runtime>(
             folder.folder_id.as_ref().map(|s| s.as_str()).into(),
             trimmed_name.into(),
             folder.description.into(),
+            serde_json::to_string(&folder.authentication)?.into(),
+            folder.authentication_type.as_ref().map(|s| s.as_str()).into(),
+            serde_json::to_string(&folder.headers)?.into(),
+            folder.post_response_script.into(),
+            folder.pre_request_script.into(),
             folder.sort_priority.into(),
+            serde_json::to_string(&folder.variables)?.into(),
         ])
         .on_conflict(
             OnConflict::column(GrpcEventIden::Id)
@@ -1778,7 +1884,13 @@ pub async fn upsert_folder<R: Runtime>(
                     FolderIden::Name,
                     FolderIden::Description,
                     FolderIden::FolderId,
+                    FolderIden::Authentication,
+                    FolderIden::AuthenticationType,
+                    FolderIden::Headers,
+                    FolderIden::PostResponseScript,
+                    FolderIden::PreRequestScript,
                     FolderIden::SortPriority,
+                    FolderIden::Variables,
                 ])
                 .to_owned(),
         )
@@ -1908,7 +2020,16 @@ pub async fn upsert_http_request<R: Runtime>(
             HttpRequestIden::BodyType,
             HttpRequestIden::Authentication,
             HttpRequestIden::AuthenticationType,
+            HttpRequestIden::DisabledCookies,
             HttpRequestIden::Headers,
+            HttpRequestIden::Assertions,
+            HttpRequestIden::Extractors,
+            HttpRequestIden::HistoryMaxAgeDays,
+            HttpRequestIden::HistoryMaxBytes,
+            HttpRequestIden::HistoryMaxCount,
+            HttpRequestIden::PostResponseScript,
+            HttpRequestIden::PreRequestScript,
+            HttpRequestIden::ResponseCharset,
             HttpRequestIden::SortPriority,
         ])
         .values_panic([
@@ -1926,7 +2047,16 @@ pub async fn upsert_http_request<R: Runtime>(
             request.body_type.as_ref().map(|s| s.as_str()).into(),
             serde_json::to_string(&request.authentication)?.into(),
             request.authentication_type.as_ref().map(|s| s.as_str()).into(),
+            serde_json::to_string(&request.disabled_cookies)?.into(),
             serde_json::to_string(&request.headers)?.into(),
+            serde_json::to_string(&request.assertions)?.into(),
+            serde_json::to_string(&request.extractors)?.into(),
+            request.history_max_age_days.into(),
+            request.history_max_bytes.into(),
+            request.history_max_count.into(),
+            request.post_response_script.into(),
+            request.pre_request_script.into(),
+            request.response_charset.as_ref().map(|s| s.as_str()).into(),
             request.sort_priority.into(),
         ])
         .on_conflict(
@@ -1943,8 +2073,17 @@ pub async fn upsert_http_request<R: Runtime>(
                     HttpRequestIden::BodyType,
                     HttpRequestIden::Authentication,
                     HttpRequestIden::AuthenticationType,
+                    HttpRequestIden::DisabledCookies,
                     HttpRequestIden::Url,
                     HttpRequestIden::UrlParameters,
+                    HttpRequestIden::Assertions,
+                    HttpRequestIden::Extractors,
+                    HttpRequestIden::HistoryMaxAgeDays,
+                    HttpRequestIden::HistoryMaxBytes,
+                    HttpRequestIden::HistoryMaxCount,
+                    HttpRequestIden::PostResponseScript,
+                    HttpRequestIden::PreRequestScript,
+                    HttpRequestIden::ResponseCharset,
                     HttpRequestIden::SortPriority,
                 ])
                 .to_owned(),
@@ -2057,11 +2196,7 @@ pub async fn create_http_response<R: Runtime>(
     remote_addr: Option<&str>,
     update_source: &UpdateSource,
 ) -> Result<HttpResponse> {
-    let responses = list_http_responses_for_request(window, request_id, None).await?;
-    for response in responses.iter().skip(MAX_HISTORY_ITEMS - 1) {
-        debug!("Deleting old response {}", response.id);
-        delete_http_response(window, response.id.as_str(), update_source).await?;
-    }
+    prune_http_responses(window, request_id, 1, update_source).await?;
 
     let req = match get_http_request(window, request_id).await? {
         None => return Err(ModelNotFound(request_id.to_string())),
@@ -2133,6 +2268,21 @@ pub async fn cancel_pending_grpc_connections(app: &AppHandle) -> Result<()> {
     Ok(())
 }
 
+pub async fn cancel_pending_websocket_connections(app: &AppHandle) -> Result<()> {
+    let dbm = &*app.app_handle().state::<SqliteConnection>();
+    let db = dbm.0.lock().await.get().unwrap();
+
+    let closed = serde_json::to_value(&WebsocketConnectionState::Closed)?;
+    let (sql, params) = Query::update()
+        .table(WebsocketConnectionIden::Table)
+        .values([(WebsocketConnectionIden::State, closed.as_str().into())])
+        .cond_where(Expr::col(WebsocketConnectionIden::State).ne(closed.as_str()))
+        .build_rusqlite(SqliteQueryBuilder);
+
+    db.execute(sql.as_str(), &*params.as_params())?;
+    Ok(())
+}
+
 pub async fn cancel_pending_responses(app: &AppHandle) -> Result<()> {
     let dbm = &*app.app_handle().state::<SqliteConnection>();
     let db = dbm.0.lock().await.get().unwrap();
@@ -2186,16 +2336,51 @@ pub async fn update_http_response<R: Runtime>(
             (HttpResponseIden::ContentLength, response.content_length.into()),
             (HttpResponseIden::BodyPath, response.body_path.as_ref().map(|s| s.as_str()).into()),
             (HttpResponseIden::Error, response.error.as_ref().map(|s| s.as_str()).into()),
+            (
+                HttpResponseIden::AssertionResults,
+                serde_json::to_string(&response.assertion_results).unwrap_or_default().into(),
+            ),
+            (
+                HttpResponseIden::ExtractedValues,
+                serde_json::to_string(&response.extracted_values).unwrap_or_default().into(),
+            ),
             (
                 HttpResponseIden::Headers,
                 serde_json::to_string(&response.headers).unwrap_or_default().into(),
             ),
+            (
+                HttpResponseIden::Redirects,
+                serde_json::to_string(&response.redirects).unwrap_or_default().into(),
+            ),
             (HttpResponseIden::Version, response.version.as_ref().map(|s| s.as_str()).into()),
             (HttpResponseIden::State, serde_json::to_value(&response.state)?.as_str().into()),
             (
                 HttpResponseIden::RemoteAddr,
                 response.remote_addr.as_ref().map(|s| s.as_str()).into(),
             ),
+            (
+                HttpResponseIden::RequestBodyPath,
+                response.request_body_path.as_ref().map(|s| s.as_str()).into(),
+            ),
+            (HttpResponseIden::RequestBytesSent, response.request_bytes_sent.into()),
+            (HttpResponseIden::RequestContentLength, response.request_content_length.into()),
+            (
+                HttpResponseIden::RequestCookies,
+                serde_json::to_string(&response.request_cookies).unwrap_or_default().into(),
+            ),
+            (
+                HttpResponseIden::RequestHeaders,
+                serde_json::to_string(&response.request_headers).unwrap_or_default().into(),
+            ),
+            (HttpResponseIden::RequestUrl, response.request_url.as_str().into()),
+            (
+                HttpResponseIden::Timing,
+                response.timing.as_ref().and_then(|t| serde_json::to_string(t).ok()).into(),
+            ),
+            (
+                HttpResponseIden::Tls,
+                response.tls.as_ref().and_then(|t| serde_json::to_string(t).ok()).into(),
+            ),
         ])
         .returning_all()
         .build_rusqlite(SqliteQueryBuilder);
@@ -2221,6 +2406,82 @@ pub async fn get_http_response<R: Runtime>(
     Ok(stmt.query_row(&*params.as_params(), |row| row.try_into())?)
 }
 
+pub async fn upsert_http_response_event<R: Runtime>(
+    window: &WebviewWindow<R>,
+    event: &HttpResponseEvent,
+    update_source: &UpdateSource,
+) -> Result<HttpResponseEvent> {
+    let id = match event.id.as_str() {
+        "" => generate_model_id(ModelType::TypeHttpResponseEvent),
+        _ => event.id.to_string(),
+    };
+
+    let dbm = &*window.app_handle().state::<SqliteConnection>();
+    let db = dbm.0.lock().await.get().unwrap();
+    let (sql, params) = Query::insert()
+        .into_table(HttpResponseEventIden::Table)
+        .columns([
+            HttpResponseEventIden::Id,
+            HttpResponseEventIden::CreatedAt,
+            HttpResponseEventIden::UpdatedAt,
+            HttpResponseEventIden::WorkspaceId,
+            HttpResponseEventIden::RequestId,
+            HttpResponseEventIden::ResponseId,
+            HttpResponseEventIden::EventType,
+            HttpResponseEventIden::Data,
+            HttpResponseEventIden::EventId,
+            HttpResponseEventIden::Retry,
+        ])
+        .values_panic([
+            id.as_str().into(),
+            timestamp_for_upsert(update_source, event.created_at).into(),
+            timestamp_for_upsert(update_source, event.updated_at).into(),
+            event.workspace_id.as_str().into(),
+            event.request_id.as_str().into(),
+            event.response_id.as_str().into(),
+            event.event_type.as_str().into(),
+            event.data.as_str().into(),
+            event.event_id.as_ref().map(|s| s.as_str()).into(),
+            event.retry.into(),
+        ])
+        .on_conflict(
+            OnConflict::column(HttpResponseEventIden::Id)
+                .update_columns([
+                    HttpResponseEventIden::UpdatedAt,
+                    HttpResponseEventIden::EventType,
+                    HttpResponseEventIden::Data,
+                    HttpResponseEventIden::EventId,
+                    HttpResponseEventIden::Retry,
+                ])
+                .to_owned(),
+        )
+        .returning_all()
+        .build_rusqlite(SqliteQueryBuilder);
+
+    let mut stmt = db.prepare(sql.as_str())?;
+    let m: HttpResponseEvent = stmt.query_row(&*params.as_params(), |row| row.try_into())?;
+    emit_upserted_model(window, &AnyModel::HttpResponseEvent(m.to_owned()), update_source);
+    Ok(m)
+}
+
+pub async fn list_http_response_events<R: Runtime>(
+    mgr: &impl Manager<R>,
+    response_id: &str,
+) -> Result<Vec<HttpResponseEvent>> {
+    let dbm = &*mgr.state::<SqliteConnection>();
+    let db = dbm.0.lock().await.get().unwrap();
+
+    let (sql, params) = Query::select()
+        .from(HttpResponseEventIden::Table)
+        .cond_where(Expr::col(HttpResponseEventIden::ResponseId).eq(response_id))
+        .column(Asterisk)
+        .order_by(HttpResponseEventIden::CreatedAt, Order::Asc)
+        .build_rusqlite(SqliteQueryBuilder);
+    let mut stmt = db.prepare(sql.as_str())?;
+    let items = stmt.query_map(&*params.as_params(), |row| row.try_into())?;
+    Ok(items.map(|v| v.unwrap()).collect())
+}
+
 pub async fn delete_http_response<R: Runtime>(
     window: &WebviewWindow<R>,
     id: &str,
@@ -2228,8 +2489,8 @@ pub async fn delete_http_response<R: Runtime>(
 ) -> Result<HttpResponse> {
     let resp = get_http_response(window, id).await?;
 
-    // Delete the body file if it exists
-    if let Some(p) = resp.body_path.clone() {
+    // Delete the body files if they exist
+    for p in [resp.body_path.clone(), resp.request_body_path.clone()].into_iter().flatten() {
         if let Err(e) = fs::remove_file(p) {
             error!("Failed to delete body file: {}", e);
         };
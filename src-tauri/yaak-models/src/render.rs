@@ -1,18 +1,164 @@
-use std::collections::HashMap;
-use crate::models::{Environment, EnvironmentVariable};
+use crate::models::{
+    Environment, EnvironmentVariable, Folder, HttpRequestHeader, Workspace,
+    AUTHENTICATION_TYPE_INHERIT,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use ts_rs::TS;
+
+/// Variables from one place, like an environment, a folder, or a row of a data file
+#[derive(Debug, Clone, Default)]
+pub struct VariableLayer {
+    pub source: String,
+    pub variables: Vec<EnvironmentVariable>,
+}
+
+/// The value a variable ends up with, and the name of the layer it came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct ResolvedVariable {
+    pub name: String,
+    pub value: String,
+    pub source: String,
+}
+
+/// Variables to render templates with, stacked in layers where each overrides the ones below
+/// it. The base environment and the active sub environment are always the bottom two, then
+/// the folders a request is in, and things like a row of a runner's data file can be added on
+/// top.
+#[derive(Debug, Clone, Default)]
+pub struct VariableLayers {
+    layers: Vec<VariableLayer>,
+}
+
+impl VariableLayers {
+    pub fn new(base_environment: &Environment, environment: Option<&Environment>) -> Self {
+        let mut layers = Self::default();
+        layers.push(&base_environment.name, base_environment.variables.clone());
+        if let Some(e) = environment {
+            layers.push(&e.name, e.variables.clone());
+        }
+        layers
+    }
+
+    /// Add a layer that overrides every layer added before it
+    pub fn push(&mut self, source: &str, variables: Vec<EnvironmentVariable>) {
+        self.layers.push(VariableLayer {
+            source: source.to_string(),
+            variables,
+        });
+    }
+
+    /// Flatten the layers into the value each variable ends up with
+    pub fn to_hashmap(&self) -> HashMap<String, String> {
+        let mut variables = HashMap::new();
+        for layer in &self.layers {
+            variables = add_variable_to_map(variables, &layer.variables);
+        }
+        variables
+    }
+
+    /// Like [`Self::to_hashmap`], but keeping which layer supplied each value, sorted by name
+    pub fn resolve(&self) -> Vec<ResolvedVariable> {
+        let mut resolved: BTreeMap<&str, ResolvedVariable> = BTreeMap::new();
+        for layer in &self.layers {
+            for v in layer.variables.iter().filter(|v| v.enabled && !v.value.is_empty()) {
+                let variable = ResolvedVariable {
+                    name: v.name.clone(),
+                    value: v.value.clone(),
+                    source: layer.source.clone(),
+                };
+                resolved.insert(v.name.as_str(), variable);
+            }
+        }
+        resolved.into_values().collect()
+    }
+}
+
+/// Headers, authentication, and variables a request inherits from its workspace and the
+/// folders it's in. Each folder overrides the ones it's inside of, and the request overrides
+/// them all.
+#[derive(Debug, Clone, Default)]
+pub struct Inherited {
+    pub headers: Vec<HttpRequestHeader>,
+    pub authentication_type: Option<String>,
+    pub authentication: BTreeMap<String, Value>,
+    // One layer per folder, outermost first
+    pub variables: Vec<VariableLayer>,
+}
+
+impl Inherited {
+    /// Resolve what a request gets from its workspace and folders, outermost folder first
+    pub fn new(workspace: &Workspace, folders: &[Folder]) -> Self {
+        let mut inherited = Self::default();
+        inherited.apply(
+            &workspace.headers,
+            &workspace.authentication_type,
+            &workspace.authentication,
+        );
+        for f in folders {
+            inherited.apply(&f.headers, &f.authentication_type, &f.authentication);
+            inherited.variables.push(VariableLayer {
+                source: f.name.clone(),
+                variables: f.variables.clone(),
+            });
+        }
+        inherited
+    }
+
+    /// Variables of the environments, overridden by those of each folder
+    pub fn variable_layers(
+        &self,
+        base_environment: &Environment,
+        environment: Option<&Environment>,
+    ) -> VariableLayers {
+        let mut layers = VariableLayers::new(base_environment, environment);
+        layers.layers.extend(self.variables.iter().cloned());
+        layers
+    }
+
+    fn apply(
+        &mut self,
+        headers: &[HttpRequestHeader],
+        authentication_type: &Option<String>,
+        authentication: &BTreeMap<String, Value>,
+    ) {
+        self.headers = self.headers(headers);
+        (self.authentication_type, self.authentication) =
+            self.authentication(authentication_type, authentication);
+    }
+
+    /// The inherited headers followed by the given ones, where an enabled header replaces any
+    /// inherited header with the same name
+    pub fn headers(&self, headers: &[HttpRequestHeader]) -> Vec<HttpRequestHeader> {
+        let overridden = |name: &str| {
+            headers.iter().any(|h| h.enabled && h.name.trim().eq_ignore_ascii_case(name.trim()))
+        };
+        self.headers.iter().filter(|h| !overridden(&h.name)).chain(headers).cloned().collect()
+    }
+
+    /// The inherited authentication if the given type is to inherit it, or else the given one
+    pub fn authentication(
+        &self,
+        authentication_type: &Option<String>,
+        authentication: &BTreeMap<String, Value>,
+    ) -> (Option<String>, BTreeMap<String, Value>) {
+        match authentication_type.as_deref() {
+            Some(AUTHENTICATION_TYPE_INHERIT) => {
+                (self.authentication_type.clone(), self.authentication.clone())
+            }
+            _ => (authentication_type.clone(), authentication.clone()),
+        }
+    }
+}
 
 pub fn make_vars_hashmap(
     base_environment: &Environment,
     environment: Option<&Environment>,
 ) -> HashMap<String, String> {
-    let mut variables = HashMap::new();
-    variables = add_variable_to_map(variables, &base_environment.variables);
-
-    if let Some(e) = environment {
-        variables = add_variable_to_map(variables, &e.variables);
-    }
-
-    variables
+    VariableLayers::new(base_environment, environment).to_hashmap()
 }
 
 fn add_variable_to_map(
@@ -32,3 +178,146 @@ fn add_variable_to_map(
     map
 }
 
+#[cfg(test)]
+mod render_tests {
+    use crate::models::{
+        Environment, EnvironmentVariable, Folder, HttpRequestHeader, Workspace,
+        AUTHENTICATION_TYPE_INHERIT,
+    };
+    use crate::render::{Inherited, VariableLayers};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn var(name: &str, value: &str) -> EnvironmentVariable {
+        EnvironmentVariable {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            id: None,
+        }
+    }
+
+    #[test]
+    fn later_layers_override() {
+        let base = Environment {
+            variables: vec![
+                var("host", "base"),
+                var("user", "base"),
+                var("token", "base"),
+            ],
+            ..Default::default()
+        };
+        let environment = Environment {
+            variables: vec![var("host", "env"), var("user", "env")],
+            ..Default::default()
+        };
+
+        let mut layers = VariableLayers::new(&base, Some(&environment));
+        layers.push("Row", vec![var("user", "row"), var("token", "")]);
+        let vars = layers.to_hashmap();
+        assert_eq!(vars["host"], "env");
+        assert_eq!(vars["user"], "row");
+        // Empty values never override, same as in environments
+        assert_eq!(vars["token"], "base");
+    }
+
+    #[test]
+    fn folders_override_environments() {
+        let base = Environment {
+            name: "Global Variables".to_string(),
+            variables: vec![var("host", "base"), var("user", "base")],
+            ..Default::default()
+        };
+        let environment = Environment {
+            name: "Staging".to_string(),
+            variables: vec![var("host", "staging")],
+            ..Default::default()
+        };
+        let outer = Folder {
+            name: "Users".to_string(),
+            variables: vec![var("user", "outer"), var("path", "/users")],
+            ..Default::default()
+        };
+        let inner = Folder {
+            name: "Admins".to_string(),
+            variables: vec![var("path", "/admins")],
+            ..Default::default()
+        };
+
+        let inherited = Inherited::new(&Workspace::default(), &[outer, inner]);
+        let mut layers = inherited.variable_layers(&base, Some(&environment));
+        layers.push("Row", vec![var("host", "row")]);
+        let resolved = layers.resolve();
+        let resolved: Vec<(&str, &str, &str)> = resolved
+            .iter()
+            .map(|v| (v.name.as_str(), v.value.as_str(), v.source.as_str()))
+            .collect();
+        assert_eq!(
+            resolved,
+            [
+                ("host", "row", "Row"),
+                ("path", "/admins", "Admins"),
+                ("user", "outer", "Users"),
+            ]
+        );
+    }
+
+    fn header(name: &str, value: &str) -> HttpRequestHeader {
+        HttpRequestHeader {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            id: None,
+        }
+    }
+
+    fn auth(token: &str) -> BTreeMap<String, serde_json::Value> {
+        BTreeMap::from([("token".to_string(), json!(token))])
+    }
+
+    #[test]
+    fn inherits_from_workspace_and_folders() {
+        let inherit = Some(AUTHENTICATION_TYPE_INHERIT.to_string());
+        let bearer = Some("bearer".to_string());
+        let workspace = Workspace {
+            headers: vec![header("X-Tenant", "acme"), header("Accept", "*/*")],
+            authentication_type: bearer.clone(),
+            authentication: auth("workspace"),
+            ..Default::default()
+        };
+        let outer = Folder {
+            headers: vec![header("x-tenant", "outer")],
+            authentication_type: inherit.clone(),
+            ..Default::default()
+        };
+        let inner = Folder {
+            authentication_type: bearer.clone(),
+            authentication: auth("inner"),
+            ..Default::default()
+        };
+
+        let inherited = Inherited::new(&workspace, &[outer.clone(), inner]);
+        let headers = inherited.headers(&[header("Accept", "application/json")]);
+        let headers: Vec<(&str, &str)> =
+            headers.iter().map(|h| (h.name.as_str(), h.value.as_str())).collect();
+        assert_eq!(headers, [("x-tenant", "outer"), ("Accept", "application/json")]);
+        assert_eq!(
+            inherited.authentication(&inherit, &auth("request")),
+            (bearer.clone(), auth("inner"))
+        );
+        assert_eq!(inherited.authentication(&None, &auth("request")), (None, auth("request")));
+
+        // Folders that inherit pass along the workspace's, and a workspace that inherits has none
+        let inherited = Inherited::new(&workspace, std::slice::from_ref(&outer));
+        assert_eq!(
+            inherited.authentication(&inherit, &BTreeMap::new()),
+            (bearer, auth("workspace"))
+        );
+        let workspace = Workspace {
+            authentication_type: inherit.clone(),
+            ..workspace
+        };
+        let inherited = Inherited::new(&workspace, &[outer]);
+        assert_eq!(inherited.authentication(&inherit, &BTreeMap::new()).0, None);
+    }
+}
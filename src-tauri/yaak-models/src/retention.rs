@@ -0,0 +1,415 @@
+use crate::error::Result;
+use crate::models::{
+    GrpcConnectionState, GrpcEventIden, HttpRequest, HttpResponse, HttpResponseIden,
+    HttpResponseState, WebsocketConnectionState, WebsocketEventIden, Workspace,
+};
+use crate::plugin::SqliteConnection;
+use crate::queries::{
+    delete_grpc_connection, delete_http_response, delete_websocket_connection, get_http_request,
+    get_workspace, list_grpc_connections_for_request, list_grpc_requests, list_http_requests,
+    list_http_responses_for_request, list_websocket_connections_for_request,
+    list_websocket_requests, list_workspaces, UpdateSource,
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use log::{debug, warn};
+use sea_query::{Expr, Query, SqliteQueryBuilder};
+use sea_query_rusqlite::RusqliteBinder;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tauri::{Manager, Runtime, WebviewWindow};
+use ts_rs::TS;
+
+/// Body files newer than this are left alone even if nothing references them yet, since they
+/// might belong to a response that's still being written or an ephemeral one that's being read
+const ORPHAN_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// How much history to keep for a request. `None` means no limit.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HistoryRetention {
+    pub max_count: Option<usize>,
+    pub max_age: Option<Duration>,
+    pub max_bytes: Option<u64>,
+}
+
+impl HistoryRetention {
+    pub fn for_workspace(workspace: &Workspace) -> Self {
+        Self {
+            max_count: limit(workspace.setting_history_max_count),
+            max_age: limit(workspace.setting_history_max_age_days).and_then(Duration::try_days),
+            max_bytes: limit(workspace.setting_history_max_bytes),
+        }
+    }
+
+    /// The request's own settings, falling back to its workspace's
+    pub fn for_http_request(workspace: &Workspace, request: &HttpRequest) -> Self {
+        let workspace = Self::for_workspace(workspace);
+        Self {
+            max_count: match request.history_max_count {
+                None => workspace.max_count,
+                Some(n) => limit(n),
+            },
+            max_age: match request.history_max_age_days {
+                None => workspace.max_age,
+                Some(n) => limit(n).and_then(Duration::try_days),
+            },
+            max_bytes: match request.history_max_bytes {
+                None => workspace.max_bytes,
+                Some(n) => limit(n),
+            },
+        }
+    }
+
+    /// Pick which items of a history to delete. Items must be ordered newest first and are given
+    /// as `(created_at, size_in_bytes)`. `reserve` makes room for items that are about to be
+    /// added. The newest item is never deleted for taking up too much space, since that would
+    /// throw away a response before it could be looked at.
+    pub fn expired(
+        &self,
+        items: &[(NaiveDateTime, u64)],
+        now: NaiveDateTime,
+        reserve: usize,
+    ) -> Vec<usize> {
+        let mut kept_count = 0;
+        let mut kept_bytes = 0;
+        let mut expired = Vec::new();
+        for (i, (created_at, bytes)) in items.iter().enumerate() {
+            let over_count = self.max_count.is_some_and(|max| kept_count + reserve >= max);
+            let too_old = self.max_age.is_some_and(|max| now - *created_at > max);
+            let too_big =
+                kept_count > 0 && self.max_bytes.is_some_and(|max| kept_bytes + bytes > max);
+            if over_count || too_old || too_big {
+                expired.push(i);
+            } else {
+                kept_count += 1;
+                kept_bytes += bytes;
+            }
+        }
+        expired
+    }
+}
+
+/// Zero and negative settings mean no limit
+fn limit<T: TryFrom<i64>>(n: impl Into<i64>) -> Option<T> {
+    match n.into() {
+        n if n <= 0 => None,
+        n => T::try_from(n).ok(),
+    }
+}
+
+/// What a garbage collection run deleted
+#[derive(Debug, Clone, Default, Serialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct GarbageCollectionReport {
+    pub responses_deleted: usize,
+    pub connections_deleted: usize,
+    pub events_deleted: usize,
+    pub files_deleted: usize,
+    // Size of the body files that were deleted
+    #[ts(type = "number")]
+    pub bytes_reclaimed: u64,
+}
+
+/// Delete the responses of a request that its retention settings no longer allow, returning
+/// how many bytes of body files were freed
+pub async fn prune_http_responses<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request_id: &str,
+    reserve: usize,
+    update_source: &UpdateSource,
+) -> Result<(usize, u64)> {
+    let request = match get_http_request(window, request_id).await? {
+        None => return Ok((0, 0)),
+        Some(r) => r,
+    };
+    let workspace = get_workspace(window, &request.workspace_id).await?;
+    let retention = HistoryRetention::for_http_request(&workspace, &request);
+
+    let responses = list_http_responses_for_request(window, request_id, None).await?;
+    let items: Vec<_> =
+        responses.iter().map(|r| (r.created_at, http_response_disk_size(r))).collect();
+
+    let mut deleted = (0, 0);
+    for i in retention.expired(&items, Utc::now().naive_utc(), reserve) {
+        let response = &responses[i];
+        // Don't pull the body out from under a response that's still being written
+        if !matches!(response.state, HttpResponseState::Closed) {
+            continue;
+        }
+        debug!("Deleting old response {}", response.id);
+        delete_http_response(window, &response.id, update_source).await?;
+        deleted.0 += 1;
+        deleted.1 += items[i].1;
+    }
+    Ok(deleted)
+}
+
+/// Delete the gRPC connections of a request, and their events, that its workspace's retention
+/// settings no longer allow
+pub async fn prune_grpc_connections<R: Runtime>(
+    window: &WebviewWindow<R>,
+    workspace_id: &str,
+    request_id: &str,
+    reserve: usize,
+    update_source: &UpdateSource,
+) -> Result<usize> {
+    let workspace = get_workspace(window, workspace_id).await?;
+    let retention = HistoryRetention::for_workspace(&workspace);
+    let connections = list_grpc_connections_for_request(window, request_id).await?;
+    let items: Vec<_> = connections.iter().map(|c| (c.created_at, 0)).collect();
+
+    let mut deleted = 0;
+    for i in retention.expired(&items, Utc::now().naive_utc(), reserve) {
+        let connection = &connections[i];
+        if !matches!(connection.state, GrpcConnectionState::Closed) {
+            continue;
+        }
+        debug!("Deleting old grpc connection {}", connection.id);
+        delete_grpc_connection(window, &connection.id, update_source).await?;
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+/// Delete the websocket connections of a request, and their events, that its workspace's
+/// retention settings no longer allow
+pub async fn prune_websocket_connections<R: Runtime>(
+    window: &WebviewWindow<R>,
+    workspace_id: &str,
+    request_id: &str,
+    reserve: usize,
+    update_source: &UpdateSource,
+) -> Result<usize> {
+    let workspace = get_workspace(window, workspace_id).await?;
+    let retention = HistoryRetention::for_workspace(&workspace);
+    let connections = list_websocket_connections_for_request(window, request_id).await?;
+    let items: Vec<_> = connections.iter().map(|c| (c.created_at, 0)).collect();
+
+    let mut deleted = 0;
+    for i in retention.expired(&items, Utc::now().naive_utc(), reserve) {
+        let connection = &connections[i];
+        if !matches!(connection.state, WebsocketConnectionState::Closed) {
+            continue;
+        }
+        debug!("Deleting old websocket connection {}", connection.id);
+        delete_websocket_connection(window, &connection.id, update_source).await?;
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+/// Apply every request's retention settings, then delete any body files in `responses_dir` that
+/// no response points to. Those are left behind when responses are deleted along with their
+/// request or workspace, since the database cascades the rows but can't know about the files.
+pub async fn collect_garbage<R: Runtime>(
+    window: &WebviewWindow<R>,
+    responses_dir: &Path,
+) -> Result<GarbageCollectionReport> {
+    let update_source = &UpdateSource::Background;
+    let mut report = GarbageCollectionReport::default();
+
+    for workspace in list_workspaces(window).await? {
+        let max_age = HistoryRetention::for_workspace(&workspace).max_age;
+        if let Some(cutoff) = max_age.and_then(|a| Utc::now().naive_utc().checked_sub_signed(a)) {
+            report.events_deleted += delete_old_events(window, &workspace.id, cutoff).await?;
+        }
+        for request in list_http_requests(window, &workspace.id).await? {
+            let (count, bytes) =
+                prune_http_responses(window, &request.id, 0, update_source).await?;
+            report.responses_deleted += count;
+            report.bytes_reclaimed += bytes;
+        }
+        for request in list_grpc_requests(window, &workspace.id).await? {
+            report.connections_deleted +=
+                prune_grpc_connections(window, &workspace.id, &request.id, 0, update_source)
+                    .await?;
+        }
+        for request in list_websocket_requests(window, &workspace.id).await? {
+            report.connections_deleted +=
+                prune_websocket_connections(window, &workspace.id, &request.id, 0, update_source)
+                    .await?;
+        }
+    }
+
+    let (files, bytes) = delete_orphaned_body_files(window, responses_dir).await?;
+    report.files_deleted += files;
+    report.bytes_reclaimed += bytes;
+
+    Ok(report)
+}
+
+/// Size of a response's body files on disk
+fn http_response_disk_size(response: &HttpResponse) -> u64 {
+    [&response.body_path, &response.request_body_path]
+        .into_iter()
+        .flatten()
+        .filter_map(|p| fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Delete gRPC and websocket events older than a workspace's max age. Events of connections
+/// that get pruned go with them, but a long-lived connection can collect old events of its own.
+async fn delete_old_events<R: Runtime>(
+    mgr: &impl Manager<R>,
+    workspace_id: &str,
+    cutoff: NaiveDateTime,
+) -> Result<usize> {
+    let dbm = &*mgr.state::<SqliteConnection>();
+    let db = dbm.0.lock().await.get().unwrap();
+
+    let mut deleted = 0;
+    for statement in [
+        Query::delete()
+            .from_table(GrpcEventIden::Table)
+            .cond_where(Expr::col(GrpcEventIden::WorkspaceId).eq(workspace_id))
+            .and_where(Expr::col(GrpcEventIden::CreatedAt).lt(cutoff))
+            .to_owned(),
+        Query::delete()
+            .from_table(WebsocketEventIden::Table)
+            .cond_where(Expr::col(WebsocketEventIden::WorkspaceId).eq(workspace_id))
+            .and_where(Expr::col(WebsocketEventIden::CreatedAt).lt(cutoff))
+            .to_owned(),
+    ] {
+        let (sql, params) = statement.build_rusqlite(SqliteQueryBuilder);
+        deleted += db.execute(sql.as_str(), &*params.as_params())?;
+    }
+    Ok(deleted)
+}
+
+/// Delete files in the responses directory that no response points to
+async fn delete_orphaned_body_files<R: Runtime>(
+    mgr: &impl Manager<R>,
+    responses_dir: &Path,
+) -> Result<(usize, u64)> {
+    let referenced: HashSet<PathBuf> = {
+        let dbm = &*mgr.state::<SqliteConnection>();
+        let db = dbm.0.lock().await.get().unwrap();
+        let (sql, params) = Query::select()
+            .from(HttpResponseIden::Table)
+            .columns([
+                HttpResponseIden::BodyPath,
+                HttpResponseIden::RequestBodyPath,
+            ])
+            .build_rusqlite(SqliteQueryBuilder);
+        let mut stmt = db.prepare(sql.as_str())?;
+        let rows = stmt.query_map(&*params.as_params(), |row| {
+            Ok([
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<String>>(1)?,
+            ])
+        })?;
+        rows.filter_map(|r| r.ok()).flatten().flatten().map(PathBuf::from).collect()
+    };
+
+    let entries = match fs::read_dir(responses_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(e) => {
+            warn!("Failed to read responses directory {responses_dir:?}: {e}");
+            return Ok((0, 0));
+        }
+    };
+
+    let now = SystemTime::now();
+    let mut deleted = (0, 0);
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let metadata = match entry.metadata() {
+            Ok(m) if m.is_file() => m,
+            _ => continue,
+        };
+        let is_recent = metadata
+            .modified()
+            .ok()
+            .and_then(|m| now.duration_since(m).ok())
+            .is_none_or(|age| age < ORPHAN_GRACE_PERIOD);
+        if is_recent || referenced.contains(&path) {
+            continue;
+        }
+
+        debug!("Deleting orphaned body file {path:?}");
+        match fs::remove_file(&path) {
+            Ok(_) => {
+                deleted.0 += 1;
+                deleted.1 += metadata.len();
+            }
+            Err(e) => warn!("Failed to delete orphaned body file {path:?}: {e}"),
+        }
+    }
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod retention_tests {
+    use crate::models::{HttpRequest, Workspace};
+    use crate::retention::HistoryRetention;
+    use chrono::{Duration, NaiveDateTime, Utc};
+
+    fn history(now: NaiveDateTime, items: &[(i64, u64)]) -> Vec<(NaiveDateTime, u64)> {
+        items.iter().map(|(days_ago, bytes)| (now - Duration::days(*days_ago), *bytes)).collect()
+    }
+
+    #[test]
+    fn expires_by_count() {
+        let now = Utc::now().naive_utc();
+        let retention = HistoryRetention {
+            max_count: Some(2),
+            ..Default::default()
+        };
+        let items = history(now, &[(0, 1), (1, 1), (2, 1), (3, 1)]);
+        assert_eq!(retention.expired(&items, now, 0), vec![2, 3]);
+        assert_eq!(retention.expired(&items, now, 1), vec![1, 2, 3]);
+        assert_eq!(HistoryRetention::default().expired(&items, now, 1), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn expires_by_age_and_size() {
+        let now = Utc::now().naive_utc();
+        let retention = HistoryRetention {
+            max_age: Some(Duration::days(7)),
+            ..Default::default()
+        };
+        let items = history(now, &[(0, 1), (6, 1), (8, 1)]);
+        assert_eq!(retention.expired(&items, now, 0), vec![2]);
+
+        let retention = HistoryRetention {
+            max_bytes: Some(100),
+            ..Default::default()
+        };
+        // The newest is kept even when it's too big on its own
+        let items = history(now, &[(0, 500), (1, 60), (2, 60), (3, 30)]);
+        assert_eq!(retention.expired(&items, now, 0), vec![1, 2, 3]);
+
+        // Smaller, older ones can still fit after a bigger one doesn't
+        let items = history(now, &[(0, 50), (1, 60), (2, 30)]);
+        assert_eq!(retention.expired(&items, now, 0), vec![1]);
+    }
+
+    #[test]
+    fn request_overrides_workspace() {
+        let workspace = Workspace {
+            setting_history_max_count: 20,
+            setting_history_max_age_days: 30,
+            ..Workspace::new("Test".to_string())
+        };
+        let request = HttpRequest {
+            history_max_count: Some(5),
+            history_max_age_days: Some(0),
+            history_max_bytes: None,
+            ..Default::default()
+        };
+
+        let retention = HistoryRetention::for_http_request(&workspace, &request);
+        assert_eq!(retention.max_count, Some(5));
+        assert_eq!(retention.max_age, None);
+        assert_eq!(retention.max_bytes, None);
+
+        let retention = HistoryRetention::for_http_request(&workspace, &HttpRequest::default());
+        assert_eq!(retention.max_count, Some(20));
+        assert_eq!(retention.max_age, Some(Duration::days(30)));
+    }
+}
@@ -2,8 +2,13 @@
 import type { Environment } from "./gen_models.js";
 import type { Folder } from "./gen_models.js";
 import type { GrpcRequest } from "./gen_models.js";
+import type { HttpAssertionResult } from "./gen_models.js";
 import type { HttpRequest } from "./gen_models.js";
 import type { HttpResponse } from "./gen_models.js";
+import type { HttpResponseBodyChunk } from "./gen_models.js";
+import type { HttpResponseBodyHexdumpRow } from "./gen_models.js";
+import type { HttpResponseBodyLines } from "./gen_models.js";
+import type { HttpResponseBodySearch } from "./gen_models.js";
 import type { JsonValue } from "./serde_json/JsonValue.js";
 import type { WebsocketRequest } from "./gen_models.js";
 import type { Workspace } from "./gen_models.js";
@@ -29,6 +34,42 @@ export type CallHttpRequestActionArgs = { httpRequest: HttpRequest, };
 
 export type CallHttpRequestActionRequest = { index: number, pluginRefId: string, args: CallHttpRequestActionArgs, };
 
+export type CallPostResponseScriptRequest = { script: string, 
+/**
+ * The request as it was sent
+ */
+httpRequest: HttpRequest, httpResponse: HttpResponse, variables: { [key in string]?: string }, };
+
+export type CallPostResponseScriptResponse = { 
+/**
+ * Variables the script set
+ */
+variables: { [key in string]?: string }, 
+/**
+ * Tests the script ran
+ */
+assertionResults: Array<HttpAssertionResult>, };
+
+export type CallPreRequestScriptRequest = { script: string, 
+/**
+ * The request before it's rendered, so the variables the script sets can be used
+ */
+httpRequest: HttpRequest, 
+/**
+ * Variables the request will be rendered with
+ */
+variables: { [key in string]?: string }, };
+
+export type CallPreRequestScriptResponse = { 
+/**
+ * The request, with any changes the script made
+ */
+httpRequest: HttpRequest, 
+/**
+ * Variables the script set
+ */
+variables: { [key in string]?: string }, };
+
 export type CallTemplateFunctionArgs = { purpose: RenderPurpose, values: { [key in string]?: string }, };
 
 export type CallTemplateFunctionRequest = { name: string, args: CallTemplateFunctionArgs, };
@@ -65,7 +106,7 @@ export type FileFilter = { name: string,
  */
 extensions: Array<string>, };
 
-export type FilterRequest = { content: string, filter: string, };
+export type FilterRequest = { content: string, filter: string, mimeType: string, };
 
 export type FilterResponse = { content: string, };
 
@@ -310,6 +351,12 @@ defaultValue?: string, disabled?: boolean, };
 
 export type GenericCompletionOption = { label: string, detail?: string, info?: string, type?: CompletionOptionType, boost?: number, };
 
+export type GetFilterSummaryResponse = { name: string, 
+/**
+ * MIME types the filter handles, which can use `*` wildcards like `application/*+json`
+ */
+mimeTypes: Array<string>, };
+
 export type GetHttpAuthenticationConfigRequest = { contextId: string, values: { [key in string]?: JsonPrimitive }, };
 
 export type GetHttpAuthenticationConfigResponse = { args: Array<FormInput>, pluginRefId: string, actions?: Array<HttpAuthenticationAction>, };
@@ -324,6 +371,26 @@ export type GetHttpRequestByIdRequest = { id: string, };
 
 export type GetHttpRequestByIdResponse = { httpRequest: HttpRequest | null, };
 
+export type GetHttpResponseBodyChunkRequest = { responseId: string, offset: number, length: number, };
+
+export type GetHttpResponseBodyChunkResponse = { chunk: HttpResponseBodyChunk | null, };
+
+export type GetHttpResponseBodyHexdumpRequest = { responseId: string, offset: number, length: number, };
+
+export type GetHttpResponseBodyHexdumpResponse = { rows: Array<HttpResponseBodyHexdumpRow> | null, };
+
+export type GetHttpResponseBodyLinesRequest = { responseId: string, startLine: number, lineCount: number, };
+
+export type GetHttpResponseBodyLinesResponse = { lines: HttpResponseBodyLines | null, };
+
+export type GetHttpResponseBodyRequest = { responseId: string, };
+
+export type GetHttpResponseBodyResponse = { 
+/**
+ * Body decoded with the response's charset, or null if it has none
+ */
+body: string | null, };
+
 export type GetKeyValueRequest = { key: string, };
 
 export type GetKeyValueResponse = { value?: string, };
@@ -346,7 +413,7 @@ export type ImportResponse = { resources: ImportResources, };
 
 export type InternalEvent = { id: string, pluginRefId: string, pluginName: string, replyId: string | null, windowContext: WindowContext, payload: InternalEventPayload, };
 
-export type InternalEventPayload = { "type": "boot_request" } & BootRequest | { "type": "boot_response" } & BootResponse | { "type": "reload_request" } & EmptyPayload | { "type": "reload_response" } & EmptyPayload | { "type": "terminate_request" } | { "type": "terminate_response" } | { "type": "import_request" } & ImportRequest | { "type": "import_response" } & ImportResponse | { "type": "filter_request" } & FilterRequest | { "type": "filter_response" } & FilterResponse | { "type": "export_http_request_request" } & ExportHttpRequestRequest | { "type": "export_http_request_response" } & ExportHttpRequestResponse | { "type": "send_http_request_request" } & SendHttpRequestRequest | { "type": "send_http_request_response" } & SendHttpRequestResponse | { "type": "get_http_request_actions_request" } & EmptyPayload | { "type": "get_http_request_actions_response" } & GetHttpRequestActionsResponse | { "type": "call_http_request_action_request" } & CallHttpRequestActionRequest | { "type": "get_template_functions_request" } | { "type": "get_template_functions_response" } & GetTemplateFunctionsResponse | { "type": "call_template_function_request" } & CallTemplateFunctionRequest | { "type": "call_template_function_response" } & CallTemplateFunctionResponse | { "type": "get_http_authentication_summary_request" } & EmptyPayload | { "type": "get_http_authentication_summary_response" } & GetHttpAuthenticationSummaryResponse | { "type": "get_http_authentication_config_request" } & GetHttpAuthenticationConfigRequest | { "type": "get_http_authentication_config_response" } & GetHttpAuthenticationConfigResponse | { "type": "call_http_authentication_request" } & CallHttpAuthenticationRequest | { "type": "call_http_authentication_response" } & CallHttpAuthenticationResponse | { "type": "call_http_authentication_action_request" } & CallHttpAuthenticationActionRequest | { "type": "call_http_authentication_action_response" } & EmptyPayload | { "type": "copy_text_request" } & CopyTextRequest | { "type": "copy_text_response" } & EmptyPayload | { "type": "render_http_request_request" } & RenderHttpRequestRequest | { "type": "render_http_request_response" } & RenderHttpRequestResponse | { "type": "get_key_value_request" } & GetKeyValueRequest | { "type": "get_key_value_response" } & GetKeyValueResponse | { "type": "set_key_value_request" } & SetKeyValueRequest | { "type": "set_key_value_response" } & SetKeyValueResponse | { "type": "delete_key_value_request" } & DeleteKeyValueRequest | { "type": "delete_key_value_response" } & DeleteKeyValueResponse | { "type": "open_window_request" } & OpenWindowRequest | { "type": "window_navigate_event" } & WindowNavigateEvent | { "type": "window_close_event" } | { "type": "close_window_request" } & CloseWindowRequest | { "type": "template_render_request" } & TemplateRenderRequest | { "type": "template_render_response" } & TemplateRenderResponse | { "type": "show_toast_request" } & ShowToastRequest | { "type": "show_toast_response" } & EmptyPayload | { "type": "prompt_text_request" } & PromptTextRequest | { "type": "prompt_text_response" } & PromptTextResponse | { "type": "get_http_request_by_id_request" } & GetHttpRequestByIdRequest | { "type": "get_http_request_by_id_response" } & GetHttpRequestByIdResponse | { "type": "find_http_responses_request" } & FindHttpResponsesRequest | { "type": "find_http_responses_response" } & FindHttpResponsesResponse | { "type": "empty_response" } & EmptyPayload | { "type": "error_response" } & ErrorResponse;
+export type InternalEventPayload = { "type": "boot_request" } & BootRequest | { "type": "boot_response" } & BootResponse | { "type": "reload_request" } & EmptyPayload | { "type": "reload_response" } & EmptyPayload | { "type": "terminate_request" } | { "type": "terminate_response" } | { "type": "import_request" } & ImportRequest | { "type": "import_response" } & ImportResponse | { "type": "get_filter_summary_request" } & EmptyPayload | { "type": "get_filter_summary_response" } & GetFilterSummaryResponse | { "type": "filter_request" } & FilterRequest | { "type": "filter_response" } & FilterResponse | { "type": "export_http_request_request" } & ExportHttpRequestRequest | { "type": "export_http_request_response" } & ExportHttpRequestResponse | { "type": "send_http_request_request" } & SendHttpRequestRequest | { "type": "send_http_request_response" } & SendHttpRequestResponse | { "type": "get_http_request_actions_request" } & EmptyPayload | { "type": "get_http_request_actions_response" } & GetHttpRequestActionsResponse | { "type": "call_http_request_action_request" } & CallHttpRequestActionRequest | { "type": "call_pre_request_script_request" } & CallPreRequestScriptRequest | { "type": "call_pre_request_script_response" } & CallPreRequestScriptResponse | { "type": "call_post_response_script_request" } & CallPostResponseScriptRequest | { "type": "call_post_response_script_response" } & CallPostResponseScriptResponse | { "type": "get_template_functions_request" } | { "type": "get_template_functions_response" } & GetTemplateFunctionsResponse | { "type": "call_template_function_request" } & CallTemplateFunctionRequest | { "type": "call_template_function_response" } & CallTemplateFunctionResponse | { "type": "get_http_authentication_summary_request" } & EmptyPayload | { "type": "get_http_authentication_summary_response" } & GetHttpAuthenticationSummaryResponse | { "type": "get_http_authentication_config_request" } & GetHttpAuthenticationConfigRequest | { "type": "get_http_authentication_config_response" } & GetHttpAuthenticationConfigResponse | { "type": "call_http_authentication_request" } & CallHttpAuthenticationRequest | { "type": "call_http_authentication_response" } & CallHttpAuthenticationResponse | { "type": "call_http_authentication_action_request" } & CallHttpAuthenticationActionRequest | { "type": "call_http_authentication_action_response" } & EmptyPayload | { "type": "copy_text_request" } & CopyTextRequest | { "type": "copy_text_response" } & EmptyPayload | { "type": "render_http_request_request" } & RenderHttpRequestRequest | { "type": "render_http_request_response" } & RenderHttpRequestResponse | { "type": "get_key_value_request" } & GetKeyValueRequest | { "type": "get_key_value_response" } & GetKeyValueResponse | { "type": "set_key_value_request" } & SetKeyValueRequest | { "type": "set_key_value_response" } & SetKeyValueResponse | { "type": "delete_key_value_request" } & DeleteKeyValueRequest | { "type": "delete_key_value_response" } & DeleteKeyValueResponse | { "type": "open_window_request" } & OpenWindowRequest | { "type": "window_navigate_event" } & WindowNavigateEvent | { "type": "window_close_event" } | { "type": "close_window_request" } & CloseWindowRequest | { "type": "template_render_request" } & TemplateRenderRequest | { "type": "template_render_response" } & TemplateRenderResponse | { "type": "show_toast_request" } & ShowToastRequest | { "type": "show_toast_response" } & EmptyPayload | { "type": "prompt_text_request" } & PromptTextRequest | { "type": "prompt_text_response" } & PromptTextResponse | { "type": "get_http_request_by_id_request" } & GetHttpRequestByIdRequest | { "type": "get_http_request_by_id_response" } & GetHttpRequestByIdResponse | { "type": "find_http_responses_request" } & FindHttpResponsesRequest | { "type": "find_http_responses_response" } & FindHttpResponsesResponse | { "type": "get_http_response_body_request" } & GetHttpResponseBodyRequest | { "type": "get_http_response_body_response" } & GetHttpResponseBodyResponse | { "type": "get_http_response_body_chunk_request" } & GetHttpResponseBodyChunkRequest | { "type": "get_http_response_body_chunk_response" } & GetHttpResponseBodyChunkResponse | { "type": "get_http_response_body_lines_request" } & GetHttpResponseBodyLinesRequest | { "type": "get_http_response_body_lines_response" } & GetHttpResponseBodyLinesResponse | { "type": "search_http_response_body_request" } & SearchHttpResponseBodyRequest | { "type": "search_http_response_body_response" } & SearchHttpResponseBodyResponse | { "type": "get_http_response_body_hexdump_request" } & GetHttpResponseBodyHexdumpRequest | { "type": "get_http_response_body_hexdump_response" } & GetHttpResponseBodyHexdumpResponse | { "type": "empty_response" } & EmptyPayload | { "type": "error_response" } & ErrorResponse;
 
 export type JsonPrimitive = string | number | boolean | null;
 
@@ -378,6 +445,18 @@ export type RenderHttpRequestResponse = { httpRequest: HttpRequest, };
 
 export type RenderPurpose = "send" | "preview";
 
+export type SearchHttpResponseBodyRequest = { responseId: string, query: string, caseSensitive: boolean, 
+/**
+ * Where to start searching, like the `nextOffset` of a previous search
+ */
+offset: number, 
+/**
+ * The line `offset` is on
+ */
+line: number, };
+
+export type SearchHttpResponseBodyResponse = { search: HttpResponseBodySearch | null, };
+
 export type SendHttpRequestRequest = { httpRequest: Partial<HttpRequest>, };
 
 export type SendHttpRequestResponse = { httpResponse: HttpResponse, };
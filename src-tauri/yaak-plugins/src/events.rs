@@ -4,7 +4,9 @@ use tauri::{Runtime, WebviewWindow};
 use ts_rs::TS;
 
 use yaak_models::models::{
-    Environment, Folder, GrpcRequest, HttpRequest, HttpResponse, WebsocketRequest, Workspace,
+    Environment, Folder, GrpcRequest, HttpAssertionResult, HttpRequest, HttpResponse,
+    HttpResponseBodyChunk, HttpResponseBodyHexdumpRow, HttpResponseBodyLines,
+    HttpResponseBodySearch, WebsocketRequest, Workspace,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -65,6 +67,8 @@ pub enum InternalEventPayload {
     ImportRequest(ImportRequest),
     ImportResponse(ImportResponse),
 
+    GetFilterSummaryRequest(EmptyPayload),
+    GetFilterSummaryResponse(GetFilterSummaryResponse),
     FilterRequest(FilterRequest),
     FilterResponse(FilterResponse),
 
@@ -79,6 +83,12 @@ pub enum InternalEventPayload {
     GetHttpRequestActionsResponse(GetHttpRequestActionsResponse),
     CallHttpRequestActionRequest(CallHttpRequestActionRequest),
 
+    // Scripts
+    CallPreRequestScriptRequest(CallPreRequestScriptRequest),
+    CallPreRequestScriptResponse(CallPreRequestScriptResponse),
+    CallPostResponseScriptRequest(CallPostResponseScriptRequest),
+    CallPostResponseScriptResponse(CallPostResponseScriptResponse),
+
     // Template Functions
     GetTemplateFunctionsRequest,
     GetTemplateFunctionsResponse(GetTemplateFunctionsResponse),
@@ -128,6 +138,21 @@ pub enum InternalEventPayload {
     FindHttpResponsesRequest(FindHttpResponsesRequest),
     FindHttpResponsesResponse(FindHttpResponsesResponse),
 
+    GetHttpResponseBodyRequest(GetHttpResponseBodyRequest),
+    GetHttpResponseBodyResponse(GetHttpResponseBodyResponse),
+
+    GetHttpResponseBodyChunkRequest(GetHttpResponseBodyChunkRequest),
+    GetHttpResponseBodyChunkResponse(GetHttpResponseBodyChunkResponse),
+
+    GetHttpResponseBodyLinesRequest(GetHttpResponseBodyLinesRequest),
+    GetHttpResponseBodyLinesResponse(GetHttpResponseBodyLinesResponse),
+
+    SearchHttpResponseBodyRequest(SearchHttpResponseBodyRequest),
+    SearchHttpResponseBodyResponse(SearchHttpResponseBodyResponse),
+
+    GetHttpResponseBodyHexdumpRequest(GetHttpResponseBodyHexdumpRequest),
+    GetHttpResponseBodyHexdumpResponse(GetHttpResponseBodyHexdumpResponse),
+
     /// Returned when a plugin doesn't get run, just so the server
     /// has something to listen for
     EmptyResponse(EmptyPayload),
@@ -177,12 +202,22 @@ pub struct ImportResponse {
     pub resources: ImportResources,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct GetFilterSummaryResponse {
+    pub name: String,
+    /// MIME types the filter handles, which can use `*` wildcards like `application/*+json`
+    pub mime_types: Vec<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_events.ts")]
 pub struct FilterRequest {
     pub content: String,
     pub filter: String,
+    pub mime_type: String,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
@@ -842,6 +877,48 @@ pub struct CallHttpRequestActionArgs {
     pub http_request: HttpRequest,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct CallPreRequestScriptRequest {
+    pub script: String,
+    /// The request before it's rendered, so the variables the script sets can be used
+    pub http_request: HttpRequest,
+    /// Variables the request will be rendered with
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct CallPreRequestScriptResponse {
+    /// The request, with any changes the script made
+    pub http_request: HttpRequest,
+    /// Variables the script set
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct CallPostResponseScriptRequest {
+    pub script: String,
+    /// The request as it was sent
+    pub http_request: HttpRequest,
+    pub http_response: HttpResponse,
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct CallPostResponseScriptResponse {
+    /// Variables the script set
+    pub variables: HashMap<String, String>,
+    /// Tests the script ran
+    pub assertion_results: Vec<HttpAssertionResult>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_events.ts")]
@@ -872,6 +949,97 @@ pub struct FindHttpResponsesResponse {
     pub http_responses: Vec<HttpResponse>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct GetHttpResponseBodyRequest {
+    pub response_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct GetHttpResponseBodyResponse {
+    /// Body decoded with the response's charset, or null if it has none
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct GetHttpResponseBodyChunkRequest {
+    pub response_id: String,
+    #[ts(type = "number")]
+    pub offset: u64,
+    #[ts(type = "number")]
+    pub length: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct GetHttpResponseBodyChunkResponse {
+    pub chunk: Option<HttpResponseBodyChunk>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct GetHttpResponseBodyLinesRequest {
+    pub response_id: String,
+    #[ts(type = "number")]
+    pub start_line: u64,
+    #[ts(type = "number")]
+    pub line_count: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct GetHttpResponseBodyLinesResponse {
+    pub lines: Option<HttpResponseBodyLines>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct SearchHttpResponseBodyRequest {
+    pub response_id: String,
+    pub query: String,
+    pub case_sensitive: bool,
+    /// Where to start searching, like the `nextOffset` of a previous search
+    #[ts(type = "number")]
+    pub offset: u64,
+    /// The line `offset` is on
+    #[ts(type = "number")]
+    pub line: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct SearchHttpResponseBodyResponse {
+    pub search: Option<HttpResponseBodySearch>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct GetHttpResponseBodyHexdumpRequest {
+    pub response_id: String,
+    #[ts(type = "number")]
+    pub offset: u64,
+    #[ts(type = "number")]
+    pub length: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct GetHttpResponseBodyHexdumpResponse {
+    pub rows: Option<Vec<HttpResponseBodyHexdumpRow>>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_events.ts")]
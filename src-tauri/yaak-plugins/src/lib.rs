@@ -9,6 +9,7 @@ pub mod events;
 pub mod manager;
 pub mod plugin_handle;
 pub mod template_callback;
+mod mime;
 mod nodejs;
 mod server_ws;
 mod util;
@@ -5,14 +5,17 @@ use crate::error::Result;
 use crate::events::{
     BootRequest, CallHttpAuthenticationActionArgs, CallHttpAuthenticationActionRequest,
     CallHttpAuthenticationRequest, CallHttpAuthenticationResponse, CallHttpRequestActionRequest,
-    CallTemplateFunctionArgs, CallTemplateFunctionRequest, CallTemplateFunctionResponse,
-    EmptyPayload, FilterRequest, FilterResponse, GetHttpAuthenticationConfigRequest,
+    CallPostResponseScriptRequest, CallPostResponseScriptResponse, CallPreRequestScriptRequest,
+    CallPreRequestScriptResponse, CallTemplateFunctionArgs, CallTemplateFunctionRequest,
+    CallTemplateFunctionResponse, EmptyPayload, FilterRequest, FilterResponse,
+    GetFilterSummaryResponse, GetHttpAuthenticationConfigRequest,
     GetHttpAuthenticationConfigResponse, GetHttpAuthenticationSummaryResponse,
     GetHttpRequestActionsResponse, GetTemplateFunctionsResponse, ImportRequest, ImportResponse,
     InternalEvent, InternalEventPayload, JsonPrimitive, RenderPurpose, WindowContext,
 };
+use crate::mime::mime_type_matches;
 use crate::nodejs::start_nodejs_plugin_runtime;
-use crate::plugin_handle::PluginHandle;
+use crate::plugin_handle::{PluginHandle, SCRIPT_RUNNER_REF_ID};
 use crate::server_ws::PluginRuntimeServerWebsocket;
 use log::{info, warn};
 use std::collections::HashMap;
@@ -301,6 +304,9 @@ impl PluginManager {
     }
 
     pub async fn get_plugin_by_ref_id(&self, ref_id: &str) -> Option<PluginHandle> {
+        if ref_id == SCRIPT_RUNNER_REF_ID {
+            return self.get_script_runner().await.ok();
+        }
         self.plugins.lock().await.iter().find(|p| p.ref_id == ref_id).cloned()
     }
 
@@ -460,6 +466,55 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Scripts come from workspace data, which may be synced or imported, so they're run in a
+    /// sandbox of their own rather than in a plugin, where they'd get its access
+    async fn get_script_runner(&self) -> Result<PluginHandle> {
+        match &*self.ws_service.app_to_plugin_events_tx.lock().await {
+            None => Err(ClientNotInitializedErr),
+            Some(tx) => Ok(PluginHandle::new_script_runner(tx.clone())),
+        }
+    }
+
+    pub async fn call_pre_request_script(
+        &self,
+        window_context: &WindowContext,
+        req: CallPreRequestScriptRequest,
+    ) -> Result<CallPreRequestScriptResponse> {
+        let plugin = self.get_script_runner().await?;
+        let event = self
+            .send_to_plugin_and_wait(
+                window_context,
+                &plugin,
+                &InternalEventPayload::CallPreRequestScriptRequest(req),
+            )
+            .await?;
+        match event.payload {
+            InternalEventPayload::CallPreRequestScriptResponse(resp) => Ok(resp),
+            InternalEventPayload::ErrorResponse(e) => Err(PluginErr(e.error)),
+            e => Err(PluginErr(format!("Pre-request script returned invalid event {:?}", e))),
+        }
+    }
+
+    pub async fn call_post_response_script(
+        &self,
+        window_context: &WindowContext,
+        req: CallPostResponseScriptRequest,
+    ) -> Result<CallPostResponseScriptResponse> {
+        let plugin = self.get_script_runner().await?;
+        let event = self
+            .send_to_plugin_and_wait(
+                window_context,
+                &plugin,
+                &InternalEventPayload::CallPostResponseScriptRequest(req),
+            )
+            .await?;
+        match event.payload {
+            InternalEventPayload::CallPostResponseScriptResponse(resp) => Ok(resp),
+            InternalEventPayload::ErrorResponse(e) => Err(PluginErr(e.error)),
+            e => Err(PluginErr(format!("Post-response script returned invalid event {:?}", e))),
+        }
+    }
+
     pub async fn get_http_authentication_summaries<R: Runtime>(
         &self,
         window: &WebviewWindow<R>,
@@ -645,31 +700,60 @@ impl PluginManager {
         }
     }
 
+    pub async fn get_filter_summaries<R: Runtime>(
+        &self,
+        window: &WebviewWindow<R>,
+    ) -> Result<Vec<(PluginHandle, GetFilterSummaryResponse)>> {
+        let window_context = WindowContext::from_window(window);
+        let reply_events = self
+            .send_and_wait(
+                &window_context,
+                &InternalEventPayload::GetFilterSummaryRequest(EmptyPayload {}),
+            )
+            .await?;
+
+        let mut results = Vec::new();
+        for event in reply_events {
+            if let InternalEventPayload::GetFilterSummaryResponse(resp) = event.payload {
+                let plugin = self
+                    .get_plugin_by_ref_id(&event.plugin_ref_id)
+                    .await
+                    .ok_or(PluginNotFoundErr(event.plugin_ref_id))?;
+                results.push((plugin, resp.clone()));
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Find a plugin that registered a filter for the content type, if any
+    pub async fn get_filter_plugin<R: Runtime>(
+        &self,
+        window: &WebviewWindow<R>,
+        content_type: &str,
+    ) -> Result<Option<PluginHandle>> {
+        let results = self.get_filter_summaries(window).await?;
+        Ok(results.into_iter().find_map(|(p, r)| {
+            r.mime_types.iter().any(|m| mime_type_matches(m, content_type)).then_some(p)
+        }))
+    }
+
     pub async fn filter_data<R: Runtime>(
         &self,
         window: &WebviewWindow<R>,
+        plugin: &PluginHandle,
         filter: &str,
         content: &str,
         content_type: &str,
     ) -> Result<FilterResponse> {
-        let plugin_name = if content_type.to_lowercase().contains("json") {
-            "@yaakapp/filter-jsonpath"
-        } else {
-            "@yaakapp/filter-xpath"
-        };
-
-        let plugin = self
-            .get_plugin_by_name(plugin_name)
-            .await
-            .ok_or(PluginNotFoundErr(plugin_name.to_string()))?;
-
         let event = self
             .send_to_plugin_and_wait(
                 &WindowContext::from_window(window),
-                &plugin,
+                plugin,
                 &InternalEventPayload::FilterRequest(FilterRequest {
                     filter: filter.to_string(),
                     content: content.to_string(),
+                    mime_type: content_type.to_string(),
                 }),
             )
             .await?;
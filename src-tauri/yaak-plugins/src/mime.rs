@@ -0,0 +1,64 @@
+/// Check a content type against a MIME pattern a plugin registered, like `application/json`,
+/// `application/*+json`, or `text/*`. Parameters like `; charset=utf-8` are ignored.
+pub(crate) fn mime_type_matches(pattern: &str, content_type: &str) -> bool {
+    let content_type = content_type.split(';').next().unwrap_or_default().trim();
+    let (pattern_type, pattern_subtype) = split_mime_type(pattern);
+    let (content_type, content_subtype) = split_mime_type(content_type);
+    glob_matches(&pattern_type, &content_type) && glob_matches(&pattern_subtype, &content_subtype)
+}
+
+fn split_mime_type(mime_type: &str) -> (String, String) {
+    let mime_type = mime_type.trim().to_lowercase();
+    match mime_type.split_once('/') {
+        Some((t, s)) => (t.to_string(), s.to_string()),
+        None => (mime_type, "*".to_string()),
+    }
+}
+
+/// Match a value against a pattern where `*` matches any run of characters
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = value.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard, so the pattern has to match all of it
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod mime_tests {
+    use crate::mime::mime_type_matches;
+
+    #[test]
+    fn matches_exact_types() {
+        assert!(mime_type_matches("application/json", "application/json"));
+        assert!(mime_type_matches("application/json", "Application/JSON; charset=utf-8"));
+        assert!(!mime_type_matches("application/json", "application/jsonx"));
+        assert!(!mime_type_matches("application/json", "text/json"));
+    }
+
+    #[test]
+    fn matches_wildcards() {
+        assert!(mime_type_matches("*/*", "image/png"));
+        assert!(mime_type_matches("text/*", "text/csv"));
+        assert!(!mime_type_matches("text/*", "application/csv"));
+        assert!(mime_type_matches("application/*+json", "application/vnd.api+json"));
+        assert!(!mime_type_matches("application/*+json", "application/json"));
+        assert!(mime_type_matches("*/*xml*", "application/xml-dtd"));
+        assert!(mime_type_matches("application", "application/yaml"));
+    }
+}
@@ -6,6 +6,10 @@ use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::{mpsc, Mutex};
 
+/// Scripts are sent to this instead of a plugin, so the runtime runs them in its sandbox rather
+/// than in a plugin. Must match `SCRIPT_RUNNER_REF_ID` in the runtime.
+pub const SCRIPT_RUNNER_REF_ID: &str = "__scripts__";
+
 #[derive(Clone)]
 pub struct PluginHandle {
     pub ref_id: String,
@@ -26,6 +30,14 @@ impl PluginHandle {
         }
     }
 
+    /// Handle for the runtime's script sandbox, which talks to the app the same way plugins do
+    pub(crate) fn new_script_runner(tx: mpsc::Sender<InternalEvent>) -> Self {
+        PluginHandle {
+            ref_id: SCRIPT_RUNNER_REF_ID.to_string(),
+            ..PluginHandle::new("scripts", tx)
+        }
+    }
+
     pub async fn name(&self) -> String {
         self.boot_resp.lock().await.name.clone()
     }
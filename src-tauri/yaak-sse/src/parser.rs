@@ -0,0 +1,199 @@
+use crate::sse::ServerSentEvent;
+
+/// Incremental `text/event-stream` parser, following the WHATWG spec. Bytes can be fed in
+/// arbitrary chunks as they arrive, and complete events are returned as soon as they end.
+#[derive(Debug, Default)]
+pub struct EventStreamParser {
+    buffer: Vec<u8>,
+    started: bool,
+    // A chunk ended with CR, so a LF at the start of the next one belongs to the same line end
+    skip_lf: bool,
+
+    event_type: String,
+    data: String,
+    id: Option<String>,
+    retry: Option<u64>,
+
+    last_event_id: String,
+    reconnection_time: Option<u64>,
+}
+
+impl EventStreamParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the next chunk of the stream, returning the events it completed
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<ServerSentEvent> {
+        let mut bytes = bytes;
+        if self.skip_lf && !bytes.is_empty() {
+            self.skip_lf = false;
+            if bytes[0] == b'\n' {
+                bytes = &bytes[1..];
+            }
+        }
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        let mut line_start = 0;
+        let mut i = 0;
+        while i < self.buffer.len() {
+            let line_end = i;
+            match self.buffer[i] {
+                b'\n' => i += 1,
+                b'\r' if self.buffer.get(i + 1) == Some(&b'\n') => i += 2,
+                b'\r' if i + 1 == self.buffer.len() => {
+                    self.skip_lf = true;
+                    i += 1;
+                }
+                b'\r' => i += 1,
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            }
+
+            let line = String::from_utf8_lossy(&self.buffer[line_start..line_end]).to_string();
+            if let Some(event) = self.process_line(&line) {
+                events.push(event);
+            }
+            line_start = i;
+        }
+
+        self.buffer.drain(..line_start);
+        events
+    }
+
+    /// ID of the last event received, to send as `Last-Event-ID` when reconnecting
+    pub fn last_event_id(&self) -> &str {
+        &self.last_event_id
+    }
+
+    /// Milliseconds the server asked to wait before reconnecting, if it said
+    pub fn reconnection_time(&self) -> Option<u64> {
+        self.reconnection_time
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<ServerSentEvent> {
+        let line = match self.started {
+            true => line,
+            false => {
+                self.started = true;
+                line.strip_prefix('\u{feff}').unwrap_or(line)
+            }
+        };
+
+        if line.is_empty() {
+            return self.dispatch();
+        }
+
+        if line.starts_with(':') {
+            return None; // Comment
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event_type = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => {
+                self.last_event_id = value.to_string();
+                self.id = Some(value.to_string());
+            }
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                let retry = value.parse().ok();
+                self.reconnection_time = retry.or(self.reconnection_time);
+                self.retry = retry;
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<ServerSentEvent> {
+        let event_type = std::mem::take(&mut self.event_type);
+        let mut data = std::mem::take(&mut self.data);
+        let id = self.id.take();
+        let retry = self.retry.take();
+
+        if data.is_empty() {
+            return None;
+        }
+        data.pop(); // Trailing LF
+
+        Some(ServerSentEvent {
+            event_type: if event_type.is_empty() { "message".to_string() } else { event_type },
+            data,
+            id,
+            retry,
+        })
+    }
+}
+
+#[cfg(test)]
+mod parser_tests {
+    use crate::parser::EventStreamParser;
+
+    #[test]
+    fn parses_events() {
+        let mut parser = EventStreamParser::new();
+        let events = parser.feed(b"data: hello\n\nevent: update\nid: 1\ndata: a\ndata:b\n\n");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, "message");
+        assert_eq!(events[0].data, "hello");
+        assert_eq!(events[0].id, None);
+        assert_eq!(events[1].event_type, "update");
+        assert_eq!(events[1].data, "a\nb");
+        assert_eq!(events[1].id, Some("1".to_string()));
+        assert_eq!(parser.last_event_id(), "1");
+    }
+
+    #[test]
+    fn parses_across_chunks() {
+        let mut parser = EventStreamParser::new();
+        assert!(parser.feed(b"\xEF\xBB\xBFda").is_empty());
+        assert!(parser.feed(b"ta: caf\xC3").is_empty());
+        assert!(parser.feed(b"\xA9\r").is_empty());
+        let events = parser.feed(b"\n\r\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "café");
+    }
+
+    #[test]
+    fn handles_all_line_endings() {
+        let mut parser = EventStreamParser::new();
+        let events = parser.feed(b"data: a\r\rdata: b\r\n\r\ndata: c\n\n");
+        let data: Vec<_> = events.iter().map(|e| e.data.as_str()).collect();
+        assert_eq!(data, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn ignores_comments_and_empty_events() {
+        let mut parser = EventStreamParser::new();
+        let events = parser.feed(b": ping\n\nevent: nothing\n\nfoo: bar\ndata\n\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "message");
+        assert_eq!(events[0].data, "");
+    }
+
+    #[test]
+    fn tracks_reconnection_state() {
+        let mut parser = EventStreamParser::new();
+        let events = parser.feed(b"retry: 500\n\nid: 7\ndata: x\n\ndata: y\n\nretry: soon\n\n");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].id, None);
+        assert_eq!(parser.last_event_id(), "7");
+        assert_eq!(parser.reconnection_time(), Some(500));
+
+        // Incomplete events are dropped when the stream ends, but their fields still count
+        parser.feed(b"id: 8\ndata: partial\n");
+        assert_eq!(parser.last_event_id(), "8");
+    }
+}
@@ -0,0 +1,77 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use rustls::pki_types::CertificateDer;
+use rustls_pki_types::pem::PemObject;
+use std::fs;
+use yaak_models::models::CaCertificate;
+
+/// Load the enabled CA certificates to trust on top of the platform roots. Each file may be a
+/// PEM bundle of several certificates or a single DER certificate.
+pub fn load_ca_certificates(
+    ca_certificates: &[CaCertificate],
+) -> Result<Vec<CertificateDer<'static>>> {
+    let mut certs = Vec::new();
+    for ca_certificate in ca_certificates.iter().filter(|c| c.enabled && !c.path.is_empty()) {
+        certs.extend(load_ca_file(&ca_certificate.path)?);
+    }
+    Ok(certs)
+}
+
+fn load_ca_file(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let contents = fs::read(path)?;
+    let certs: Vec<CertificateDer<'static>> =
+        CertificateDer::pem_slice_iter(&contents).collect::<std::result::Result<_, _>>()?;
+    if !certs.is_empty() {
+        return Ok(certs);
+    }
+
+    // Not PEM, so it should be a single DER certificate, which always starts with a SEQUENCE
+    match contents.first() {
+        Some(0x30) => Ok(vec![CertificateDer::from(contents)]),
+        _ => Err(GenericError(format!("No certificates found in {path}"))),
+    }
+}
+
+#[cfg(test)]
+mod ca_certificate_tests {
+    use crate::ca_certificate::load_ca_certificates;
+    use crate::{get_config, TlsOptions};
+    use yaak_models::models::CaCertificate;
+
+    fn ca(name: &str, enabled: bool) -> CaCertificate {
+        CaCertificate {
+            enabled,
+            path: format!("{}/fixtures/{name}", env!("CARGO_MANIFEST_DIR")),
+        }
+    }
+
+    #[test]
+    fn loads_pem_and_der() {
+        let pem = load_ca_certificates(&[ca("ca.crt", true)]).unwrap();
+        let der = load_ca_certificates(&[ca("ca.der", true)]).unwrap();
+        assert_eq!(pem.len(), 1);
+        assert_eq!(pem, der);
+    }
+
+    #[test]
+    fn skips_disabled() {
+        let certs = load_ca_certificates(&[ca("ca.crt", false), ca("missing.crt", false)]);
+        assert!(certs.unwrap().is_empty());
+        assert!(load_ca_certificates(&[ca("missing.crt", true)]).is_err());
+    }
+
+    #[test]
+    fn rejects_pem_without_certificates() {
+        let err = load_ca_certificates(&[ca("client.key", true)]).unwrap_err();
+        assert!(err.to_string().starts_with("TLS error: No certificates found in"));
+    }
+
+    #[test]
+    fn builds_config() {
+        let options = TlsOptions {
+            ca_certificates: vec![ca("ca.crt", true)],
+            ..Default::default()
+        };
+        assert!(get_config(&options).is_ok());
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Serialize, Serializer};
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("TLS error: {0}")]
+    TlsError(#[from] rustls::Error),
+
+    #[error("PEM error: {0}")]
+    PemError(#[from] rustls_pki_types::pem::Error),
+
+    #[error("Private key error: {0}")]
+    Pkcs8Error(#[from] pkcs8::Error),
+
+    #[error("PKCS#12 error: {0}")]
+    Pkcs12Error(#[from] p12_keystore::error::Error),
+
+    #[error("I/o error: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("TLS error: {0}")]
+    GenericError(String),
+}
+
+impl Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_ref())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
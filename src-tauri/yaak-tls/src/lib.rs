@@ -0,0 +1,152 @@
+use crate::ca_certificate::load_ca_certificates;
+use crate::client_certificate::{find_client_certificate, load_client_identity};
+use crate::error::Result;
+use crate::pinning::PinningVerifier;
+use crate::tls_info::{RecordingSessionStore, RecordingVerifier, TlsRecorder};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::Resumption;
+use rustls::crypto::{ring, verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, SignatureScheme};
+use rustls_platform_verifier::Verifier;
+use std::sync::Arc;
+use url::Url;
+use yaak_models::models::{CaCertificate, CertificatePin, ClientCertificate};
+
+pub mod ca_certificate;
+pub mod client_certificate;
+pub mod error;
+pub mod pinning;
+pub mod tls_info;
+
+/// TLS settings shared by the HTTP, gRPC, and WebSocket transports
+#[derive(Debug, Clone)]
+pub struct TlsOptions {
+    pub validate_certificates: bool,
+    pub client_certificate: Option<ClientCertificate>,
+    pub ca_certificates: Vec<CaCertificate>,
+    // Every configured pin, since the ones to enforce depend on the server of each handshake
+    pub certificate_pins: Vec<CertificatePin>,
+    // Port being connected to, for pins whose host includes one
+    pub port: Option<u16>,
+    // Collects details of the handshakes made with the config
+    pub recorder: TlsRecorder,
+}
+
+impl Default for TlsOptions {
+    fn default() -> Self {
+        Self {
+            validate_certificates: true,
+            client_certificate: None,
+            ca_certificates: Vec::new(),
+            certificate_pins: Vec::new(),
+            port: None,
+            recorder: TlsRecorder::default(),
+        }
+    }
+}
+
+impl TlsOptions {
+    /// Default options, presenting the first of `client_certificates` that matches the host and
+    /// enforcing any `certificate_pins` for the server of each handshake
+    pub fn for_url(
+        client_certificates: &[ClientCertificate],
+        ca_certificates: &[CaCertificate],
+        certificate_pins: &[CertificatePin],
+        url: &str,
+    ) -> Self {
+        let (client_certificate, port) = match Url::parse(url) {
+            Ok(url) => {
+                let host = url.host_str().unwrap_or_default();
+                let port = url.port_or_known_default();
+                (find_client_certificate(client_certificates, host, port).cloned(), port)
+            }
+            Err(_) => (None, None),
+        };
+
+        Self {
+            client_certificate,
+            ca_certificates: ca_certificates.to_vec(),
+            certificate_pins: certificate_pins.to_vec(),
+            port,
+            ..Default::default()
+        }
+    }
+}
+
+pub fn get_config(options: &TlsOptions) -> Result<ClientConfig> {
+    let provider = Arc::new(ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+
+    let verifier: Arc<dyn ServerCertVerifier> = if options.validate_certificates {
+        // Use platform-native verifier to validate certificates, trusting custom CAs as well
+        let ca_certificates = load_ca_certificates(&options.ca_certificates)?;
+        Arc::new(Verifier::new_with_extra_roots(ca_certificates)?.with_provider(provider))
+    } else {
+        Arc::new(NoVerifier(provider))
+    };
+
+    let verifier: Arc<dyn ServerCertVerifier> = match options.certificate_pins.is_empty() {
+        true => verifier,
+        false => Arc::new(PinningVerifier::new(verifier, &options.certificate_pins, options.port)),
+    };
+
+    let verifier = Arc::new(RecordingVerifier {
+        inner: verifier,
+        recorder: options.recorder.clone(),
+    });
+    let builder = builder.dangerous().with_custom_certificate_verifier(verifier);
+
+    let mut config = match &options.client_certificate {
+        Some(client_certificate) => {
+            let (certs, key) = load_client_identity(client_certificate)?;
+            builder.with_client_auth_cert(certs, key)?
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let session_store = RecordingSessionStore::new(options.recorder.clone());
+    config.resumption = Resumption::store(Arc::new(session_store));
+
+    Ok(config)
+}
+
+/// Accepts any server certificate, but still checks that the handshake was signed by it
+#[derive(Debug)]
+pub(crate) struct NoVerifier(pub(crate) Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoVerifier {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
@@ -0,0 +1,223 @@
+use crate::client_certificate::host_matches;
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use ring::digest::{digest, SHA256};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{CertificateError, DigitallySignedStruct, SignatureScheme};
+use std::sync::Arc;
+use webpki::EndEntityCert;
+use yaak_models::models::CertificatePin;
+
+const PIN_PREFIX: &str = "sha256/";
+
+/// Find the enabled pins configured for a host. A host with several pins accepts any of them,
+/// which allows a backup key to be pinned before rotating to it.
+pub fn find_certificate_pins<'a>(
+    pins: &'a [CertificatePin],
+    host: &str,
+    port: Option<u16>,
+) -> Vec<&'a CertificatePin> {
+    pins.iter().filter(|p| p.enabled && host_matches(&p.host, host, port)).collect()
+}
+
+/// Decode a pin in the `sha256/<base64>` format used by curl's `--pinnedpubkey`. The prefix is
+/// optional.
+pub fn parse_pin(pin: &str) -> Result<Vec<u8>> {
+    let pin = pin.trim();
+    let encoded = pin.strip_prefix(PIN_PREFIX).unwrap_or(pin);
+    match BASE64_STANDARD.decode(encoded) {
+        Ok(hash) if hash.len() == SHA256.output_len() => Ok(hash),
+        _ => Err(GenericError(format!("Invalid certificate pin \"{pin}\""))),
+    }
+}
+
+/// SHA-256 hash of a certificate's DER-encoded SubjectPublicKeyInfo
+pub fn spki_sha256(cert: &CertificateDer<'_>) -> std::result::Result<Vec<u8>, rustls::Error> {
+    let cert = EndEntityCert::try_from(cert)
+        .map_err(|_| rustls::Error::InvalidCertificate(CertificateError::BadEncoding))?;
+    Ok(digest(&SHA256, cert.subject_public_key_info().as_ref()).as_ref().to_vec())
+}
+
+/// Wraps another verifier, additionally requiring the server's public key to match a pin when
+/// any are configured for the server being verified. Pins are looked up for each handshake, so
+/// a connection that follows a redirect to another host is held to that host's pins.
+#[derive(Debug)]
+pub(crate) struct PinningVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+    pins: Vec<CertificatePin>,
+    port: Option<u16>,
+}
+
+impl PinningVerifier {
+    pub(crate) fn new(
+        inner: Arc<dyn ServerCertVerifier>,
+        pins: &[CertificatePin],
+        port: Option<u16>,
+    ) -> Self {
+        Self {
+            inner,
+            pins: pins.to_vec(),
+            port,
+        }
+    }
+}
+
+impl ServerCertVerifier for PinningVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+
+        let host = server_name.to_str();
+        let pins = find_certificate_pins(&self.pins, &host, self.port);
+        if pins.is_empty() {
+            return Ok(verified);
+        }
+
+        let pins = pins
+            .iter()
+            .map(|p| parse_pin(&p.pin))
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| rustls::Error::General(e.to_string()))?;
+        let hash = spki_sha256(end_entity)?;
+        if !pins.contains(&hash) {
+            return Err(rustls::Error::General(format!(
+                "Certificate public key {PIN_PREFIX}{} does not match any pin for {}",
+                BASE64_STANDARD.encode(hash),
+                host,
+            )));
+        }
+
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+
+    fn requires_raw_public_keys(&self) -> bool {
+        self.inner.requires_raw_public_keys()
+    }
+}
+
+#[cfg(test)]
+mod pinning_tests {
+    use crate::pinning::{find_certificate_pins, parse_pin, spki_sha256, PinningVerifier};
+    use crate::NoVerifier;
+    use rustls::client::danger::ServerCertVerifier;
+    use rustls::crypto::ring;
+    use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+    use rustls_pki_types::pem::PemObject;
+    use std::sync::Arc;
+    use yaak_models::models::CertificatePin;
+
+    // openssl x509 -in client.crt -pubkey -noout | openssl pkey -pubin -outform der \
+    //   | openssl dgst -sha256 -binary | base64
+    const CLIENT_PIN: &str = "sha256/6zFuezczAqes27vF4pj2CtkzWTSSvdhgLV8xTfMkxrs=";
+    const OTHER_PIN: &str = "sha256/AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+
+    fn pin(host: &str, pin: &str) -> CertificatePin {
+        CertificatePin {
+            enabled: true,
+            host: host.to_string(),
+            pin: pin.to_string(),
+        }
+    }
+
+    fn client_cert() -> CertificateDer<'static> {
+        let path = format!("{}/fixtures/client.crt", env!("CARGO_MANIFEST_DIR"));
+        CertificateDer::from_pem_file(path).unwrap()
+    }
+
+    fn verify(pins: &[CertificatePin], host: &str) -> Result<(), rustls::Error> {
+        let inner = Arc::new(NoVerifier(Arc::new(ring::default_provider())));
+        let verifier = PinningVerifier::new(inner, pins, Some(443));
+        let server_name = ServerName::try_from(host.to_string()).unwrap();
+        verifier.verify_server_cert(&client_cert(), &[], &server_name, &[], UnixTime::now())?;
+        Ok(())
+    }
+
+    #[test]
+    fn parses_pins() {
+        let hash = parse_pin(CLIENT_PIN).unwrap();
+        assert_eq!(hash.len(), 32);
+        assert_eq!(parse_pin(CLIENT_PIN.strip_prefix("sha256/").unwrap()).unwrap(), hash);
+        assert!(parse_pin("sha256/not base64").is_err());
+        assert!(parse_pin("sha256/AAAA").is_err());
+    }
+
+    #[test]
+    fn hashes_spki() {
+        let hash = spki_sha256(&client_cert()).unwrap();
+        assert_eq!(hash, parse_pin(CLIENT_PIN).unwrap());
+    }
+
+    #[test]
+    fn finds_pins_for_host() {
+        let mut disabled = pin("*", OTHER_PIN);
+        disabled.enabled = false;
+        let pins = vec![
+            pin("api.example.com", CLIENT_PIN),
+            pin("*.example.com", OTHER_PIN),
+            disabled,
+        ];
+        assert_eq!(find_certificate_pins(&pins, "api.example.com", Some(443)).len(), 2);
+        assert_eq!(find_certificate_pins(&pins, "www.example.com", Some(443)).len(), 1);
+        assert!(find_certificate_pins(&pins, "example.org", Some(443)).is_empty());
+    }
+
+    #[test]
+    fn verifies_pins() {
+        assert!(verify(&[pin("", CLIENT_PIN)], "example.com").is_ok());
+        assert!(verify(&[pin("", OTHER_PIN), pin("", CLIENT_PIN)], "example.com").is_ok());
+
+        let err = verify(&[pin("", OTHER_PIN)], "example.com").unwrap_err().to_string();
+        assert!(err.ends_with(&format!("{CLIENT_PIN} does not match any pin for example.com")));
+    }
+
+    #[test]
+    fn verifies_pins_for_server_name() {
+        let pins = [
+            pin("example.com", OTHER_PIN),
+            pin("example.org", CLIENT_PIN),
+            pin("example.net:8443", OTHER_PIN),
+        ];
+        assert!(verify(&pins, "example.com").is_err());
+        assert!(verify(&pins, "example.org").is_ok());
+        // Hosts without pins, or only pins for another port, are left to the inner verifier
+        assert!(verify(&pins, "example.net").is_ok());
+        assert!(verify(&pins, "example.edu").is_ok());
+    }
+}
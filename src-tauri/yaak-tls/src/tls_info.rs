@@ -0,0 +1,346 @@
+use chrono::DateTime;
+use ring::digest::{digest, SHA256};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{
+    ClientSessionMemoryCache, ClientSessionStore, Tls12ClientSessionValue, Tls13ClientSessionValue,
+};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{
+    ClientConnection, DigitallySignedStruct, NamedGroup, ProtocolVersion, SignatureScheme,
+    SupportedCipherSuite,
+};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+use yaak_models::models::{TlsCertificate, TlsInfo};
+
+/// Collects details of the TLS handshakes made with a config, keyed by server name. Transports
+/// like reqwest and hyper don't expose their connections, so this hooks into the verifier and
+/// session store instead.
+#[derive(Debug, Clone, Default)]
+pub struct TlsRecorder(Arc<Mutex<RecordedHandshakes>>);
+
+#[derive(Debug, Default)]
+struct RecordedHandshakes {
+    by_server_name: HashMap<String, TlsInfo>,
+    latest: Option<String>,
+    handshake_started: Option<Instant>,
+}
+
+impl TlsRecorder {
+    /// Details of the most recent handshake with `host`
+    pub fn tls_info(&self, host: &str) -> Option<TlsInfo> {
+        // URLs wrap IPv6 addresses in brackets, but server names don't
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        self.0.lock().unwrap().by_server_name.get(host).cloned()
+    }
+
+    /// Details of the most recent handshake with any host, which is useful when a connection
+    /// failed and the host it was for isn't known
+    pub fn latest(&self) -> Option<TlsInfo> {
+        let handshakes = self.0.lock().unwrap();
+        handshakes.latest.as_ref().and_then(|s| handshakes.by_server_name.get(s)).cloned()
+    }
+
+    /// When the most recent handshake began, which is right after the TCP connection was made
+    pub fn handshake_started(&self) -> Option<Instant> {
+        self.0.lock().unwrap().handshake_started
+    }
+
+    fn record_handshake_started(&self) {
+        self.0.lock().unwrap().handshake_started = Some(Instant::now());
+    }
+
+    fn record_certificates(&self, server_name: &ServerName<'_>, certificates: Vec<TlsCertificate>) {
+        let server_name = server_name.to_str().to_string();
+        let mut handshakes = self.0.lock().unwrap();
+        handshakes.by_server_name.insert(
+            server_name.clone(),
+            TlsInfo {
+                certificates,
+                ..Default::default()
+            },
+        );
+        handshakes.latest = Some(server_name);
+    }
+
+    /// The server's signature is verified right after its certificate, so the version belongs to
+    /// the latest handshake
+    fn record_version(&self, version: ProtocolVersion) {
+        let mut handshakes = self.0.lock().unwrap();
+        let latest = handshakes.latest.clone().unwrap_or_default();
+        let info = handshakes.by_server_name.entry(latest).or_default();
+        info.version = Some(version_name(version));
+    }
+
+    fn record_cipher_suite(&self, server_name: &ServerName<'_>, suite: SupportedCipherSuite) {
+        let mut handshakes = self.0.lock().unwrap();
+        let info = handshakes.by_server_name.entry(server_name.to_str().to_string()).or_default();
+        info.version = Some(version_name(suite.version().version));
+        info.cipher_suite = Some(format!("{:?}", suite.suite()));
+    }
+}
+
+/// Details of an established connection, which are more complete than what can be recorded
+pub fn tls_info_from_connection(connection: &ClientConnection) -> TlsInfo {
+    TlsInfo {
+        version: connection.protocol_version().map(version_name),
+        cipher_suite: connection.negotiated_cipher_suite().map(|s| format!("{:?}", s.suite())),
+        alpn_protocol: connection.alpn_protocol().map(|p| String::from_utf8_lossy(p).to_string()),
+        certificates: connection
+            .peer_certificates()
+            .unwrap_or_default()
+            .iter()
+            .map(certificate_info)
+            .collect(),
+    }
+}
+
+/// Parse the details of a DER-encoded certificate worth showing to the user
+pub fn certificate_info(der: &CertificateDer<'_>) -> TlsCertificate {
+    let fingerprint_sha256 = digest(&SHA256, der.as_ref())
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(":");
+
+    let cert = match X509Certificate::from_der(der.as_ref()) {
+        Ok((_, cert)) => cert,
+        Err(_) => {
+            return TlsCertificate {
+                fingerprint_sha256,
+                ..Default::default()
+            }
+        }
+    };
+
+    let subject_alt_names = match cert.subject_alternative_name() {
+        Ok(Some(san)) => san.value.general_names.iter().filter_map(general_name).collect(),
+        _ => Vec::new(),
+    };
+
+    let validity = cert.validity();
+    TlsCertificate {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        subject_alt_names,
+        serial_number: cert.raw_serial_as_string().to_uppercase(),
+        not_before: DateTime::from_timestamp(validity.not_before.timestamp(), 0)
+            .map(|d| d.naive_utc()),
+        not_after: DateTime::from_timestamp(validity.not_after.timestamp(), 0)
+            .map(|d| d.naive_utc()),
+        fingerprint_sha256,
+    }
+}
+
+/// Format a SAN the same way `openssl x509 -text` does
+fn general_name(name: &GeneralName) -> Option<String> {
+    match name {
+        GeneralName::DNSName(n) => Some(format!("DNS:{n}")),
+        GeneralName::RFC822Name(n) => Some(format!("email:{n}")),
+        GeneralName::URI(n) => Some(format!("URI:{n}")),
+        GeneralName::IPAddress(b) => match b.len() {
+            4 => Some(format!("IP Address:{}", Ipv4Addr::from(<[u8; 4]>::try_from(*b).ok()?))),
+            16 => Some(format!("IP Address:{}", Ipv6Addr::from(<[u8; 16]>::try_from(*b).ok()?))),
+            _ => None,
+        },
+        GeneralName::DirectoryName(n) => Some(format!("DirName:{n}")),
+        _ => None,
+    }
+}
+
+fn version_name(version: ProtocolVersion) -> String {
+    match version {
+        ProtocolVersion::TLSv1_2 => "TLSv1.2".to_string(),
+        ProtocolVersion::TLSv1_3 => "TLSv1.3".to_string(),
+        v => format!("{v:?}"),
+    }
+}
+
+/// Records the chain presented by the server before handing it to the real verifier, so it's
+/// available even if validation fails
+#[derive(Debug)]
+pub(crate) struct RecordingVerifier {
+    pub(crate) inner: Arc<dyn ServerCertVerifier>,
+    pub(crate) recorder: TlsRecorder,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        let certificates =
+            [end_entity].into_iter().chain(intermediates).map(certificate_info).collect();
+        self.recorder.record_certificates(server_name, certificates);
+        self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.recorder.record_version(ProtocolVersion::TLSv1_2);
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.recorder.record_version(ProtocolVersion::TLSv1_3);
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+
+    fn requires_raw_public_keys(&self) -> bool {
+        self.inner.requires_raw_public_keys()
+    }
+}
+
+/// The default in-memory session store, which also records the cipher suite of TLS 1.3 sessions
+/// and when each handshake began. rustls doesn't make the suite of TLS 1.2 sessions public.
+#[derive(Debug)]
+pub(crate) struct RecordingSessionStore {
+    inner: ClientSessionMemoryCache,
+    recorder: TlsRecorder,
+}
+
+impl RecordingSessionStore {
+    pub(crate) fn new(recorder: TlsRecorder) -> Self {
+        Self {
+            inner: ClientSessionMemoryCache::new(256),
+            recorder,
+        }
+    }
+}
+
+impl ClientSessionStore for RecordingSessionStore {
+    fn set_kx_hint(&self, server_name: ServerName<'static>, group: NamedGroup) {
+        self.inner.set_kx_hint(server_name, group)
+    }
+
+    fn kx_hint(&self, server_name: &ServerName<'_>) -> Option<NamedGroup> {
+        self.inner.kx_hint(server_name)
+    }
+
+    fn set_tls12_session(&self, server_name: ServerName<'static>, value: Tls12ClientSessionValue) {
+        self.inner.set_tls12_session(server_name, value)
+    }
+
+    fn tls12_session(&self, server_name: &ServerName<'_>) -> Option<Tls12ClientSessionValue> {
+        self.inner.tls12_session(server_name)
+    }
+
+    fn remove_tls12_session(&self, server_name: &ServerName<'static>) {
+        self.inner.remove_tls12_session(server_name)
+    }
+
+    fn insert_tls13_ticket(
+        &self,
+        server_name: ServerName<'static>,
+        value: Tls13ClientSessionValue,
+    ) {
+        self.recorder.record_cipher_suite(&server_name, SupportedCipherSuite::Tls13(value.suite()));
+        self.inner.insert_tls13_ticket(server_name, value)
+    }
+
+    fn take_tls13_ticket(
+        &self,
+        server_name: &ServerName<'static>,
+    ) -> Option<Tls13ClientSessionValue> {
+        // The first thing rustls does when starting a handshake is look for a session to resume
+        self.recorder.record_handshake_started();
+        self.inner.take_tls13_ticket(server_name)
+    }
+}
+
+#[cfg(test)]
+mod tls_info_tests {
+    use crate::pinning::PinningVerifier;
+    use crate::tls_info::{certificate_info, RecordingVerifier, TlsRecorder};
+    use crate::NoVerifier;
+    use rustls::client::danger::ServerCertVerifier;
+    use rustls::crypto::ring;
+    use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+    use rustls_pki_types::pem::PemObject;
+    use std::sync::Arc;
+    use yaak_models::models::CertificatePin;
+
+    fn fixture(name: &str) -> CertificateDer<'static> {
+        let path = format!("{}/fixtures/{name}", env!("CARGO_MANIFEST_DIR"));
+        CertificateDer::from_pem_file(path).unwrap()
+    }
+
+    #[test]
+    fn parses_certificates() {
+        let info = certificate_info(&fixture("server.crt"));
+        assert_eq!(info.subject, "CN=localhost");
+        assert_eq!(info.issuer, "CN=Yaak Test CA");
+        assert_eq!(
+            info.subject_alt_names,
+            vec![
+                "DNS:localhost",
+                "DNS:*.localhost",
+                "IP Address:127.0.0.1",
+                "IP Address:::1"
+            ]
+        );
+        assert_eq!(info.not_before.unwrap().to_string(), "2025-02-09 00:00:00");
+        assert_eq!(info.not_after.unwrap().to_string(), "2125-01-16 00:00:00");
+        assert_eq!(info.fingerprint_sha256.len(), 32 * 3 - 1);
+        assert_eq!(info.fingerprint_sha256, info.fingerprint_sha256.to_uppercase());
+    }
+
+    #[test]
+    fn parses_invalid_certificates() {
+        let info = certificate_info(&CertificateDer::from(vec![1, 2, 3]));
+        assert_eq!(info.subject, "");
+        assert_eq!(
+            info.fingerprint_sha256,
+            "03:90:58:C6:F2:C0:CB:49:2C:53:3B:0A:4D:14:EF:77:CC:0F:78:AB:CC:CE:D5:28:7D:84:A1:A2:01:1C:FB:81"
+        );
+    }
+
+    #[test]
+    fn records_chain_even_when_invalid() {
+        // Pinned to a different key, so verification will fail
+        let pin = CertificatePin {
+            enabled: true,
+            host: String::new(),
+            pin: "sha256/AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string(),
+        };
+        let inner = Arc::new(NoVerifier(Arc::new(ring::default_provider())));
+        let recorder = TlsRecorder::default();
+        let verifier = RecordingVerifier {
+            inner: Arc::new(PinningVerifier::new(inner, &[pin], Some(443))),
+            recorder: recorder.clone(),
+        };
+
+        let server_name = ServerName::try_from("localhost").unwrap();
+        let chain = [fixture("server.crt"), fixture("ca.crt")];
+        let result =
+            verifier.verify_server_cert(&chain[0], &chain[1..], &server_name, &[], UnixTime::now());
+        assert!(result.is_err());
+
+        let info = recorder.tls_info("localhost").unwrap();
+        assert_eq!(info.certificates.len(), 2);
+        assert_eq!(info.certificates[1].subject, "CN=Yaak Test CA");
+        assert_eq!(recorder.latest(), Some(info));
+        assert_eq!(recorder.tls_info("example.com"), None);
+    }
+}
@@ -5,26 +5,33 @@ use crate::render::render_request;
 use chrono::Utc;
 use log::{info, warn};
 use std::str::FromStr;
+use tauri::http::header::COOKIE;
 use tauri::http::{HeaderMap, HeaderName};
 use tauri::{AppHandle, Manager, Runtime, State, Url, WebviewWindow};
 use tokio::sync::{mpsc, Mutex};
 use tokio_tungstenite::tungstenite::http::HeaderValue;
 use tokio_tungstenite::tungstenite::Message;
 use yaak_http::apply_path_placeholders;
+use yaak_http::cookies::{
+    cookie_header_value, cookie_store_from_jar, cookies_for_url, cookies_from_store,
+    store_response_cookies,
+};
 use yaak_models::models::{
     HttpResponseHeader, WebsocketConnection, WebsocketConnectionState, WebsocketEvent,
     WebsocketEventType, WebsocketRequest,
 };
 use yaak_models::queries;
 use yaak_models::queries::{
-    get_base_environment, get_cookie_jar, get_environment, get_websocket_connection,
-    get_websocket_request, upsert_websocket_connection, upsert_websocket_event, UpdateSource,
+    get_base_environment, get_client_certificates, get_cookie_jar, get_environment, get_inherited,
+    get_websocket_connection, get_websocket_request, get_workspace, upsert_cookie_jar,
+    upsert_websocket_connection, upsert_websocket_event, UpdateSource,
 };
 use yaak_plugins::events::{
     CallHttpAuthenticationRequest, HttpHeader, RenderPurpose, WindowContext,
 };
 use yaak_plugins::manager::PluginManager;
 use yaak_plugins::template_callback::PluginTemplateCallback;
+use yaak_tls::TlsOptions;
 
 #[tauri::command]
 pub(crate) async fn upsert_request<R: Runtime>(
@@ -106,8 +113,15 @@ pub(crate) async fn send<R: Runtime>(
         None => None,
     };
     let base_environment = get_base_environment(&window, &unrendered_request.workspace_id).await?;
+    let inherited = get_inherited(
+        &window,
+        &unrendered_request.workspace_id,
+        unrendered_request.folder_id.as_deref(),
+    )
+    .await?;
     let request = render_request(
         &unrendered_request,
+        &inherited,
         &base_environment,
         environment.as_ref(),
         &PluginTemplateCallback::new(
@@ -204,8 +218,15 @@ pub(crate) async fn connect<R: Runtime>(
         None => None,
     };
     let base_environment = get_base_environment(&window, &unrendered_request.workspace_id).await?;
+    let inherited = get_inherited(
+        &window,
+        &unrendered_request.workspace_id,
+        unrendered_request.folder_id.as_deref(),
+    )
+    .await?;
     let request = render_request(
         &unrendered_request,
+        &inherited,
         &base_environment,
         environment.as_ref(),
         &PluginTemplateCallback::new(
@@ -244,9 +265,11 @@ pub(crate) async fn connect<R: Runtime>(
         }
     }
 
-    // TODO: Handle cookies
-    let _cookie_jar = match cookie_jar_id {
-        Some(id) => Some(get_cookie_jar(&window, id).await?),
+    let maybe_cookie_manager = match cookie_jar_id {
+        Some(id) => {
+            let cookie_jar = get_cookie_jar(&window, id).await?;
+            Some((cookie_store_from_jar(&cookie_jar)?, cookie_jar))
+        }
         None => None,
     };
 
@@ -299,7 +322,6 @@ pub(crate) async fn connect<R: Runtime>(
         });
     }
 
-
     let (url, url_parameters) = apply_path_placeholders(&request.url, request.url_parameters);
 
     // Add URL parameters to URL
@@ -314,7 +336,32 @@ pub(crate) async fn connect<R: Runtime>(
         }
     }
 
-    let response = match ws_manager.connect(&connection.id, url.as_str(), headers, receive_tx).await
+    // Like HTTP requests, a Cookie header that was already set (eg. by auth) is left alone
+    if let Some((cookie_store, _)) =
+        maybe_cookie_manager.as_ref().filter(|_| !headers.contains_key(COOKIE))
+    {
+        let cookies = cookies_for_url(cookie_store, &url, &[]);
+        if let Some(value) = cookie_header_value(&cookies) {
+            match HeaderValue::from_str(&value) {
+                Ok(v) => {
+                    headers.insert(COOKIE, v);
+                }
+                Err(e) => warn!("Failed to create cookie header {e:?}"),
+            }
+        }
+    }
+
+    let workspace = get_workspace(&window, &request.workspace_id).await?;
+    let tls_options = TlsOptions::for_url(
+        &get_client_certificates(&window, &workspace).await?,
+        &workspace.setting_ca_certificates,
+        &workspace.setting_certificate_pins,
+        url.as_str(),
+    );
+
+    let (response, tls) = match ws_manager
+        .connect(&connection.id, url.as_str(), headers, &tls_options, receive_tx)
+        .await
     {
         Ok(r) => r,
         Err(e) => {
@@ -323,6 +370,7 @@ pub(crate) async fn connect<R: Runtime>(
                 &WebsocketConnection {
                     error: Some(format!("{e:?}")),
                     state: WebsocketConnectionState::Closed,
+                    tls: tls_options.recorder.latest(),
                     ..connection
                 },
                 &UpdateSource::Window,
@@ -331,6 +379,14 @@ pub(crate) async fn connect<R: Runtime>(
         }
     };
 
+    if let Some((cookie_store, mut cookie_jar)) = maybe_cookie_manager {
+        store_response_cookies(&cookie_store, response.headers(), &url);
+        cookie_jar.cookies = cookies_from_store(&cookie_store)?;
+        if let Err(e) = upsert_cookie_jar(&window, &cookie_jar, &UpdateSource::Window).await {
+            warn!("Failed to update cookie jar: {}", e);
+        }
+    }
+
     let response_headers = response
         .headers()
         .into_iter()
@@ -346,6 +402,7 @@ pub(crate) async fn connect<R: Runtime>(
             state: WebsocketConnectionState::Connected,
             headers: response_headers,
             status: response.status().as_u16() as i32,
+            tls,
             url: request.url.clone(),
             ..connection
         },
@@ -1,7 +1,5 @@
+use crate::error::Result;
 use log::info;
-use rustls::crypto::ring;
-use rustls::ClientConfig;
-use rustls_platform_verifier::BuilderVerifierExt;
 use std::sync::Arc;
 use tauri::http::HeaderMap;
 use tokio::net::TcpStream;
@@ -12,18 +10,17 @@ use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 use tokio_tungstenite::{
     connect_async_tls_with_config, Connector, MaybeTlsStream, WebSocketStream,
 };
+use yaak_models::models::TlsInfo;
+use yaak_tls::tls_info::tls_info_from_connection;
+use yaak_tls::{get_config, TlsOptions};
 
 pub(crate) async fn ws_connect(
     url: &str,
     headers: HeaderMap<HeaderValue>,
-) -> crate::error::Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> {
+    tls_options: &TlsOptions,
+) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response, Option<TlsInfo>)> {
     info!("Connecting to WS {url}");
-    let arc_crypto_provider = Arc::new(ring::default_provider());
-    let config = ClientConfig::builder_with_provider(arc_crypto_provider)
-        .with_safe_default_protocol_versions()
-        .unwrap()
-        .with_platform_verifier()
-        .with_no_client_auth();
+    let config = get_config(tls_options)?;
 
     let mut req = url.into_client_request()?;
     let req_headers = req.headers_mut();
@@ -40,7 +37,13 @@ pub(crate) async fn ws_connect(
         Some(Connector::Rustls(Arc::new(config))),
     )
     .await?;
-    Ok((stream, response))
+
+    let tls = match stream.get_ref() {
+        MaybeTlsStream::Rustls(s) => Some(tls_info_from_connection(s.get_ref().1)),
+        _ => None,
+    };
+
+    Ok((stream, response, tls))
 }
 
 #[cfg(test)]
@@ -51,11 +54,15 @@ mod tests {
     use std::time::Duration;
     use tokio::time::timeout;
     use tokio_tungstenite::tungstenite::Message;
+    use yaak_tls::TlsOptions;
 
     #[tokio::test]
     async fn test_connection() -> Result<()> {
-        let (stream, response) = ws_connect("wss://echo.websocket.org/", Default::default()).await?;
+        let (stream, response, tls) =
+            ws_connect("wss://echo.websocket.org/", Default::default(), &TlsOptions::default())
+                .await?;
         assert_eq!(response.status(), 101);
+        assert!(!tls.unwrap().certificates.is_empty());
 
         let (mut write, mut read) = stream.split();
 
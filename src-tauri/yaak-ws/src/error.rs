@@ -13,6 +13,12 @@ pub enum Error {
     #[error("Plugin error: {0}")]
     PluginError(#[from] yaak_plugins::error::Error),
 
+    #[error("HTTP error: {0}")]
+    HttpError(#[from] yaak_http::error::Error),
+
+    #[error("TLS error: {0}")]
+    TlsError(#[from] yaak_tls::error::Error),
+
     #[error("WebSocket error: {0}")]
     GenericError(String),
 }
@@ -11,6 +11,8 @@ use tokio_tungstenite::tungstenite::handshake::client::Response;
 use tokio_tungstenite::tungstenite::http::{HeaderMap, HeaderValue};
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use yaak_models::models::TlsInfo;
+use yaak_tls::TlsOptions;
 
 #[derive(Clone)]
 pub struct WebsocketManager {
@@ -30,9 +32,10 @@ impl WebsocketManager {
         id: &str,
         url: &str,
         headers: HeaderMap<HeaderValue>,
+        tls_options: &TlsOptions,
         receive_tx: mpsc::Sender<Message>,
-    ) -> Result<Response> {
-        let (stream, response) = ws_connect(url, headers).await?;
+    ) -> Result<(Response, Option<TlsInfo>)> {
+        let (stream, response, tls) = ws_connect(url, headers, tls_options).await?;
         let (write, mut read) = stream.split();
         self.connections.lock().await.insert(id.to_string(), write);
 
@@ -46,7 +49,7 @@ impl WebsocketManager {
                 tx.send(message).await.unwrap();
             }
         });
-        Ok(response)
+        Ok((response, tls))
     }
 
     pub async fn send(&mut self, id: &str, msg: Message) -> Result<()> {
@@ -1,18 +1,19 @@
 use std::collections::BTreeMap;
 use yaak_models::models::{Environment, HttpRequestHeader, WebsocketRequest};
-use yaak_models::render::make_vars_hashmap;
+use yaak_models::render::Inherited;
 use yaak_templates::{parse_and_render, render_json_value_raw, TemplateCallback};
 
 pub async fn render_request<T: TemplateCallback>(
     r: &WebsocketRequest,
+    inherited: &Inherited,
     base_environment: &Environment,
     environment: Option<&Environment>,
     cb: &T,
 ) -> WebsocketRequest {
-    let vars = &make_vars_hashmap(base_environment, environment);
+    let vars = &inherited.variable_layers(base_environment, environment).to_hashmap();
 
     let mut headers = Vec::new();
-    for p in r.headers.clone() {
+    for p in inherited.headers(&r.headers) {
         headers.push(HttpRequestHeader {
             enabled: p.enabled,
             name: parse_and_render(&p.name, vars, cb).await,
@@ -21,8 +22,10 @@ pub async fn render_request<T: TemplateCallback>(
         })
     }
 
+    let (authentication_type, unrendered_authentication) =
+        inherited.authentication(&r.authentication_type, &r.authentication);
     let mut authentication = BTreeMap::new();
-    for (k, v) in r.authentication.clone() {
+    for (k, v) in unrendered_authentication {
         authentication.insert(k, render_json_value_raw(v, vars, cb).await);
     }
 
@@ -34,6 +37,7 @@ pub async fn render_request<T: TemplateCallback>(
         url,
         headers,
         authentication,
+        authentication_type,
         message,
         ..r.to_owned()
     }
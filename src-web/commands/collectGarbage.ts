@@ -0,0 +1,24 @@
+import { formatSize } from '@yaakapp-internal/lib/formatSize';
+import type { GarbageCollectionReport } from '@yaakapp-internal/models';
+import { createFastMutation } from '../hooks/useFastMutation';
+import { pluralizeCount } from '../lib/pluralize';
+import { invokeCmd } from '../lib/tauri';
+import { showToast } from '../lib/toast';
+
+export const collectGarbage = createFastMutation<GarbageCollectionReport>({
+  mutationKey: ['collect_garbage'],
+  mutationFn: () => invokeCmd<GarbageCollectionReport>('cmd_collect_garbage'),
+  onSuccess: (report) => {
+    const deleted = [
+      pluralizeCount('response', report.responsesDeleted),
+      pluralizeCount('connection', report.connectionsDeleted),
+      pluralizeCount('event', report.eventsDeleted),
+      pluralizeCount('unused file', report.filesDeleted),
+    ];
+    showToast({
+      id: 'collect-garbage',
+      color: 'success',
+      message: `Reclaimed ${formatSize(report.bytesReclaimed)} by deleting ${deleted.join(', ')}`,
+    });
+  },
+});
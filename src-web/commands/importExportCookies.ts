@@ -0,0 +1,56 @@
+import { open, save } from '@tauri-apps/plugin-dialog';
+import type { CookieJar } from '@yaakapp-internal/models';
+import slugify from 'slugify';
+import { cookieJarsAtom } from '../hooks/useCookieJars';
+import { createFastMutation } from '../hooks/useFastMutation';
+import { updateModelList } from '../hooks/useSyncModelStores';
+import { jotaiStore } from '../lib/jotai';
+import { pluralizeCount } from '../lib/pluralize';
+import { invokeCmd } from '../lib/tauri';
+import { showToast } from '../lib/toast';
+
+const filters = [
+  { name: 'Netscape cookies.txt', extensions: ['txt'] },
+  { name: 'HAR', extensions: ['har', 'json'] },
+];
+
+function formatFromPath(filePath: string) {
+  return /\.(har|json)$/i.test(filePath) ? 'har' : 'netscape';
+}
+
+export const importCookies = createFastMutation<CookieJar | null, void, CookieJar>({
+  mutationKey: ['import_cookies'],
+  mutationFn: async (cookieJar) => {
+    const filePath = await open({ title: 'Import Cookies', multiple: false, filters });
+    if (filePath == null) return null;
+
+    const updated = await invokeCmd<CookieJar>('cmd_import_cookies', {
+      cookieJarId: cookieJar.id,
+      filePath,
+      format: formatFromPath(filePath),
+    });
+    jotaiStore.set(cookieJarsAtom, updateModelList(updated));
+    const added = updated.cookies.length - cookieJar.cookies.length;
+    showToast({ message: `Imported cookies (${pluralizeCount('new cookie', added)})` });
+    return updated;
+  },
+});
+
+export const exportCookies = createFastMutation<void, void, CookieJar>({
+  mutationKey: ['export_cookies'],
+  mutationFn: async (cookieJar) => {
+    const filePath = await save({
+      title: 'Export Cookies',
+      defaultPath: `${slugify(cookieJar.name, { lower: true })}-cookies.txt`,
+      filters,
+    });
+    if (filePath == null) return;
+
+    await invokeCmd('cmd_export_cookies', {
+      cookieJarId: cookieJar.id,
+      filePath,
+      format: formatFromPath(filePath),
+    });
+    showToast({ message: `Exported ${pluralizeCount('cookie', cookieJar.cookies.length)}` });
+  },
+});
@@ -0,0 +1,44 @@
+import { save } from '@tauri-apps/plugin-dialog';
+import type { HttpRunReport } from '@yaakapp-internal/models';
+import slugify from 'slugify';
+import { createFastMutation } from '../hooks/useFastMutation';
+import { invokeCmd } from '../lib/tauri';
+import { showToast } from '../lib/toast';
+
+export interface RunHttpRequestsArgs {
+  workspaceId: string;
+  folderId: string | null;
+  environmentId: string | null;
+  cookieJarId: string | null;
+  stopOnFailure: boolean;
+  // Milliseconds to wait between requests
+  delay: number;
+  // CSV or JSON file of rows to send every request with
+  dataFile: string | null;
+}
+
+export const runHttpRequests = createFastMutation<HttpRunReport, string, RunHttpRequestsArgs>({
+  mutationKey: ['run_http_requests'],
+  mutationFn: (args) => invokeCmd<HttpRunReport>('cmd_run_http_requests', { ...args }),
+});
+
+const filters = [
+  { name: 'JUnit XML', extensions: ['xml'] },
+  { name: 'JSON', extensions: ['json'] },
+];
+
+export const saveRunReport = createFastMutation<void, string, HttpRunReport>({
+  mutationKey: ['save_run_report'],
+  mutationFn: async (report) => {
+    const filepath = await save({
+      title: 'Save Report',
+      defaultPath: `${slugify(report.name || 'report', { lower: true })}-report.xml`,
+      filters,
+    });
+    if (filepath == null) return;
+
+    const format = /\.json$/i.test(filepath) ? 'json' : 'junit';
+    await invokeCmd('cmd_save_run_report', { report, format, filepath });
+    showToast({ message: 'Report saved' });
+  },
+});
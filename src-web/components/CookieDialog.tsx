@@ -1,7 +1,8 @@
-import type { Cookie } from '@yaakapp-internal/models';
-import { useCookieJars } from '../hooks/useCookieJars';
-import { useUpdateCookieJar } from '../hooks/useUpdateCookieJar';
-import { cookieDomain } from '../lib/model_util';
+import type { Cookie, CookieJar } from '@yaakapp-internal/models';
+import { useSetAtom } from 'jotai/index';
+import { cookieJarsAtom, useCookieJars } from '../hooks/useCookieJars';
+import { updateModelList } from '../hooks/useSyncModelStores';
+import { invokeCmd } from '../lib/tauri';
 import { Banner } from './core/Banner';
 import { IconButton } from './core/IconButton';
 import { InlineCode } from './core/InlineCode';
@@ -11,7 +12,7 @@ interface Props {
 }
 
 export const CookieDialog = function ({ cookieJarId }: Props) {
-  const updateCookieJar = useUpdateCookieJar(cookieJarId ?? null);
+  const setCookieJars = useSetAtom(cookieJarsAtom);
   const cookieJars = useCookieJars();
   const cookieJar = cookieJars?.find((c) => c.id === cookieJarId);
 
@@ -33,18 +34,22 @@ export const CookieDialog = function ({ cookieJarId }: Props) {
         <thead>
           <tr>
             <th className="py-2 text-left">Domain</th>
+            <th className="py-2 text-left pl-4">Path</th>
             <th className="py-2 text-left pl-4">Cookie</th>
             <th className="py-2 pl-4"></th>
           </tr>
         </thead>
         <tbody className="divide-y divide-surface-highlight">
           {cookieJar?.cookies.map((c: Cookie) => (
-            <tr key={c.domain + c.raw_cookie}>
+            <tr key={c.domain + c.path + c.name}>
               <td className="py-2 select-text cursor-text font-mono font-semibold max-w-0">
-                {cookieDomain(c)}
+                {c.hostOnly ? c.domain : `.${c.domain}`}
+              </td>
+              <td className="py-2 pl-4 select-text cursor-text font-mono text-text-subtle">
+                {c.path}
               </td>
               <td className="py-2 pl-4 select-text cursor-text font-mono text-text-subtle whitespace-nowrap overflow-x-auto max-w-[200px] hide-scrollbars">
-                {c.raw_cookie}
+                {c.name}={c.value}
               </td>
               <td className="max-w-0 w-10">
                 <IconButton
@@ -54,10 +59,11 @@ export const CookieDialog = function ({ cookieJarId }: Props) {
                   title="Delete"
                   className="ml-auto"
                   onClick={async () => {
-                    await updateCookieJar.mutateAsync({
-                      ...cookieJar,
-                      cookies: cookieJar.cookies.filter((c2: Cookie) => c2 !== c),
+                    const updated = await invokeCmd<CookieJar>('cmd_delete_cookie', {
+                      cookieJarId: cookieJar.id,
+                      cookie: c,
                     });
+                    setCookieJars(updateModelList(updated));
                   }}
                 />
               </td>
@@ -1,5 +1,6 @@
 import { useAtomValue } from 'jotai';
 import { memo, useMemo } from 'react';
+import { exportCookies, importCookies } from '../commands/importExportCookies';
 import { useActiveCookieJar } from '../hooks/useActiveCookieJar';
 import { cookieJarsAtom } from '../hooks/useCookieJars';
 import { useCreateCookieJar } from '../hooks/useCreateCookieJar';
@@ -48,6 +49,18 @@ export const CookieDropdown = memo(function CookieDropdown() {
                 });
               },
             },
+            {
+              key: 'import',
+              label: 'Import Cookies',
+              leftSlot: <Icon icon="import" />,
+              onSelect: () => importCookies.mutate(activeCookieJar),
+            },
+            {
+              key: 'export',
+              label: 'Export Cookies',
+              leftSlot: <Icon icon="download" />,
+              onSelect: () => exportCookies.mutate(activeCookieJar),
+            },
             {
               key: 'rename',
               label: 'Rename',
@@ -1,8 +1,12 @@
 import { useFolders } from '../hooks/useFolders';
 import { useUpdateAnyFolder } from '../hooks/useUpdateAnyFolder';
 import { Input } from './core/Input';
+import { PairOrBulkEditor } from './core/PairOrBulkEditor';
 import { VStack } from './core/Stacks';
+import { HeadersAndAuthenticationEditor } from './HeadersAndAuthenticationEditor';
 import { MarkdownEditor } from './MarkdownEditor';
+import { ResolvedVariablesTable } from './ResolvedVariablesTable';
+import { ScriptsEditor } from './ScriptsEditor';
 
 interface Props {
   folderId: string | null;
@@ -41,6 +45,43 @@ export function FolderSettingsDialog({ folderId }: Props) {
           });
         }}
       />
+
+      <VStack space={2}>
+        <div className="text-text-subtle text-sm">Variables</div>
+        <div className="min-h-[8rem] grid">
+          <PairOrBulkEditor
+            preferenceName="environment"
+            namePlaceholder="VAR_NAME"
+            nameAutocompleteVariables={false}
+            valueAutocompleteVariables
+            forceUpdateKey={folder.id}
+            pairs={folder.variables}
+            stateKey={`folder_variables.${folder.id}`}
+            onChange={(variables) => {
+              if (folderId == null) return;
+              updateFolder({ id: folderId, update: (folder) => ({ ...folder, variables }) });
+            }}
+          />
+        </div>
+        <div className="text-text-subtle text-sm">Variables of Requests in this Folder</div>
+        <ResolvedVariablesTable folderId={folder.id} />
+      </VStack>
+
+      <HeadersAndAuthenticationEditor
+        model={folder}
+        onChange={(update) => {
+          if (folderId == null) return;
+          updateFolder({ id: folderId, update: (folder) => ({ ...folder, ...update }) });
+        }}
+      />
+
+      <ScriptsEditor
+        model={folder}
+        onChange={(update) => {
+          if (folderId == null) return;
+          updateFolder({ id: folderId, update: (folder) => ({ ...folder, ...update }) });
+        }}
+      />
     </VStack>
   );
 }
@@ -12,9 +12,11 @@ import { useSyncModelStores } from '../hooks/useSyncModelStores';
 import { useSyncWorkspaceChildModels } from '../hooks/useSyncWorkspaceChildModels';
 import { useSyncZoomSetting } from '../hooks/useSyncZoomSetting';
 import { useSubscribeTemplateFunctions } from '../hooks/useTemplateFunctions';
+import { showConfirm } from '../lib/confirm';
 import { generateId } from '../lib/generateId';
 import { showPrompt } from '../lib/prompt';
 import { showToast } from '../lib/toast';
+import { InlineCode } from './core/InlineCode';
 
 export function GlobalHooks() {
   useSyncModelStores();
@@ -37,6 +39,27 @@ export function GlobalHooks() {
     showToast({ ...event.payload });
   });
 
+  // Ask before running the scripts of a workspace that isn't trusted yet
+  useListenToTauriEvent<{ replyId: string; workspaceName: string }>(
+    'trust_scripts_request',
+    async ({ payload }) => {
+      const confirmed = await showConfirm({
+        id: 'trust-scripts',
+        title: 'Trust Workspace Scripts?',
+        description: (
+          <>
+            <InlineCode>{payload.workspaceName}</InlineCode> has scripts that run when its requests
+            are sent. Scripts can read and change requests, responses, and variables, so only trust
+            workspaces from people you trust.
+          </>
+        ),
+        confirmText: 'Trust Workspace',
+        color: 'warning',
+      });
+      await emit(payload.replyId, confirmed);
+    },
+  );
+
   // Listen for plugin events
   useListenToTauriEvent<InternalEvent>('plugin_event', async ({ payload: event }) => {
     if (event.payload.type === 'prompt_text_request') {
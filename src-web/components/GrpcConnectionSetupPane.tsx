@@ -21,7 +21,7 @@ import { HStack, VStack } from './core/Stacks';
 import type { TabItem } from './core/Tabs/Tabs';
 import { TabContent, Tabs } from './core/Tabs/Tabs';
 import { GrpcEditor } from './GrpcEditor';
-import { HttpAuthenticationEditor } from './HttpAuthenticationEditor';
+import { HttpAuthenticationEditor, INHERIT_AUTHENTICATION } from './HttpAuthenticationEditor';
 import { MarkdownEditor } from './MarkdownEditor';
 import { UrlBar } from './UrlBar';
 
@@ -148,6 +148,7 @@ export function GrpcConnectionSetupPane({
               value: a.name,
             })),
             { type: 'separator' },
+            { label: 'Inherit from Parent', shortLabel: 'Inherited', value: INHERIT_AUTHENTICATION },
             { label: 'No Authentication', shortLabel: 'Auth', value: null },
           ],
           onChange: (authenticationType) => {
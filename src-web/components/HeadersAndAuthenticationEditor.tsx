@@ -0,0 +1,58 @@
+import type { Folder, Workspace } from '@yaakapp-internal/models';
+import { useHttpAuthenticationSummaries } from '../hooks/useHttpAuthentication';
+import { Select } from './core/Select';
+import { VStack } from './core/Stacks';
+import { HeadersEditor } from './HeadersEditor';
+import { HttpAuthenticationEditor, INHERIT_AUTHENTICATION } from './HttpAuthenticationEditor';
+
+type Inheritable = Pick<Folder | Workspace, 'headers' | 'authenticationType' | 'authentication'>;
+
+interface Props {
+  model: Folder | Workspace;
+  forceUpdateKey?: string;
+  onChange: (update: Partial<Inheritable>) => void;
+}
+
+// Select values can't be null, so this stands in for no authentication
+const NO_AUTHENTICATION = '__none__';
+
+/** Edit the headers and authentication that every request inside a folder or workspace gets */
+export function HeadersAndAuthenticationEditor({ model, forceUpdateKey, onChange }: Props) {
+  const authentication = useHttpAuthenticationSummaries();
+
+  const options = [
+    ...authentication.map((a) => ({ label: a.label || 'UNKNOWN', value: a.name })),
+    // A workspace has no parent to inherit from
+    ...(model.model === 'folder'
+      ? [{ label: 'Inherit from Parent', value: INHERIT_AUTHENTICATION }]
+      : []),
+    { label: 'No Authentication', value: NO_AUTHENTICATION },
+  ];
+
+  return (
+    <VStack space={2} className="pb-4">
+      <div className="text-text-subtle text-sm">Headers</div>
+      <div className="min-h-[8rem] grid">
+        <HeadersEditor
+          forceUpdateKey={forceUpdateKey ?? model.id}
+          headers={model.headers}
+          stateKey={`headers.${model.id}`}
+          onChange={(headers) => onChange({ headers })}
+        />
+      </div>
+      <Select
+        name={`authenticationType.${model.id}`}
+        label="Authentication"
+        value={model.authenticationType ?? NO_AUTHENTICATION}
+        options={options}
+        onChange={(value) => {
+          const authenticationType = value === NO_AUTHENTICATION ? null : value;
+          if (authenticationType === model.authenticationType) return;
+          // Reset auth if changing types
+          onChange({ authenticationType, authentication: {} });
+        }}
+      />
+      <HttpAuthenticationEditor request={model} />
+    </VStack>
+  );
+}
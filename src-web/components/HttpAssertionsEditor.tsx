@@ -0,0 +1,110 @@
+import type { HttpAssertion, HttpAssertionType, HttpRequest } from '@yaakapp-internal/models';
+import React from 'react';
+import { useUpdateAnyHttpRequest } from '../hooks/useUpdateAnyHttpRequest';
+import { generateId } from '../lib/generateId';
+import { assertionTypes } from '../lib/httpAssertions';
+import { Button } from './core/Button';
+import { Checkbox } from './core/Checkbox';
+import { IconButton } from './core/IconButton';
+import { PlainInput } from './core/PlainInput';
+import { Select } from './core/Select';
+import { VStack } from './core/Stacks';
+
+interface Props {
+  request: HttpRequest;
+  forceUpdateKey: string;
+}
+
+const typeOptions = Object.entries(assertionTypes)
+  .filter(([value]) => value !== 'script')
+  .map(([value, { label }]) => ({
+    label,
+    value: value as HttpAssertionType,
+  }));
+
+/** Checks to run against each response to the request once it finishes */
+export function HttpAssertionsEditor({ request, forceUpdateKey }: Props) {
+  const updateRequest = useUpdateAnyHttpRequest();
+
+  const setAssertions = (assertions: HttpAssertion[]) =>
+    updateRequest.mutate({ id: request.id, update: { assertions } });
+  const updateAssertion = (i: number, update: Partial<HttpAssertion>) =>
+    setAssertions(request.assertions.map((a, j) => (i === j ? { ...a, ...update } : a)));
+
+  return (
+    <VStack space={2} className="pb-4">
+      {request.assertions.map((assertion, i) => {
+        const { targetPlaceholder, valuePlaceholder } = assertionTypes[assertion.assertionType];
+        return (
+          <div
+            key={assertion.id ?? i}
+            className="grid grid-cols-[auto_minmax(0,12rem)_minmax(0,1fr)_minmax(0,1fr)_auto] gap-2 items-center"
+          >
+            <Checkbox
+              hideLabel
+              title="Enabled"
+              checked={assertion.enabled !== false}
+              onChange={(enabled) => updateAssertion(i, { enabled })}
+            />
+            <Select
+              hideLabel
+              size="sm"
+              name={`assertionType.${i}`}
+              label="Type"
+              value={assertion.assertionType}
+              options={typeOptions}
+              onChange={(assertionType) => updateAssertion(i, { assertionType })}
+            />
+            {targetPlaceholder == null ? (
+              <span />
+            ) : (
+              <PlainInput
+                hideLabel
+                size="sm"
+                name={`assertionTarget.${i}`}
+                label="Target"
+                forceUpdateKey={forceUpdateKey}
+                placeholder={targetPlaceholder}
+                defaultValue={assertion.target}
+                onChange={(target) => updateAssertion(i, { target })}
+              />
+            )}
+            {assertion.assertionType === 'json_path_exists' ? (
+              <span />
+            ) : (
+              <PlainInput
+                hideLabel
+                size="sm"
+                name={`assertionValue.${i}`}
+                label="Value"
+                forceUpdateKey={forceUpdateKey}
+                placeholder={valuePlaceholder}
+                defaultValue={assertion.value}
+                onChange={(value) => updateAssertion(i, { value })}
+              />
+            )}
+            <IconButton
+              size="sm"
+              icon="trash"
+              title="Delete assertion"
+              onClick={() => setAssertions(request.assertions.filter((_, j) => i !== j))}
+            />
+          </div>
+        );
+      })}
+      <Button
+        size="xs"
+        variant="border"
+        className="mr-auto"
+        onClick={() =>
+          setAssertions([
+            ...request.assertions,
+            { enabled: true, assertionType: 'status', target: '', value: '2xx', id: generateId() },
+          ])
+        }
+      >
+        Add Assertion
+      </Button>
+    </VStack>
+  );
+}
@@ -1,7 +1,15 @@
-import type { GrpcRequest, HttpRequest, WebsocketRequest } from '@yaakapp-internal/models';
+import type {
+  Folder,
+  GrpcRequest,
+  HttpRequest,
+  WebsocketRequest,
+  Workspace,
+} from '@yaakapp-internal/models';
 import React, { useCallback } from 'react';
 import { upsertWebsocketRequest } from '../commands/upsertWebsocketRequest';
+import { upsertWorkspace } from '../commands/upsertWorkspace';
 import { useHttpAuthenticationConfig } from '../hooks/useHttpAuthenticationConfig';
+import { useUpdateAnyFolder } from '../hooks/useUpdateAnyFolder';
 import { useUpdateAnyGrpcRequest } from '../hooks/useUpdateAnyGrpcRequest';
 import { useUpdateAnyHttpRequest } from '../hooks/useUpdateAnyHttpRequest';
 import { Checkbox } from './core/Checkbox';
@@ -13,13 +21,17 @@ import { HStack } from './core/Stacks';
 import { DynamicForm } from './DynamicForm';
 import { EmptyStateText } from './EmptyStateText';
 
+// Authentication type of requests and folders that use their parent's
+export const INHERIT_AUTHENTICATION = 'inherit';
+
 interface Props {
-  request: HttpRequest | GrpcRequest | WebsocketRequest;
+  request: HttpRequest | GrpcRequest | WebsocketRequest | Folder | Workspace;
 }
 
 export function HttpAuthenticationEditor({ request }: Props) {
   const updateHttpRequest = useUpdateAnyHttpRequest();
   const updateGrpcRequest = useUpdateAnyGrpcRequest();
+  const updateFolder = useUpdateAnyFolder();
   const authConfig = useHttpAuthenticationConfig(
     request.authenticationType,
     request.authentication,
@@ -35,6 +47,13 @@ export function HttpAuthenticationEditor({ request }: Props) {
         });
       } else if (request.model === 'websocket_request') {
         upsertWebsocketRequest.mutate({ ...request, authentication });
+      } else if (request.model === 'folder') {
+        updateFolder.mutate({
+          id: request.id,
+          update: (f) => ({ ...f, authentication }),
+        });
+      } else if (request.model === 'workspace') {
+        upsertWorkspace.mutate({ ...request, authentication });
       } else {
         updateGrpcRequest.mutate({
           id: request.id,
@@ -42,9 +61,13 @@ export function HttpAuthenticationEditor({ request }: Props) {
         });
       }
     },
-    [request, updateGrpcRequest, updateHttpRequest],
+    [request, updateFolder, updateGrpcRequest, updateHttpRequest],
   );
 
+  if (request.authenticationType === INHERIT_AUTHENTICATION) {
+    return <EmptyStateText>Inherited from the parent folder or workspace</EmptyStateText>;
+  }
+
   if (authConfig.data == null) {
     return <EmptyStateText>No Authentication {request.authenticationType}</EmptyStateText>;
   }
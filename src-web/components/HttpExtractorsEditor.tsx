@@ -0,0 +1,131 @@
+import type { HttpExtractor, HttpExtractorType, HttpRequest } from '@yaakapp-internal/models';
+import React from 'react';
+import { useEnvironments } from '../hooks/useEnvironments';
+import { useUpdateAnyHttpRequest } from '../hooks/useUpdateAnyHttpRequest';
+import { generateId } from '../lib/generateId';
+import { Button } from './core/Button';
+import { Checkbox } from './core/Checkbox';
+import { IconButton } from './core/IconButton';
+import { PlainInput } from './core/PlainInput';
+import { Select } from './core/Select';
+import { VStack } from './core/Stacks';
+
+interface Props {
+  request: HttpRequest;
+  forceUpdateKey: string;
+}
+
+const extractorTypes: Record<HttpExtractorType, { label: string; sourcePlaceholder: string }> = {
+  json_path: { label: 'JSONPath', sourcePlaceholder: '$.data.token' },
+  header: { label: 'Header', sourcePlaceholder: 'X-Request-Id' },
+  regex: { label: 'Regex', sourcePlaceholder: '"token":"(\\w+)"' },
+  cookie: { label: 'Cookie', sourcePlaceholder: 'session' },
+};
+
+const typeOptions = Object.entries(extractorTypes).map(([value, { label }]) => ({
+  label,
+  value: value as HttpExtractorType,
+}));
+
+// Select values can't be null, so this stands in for not saving to an environment
+const RUN_ONLY = '__run__';
+
+/** Values to pull out of each successful response and save as variables for later requests */
+export function HttpExtractorsEditor({ request, forceUpdateKey }: Props) {
+  const updateRequest = useUpdateAnyHttpRequest();
+  const { baseEnvironment, subEnvironments } = useEnvironments();
+
+  const environmentOptions = [
+    { label: 'Run only', value: RUN_ONLY },
+    ...[baseEnvironment, ...subEnvironments].flatMap((e) =>
+      e == null ? [] : [{ label: e.name, value: e.id }],
+    ),
+  ];
+
+  const setExtractors = (extractors: HttpExtractor[]) =>
+    updateRequest.mutate({ id: request.id, update: { extractors } });
+  const updateExtractor = (i: number, update: Partial<HttpExtractor>) =>
+    setExtractors(request.extractors.map((e, j) => (i === j ? { ...e, ...update } : e)));
+
+  return (
+    <VStack space={2} className="pb-4">
+      {request.extractors.map((extractor, i) => (
+        <div
+          key={extractor.id ?? i}
+          className="grid grid-cols-[auto_minmax(0,8rem)_minmax(0,1fr)_minmax(0,1fr)_minmax(0,10rem)_auto] gap-2 items-center"
+        >
+          <Checkbox
+            hideLabel
+            title="Enabled"
+            checked={extractor.enabled !== false}
+            onChange={(enabled) => updateExtractor(i, { enabled })}
+          />
+          <Select
+            hideLabel
+            size="sm"
+            name={`extractorType.${i}`}
+            label="Type"
+            value={extractor.extractorType}
+            options={typeOptions}
+            onChange={(extractorType) => updateExtractor(i, { extractorType })}
+          />
+          <PlainInput
+            hideLabel
+            size="sm"
+            name={`extractorSource.${i}`}
+            label="Source"
+            forceUpdateKey={forceUpdateKey}
+            placeholder={extractorTypes[extractor.extractorType].sourcePlaceholder}
+            defaultValue={extractor.source}
+            onChange={(source) => updateExtractor(i, { source })}
+          />
+          <PlainInput
+            hideLabel
+            size="sm"
+            name={`extractorVariable.${i}`}
+            label="Variable"
+            forceUpdateKey={forceUpdateKey}
+            placeholder="Variable name"
+            defaultValue={extractor.variable}
+            onChange={(variable) => updateExtractor(i, { variable })}
+          />
+          <Select
+            hideLabel
+            size="sm"
+            name={`extractorEnvironment.${i}`}
+            label="Save to"
+            value={extractor.environmentId ?? RUN_ONLY}
+            options={environmentOptions}
+            onChange={(id) => updateExtractor(i, { environmentId: id === RUN_ONLY ? null : id })}
+          />
+          <IconButton
+            size="sm"
+            icon="trash"
+            title="Delete extractor"
+            onClick={() => setExtractors(request.extractors.filter((_, j) => i !== j))}
+          />
+        </div>
+      ))}
+      <Button
+        size="xs"
+        variant="border"
+        className="mr-auto"
+        onClick={() =>
+          setExtractors([
+            ...request.extractors,
+            {
+              enabled: true,
+              extractorType: 'json_path',
+              source: '',
+              variable: '',
+              environmentId: null,
+              id: generateId(),
+            },
+          ])
+        }
+      >
+        Add Extractor
+      </Button>
+    </VStack>
+  );
+}
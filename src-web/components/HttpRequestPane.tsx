@@ -46,8 +46,13 @@ import { FormMultipartEditor } from './FormMultipartEditor';
 import { FormUrlencodedEditor } from './FormUrlencodedEditor';
 import { GraphQLEditor } from './GraphQLEditor';
 import { HeadersEditor } from './HeadersEditor';
-import { HttpAuthenticationEditor } from './HttpAuthenticationEditor';
+import { HttpAssertionsEditor } from './HttpAssertionsEditor';
+import { HttpAuthenticationEditor, INHERIT_AUTHENTICATION } from './HttpAuthenticationEditor';
+import { HttpExtractorsEditor } from './HttpExtractorsEditor';
 import { MarkdownEditor } from './MarkdownEditor';
+import { RequestHistorySettings } from './RequestHistorySettings';
+import { ResponseCharsetSelect } from './ResponseCharsetSelect';
+import { ScriptsEditor } from './ScriptsEditor';
 import { UrlBar } from './UrlBar';
 import { UrlParametersEditor } from './UrlParameterEditor';
 
@@ -62,6 +67,9 @@ const TAB_BODY = 'body';
 const TAB_PARAMS = 'params';
 const TAB_HEADERS = 'headers';
 const TAB_AUTH = 'auth';
+const TAB_TESTS = 'tests';
+const TAB_EXTRACT = 'extract';
+const TAB_SCRIPTS = 'scripts';
 const TAB_DESCRIPTION = 'description';
 
 const tabsAtom = atomWithStorage<Record<string, string>>('requestPaneActiveTabs', {});
@@ -230,6 +238,7 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
               value: a.name,
             })),
             { type: 'separator' },
+            { label: 'Inherit from Parent', shortLabel: 'Inherited', value: INHERIT_AUTHENTICATION },
             { label: 'No Authentication', shortLabel: 'Auth', value: null },
           ],
           onChange: async (authenticationType) => {
@@ -246,15 +255,35 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
           },
         },
       },
+      {
+        value: TAB_TESTS,
+        label: 'Tests',
+        rightSlot: (
+          <CountBadge count={activeRequest.assertions.filter((a) => a.enabled !== false).length} />
+        ),
+      },
+      {
+        value: TAB_EXTRACT,
+        label: 'Extract',
+        rightSlot: (
+          <CountBadge count={activeRequest.extractors.filter((e) => e.enabled !== false).length} />
+        ),
+      },
+      {
+        value: TAB_SCRIPTS,
+        label: 'Scripts',
+      },
       {
         value: TAB_DESCRIPTION,
         label: 'Info',
       },
     ],
     [
+      activeRequest.assertions,
       activeRequest.authentication,
       activeRequest.authenticationType,
       activeRequest.bodyType,
+      activeRequest.extractors,
       activeRequest.headers,
       activeRequest.method,
       activeRequestId,
@@ -475,8 +504,21 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
                 <EmptyStateText>No Body</EmptyStateText>
               )}
             </TabContent>
+            <TabContent value={TAB_TESTS}>
+              <HttpAssertionsEditor request={activeRequest} forceUpdateKey={forceUpdateKey} />
+            </TabContent>
+            <TabContent value={TAB_EXTRACT}>
+              <HttpExtractorsEditor request={activeRequest} forceUpdateKey={forceUpdateKey} />
+            </TabContent>
+            <TabContent value={TAB_SCRIPTS}>
+              <ScriptsEditor
+                model={activeRequest}
+                forceUpdateKey={forceUpdateKey}
+                onChange={(update) => updateRequest({ id: activeRequestId, update })}
+              />
+            </TabContent>
             <TabContent value={TAB_DESCRIPTION}>
-              <div className="grid grid-rows-[auto_minmax(0,1fr)] h-full">
+              <div className="grid grid-rows-[auto_auto_auto_minmax(0,1fr)] h-full">
                 <PlainInput
                   label="Request Name"
                   hideLabel
@@ -487,6 +529,8 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
                   placeholder={resolvedModelName(activeRequest)}
                   onChange={(name) => updateRequest({ id: activeRequestId, update: { name } })}
                 />
+                <ResponseCharsetSelect request={activeRequest} />
+                <RequestHistorySettings request={activeRequest} forceUpdateKey={updateKey} />
                 <MarkdownEditor
                   name="request-description"
                   placeholder="Request description"
@@ -17,8 +17,10 @@ import { HStack } from './core/Stacks';
 import { StatusTag } from './core/StatusTag';
 import type { TabItem } from './core/Tabs/Tabs';
 import { TabContent, Tabs } from './core/Tabs/Tabs';
+import { UploadProgressTag } from './core/UploadProgressTag';
 import { EmptyStateText } from './EmptyStateText';
 import { RecentHttpResponsesDropdown } from './RecentHttpResponsesDropdown';
+import { ResponseAssertions } from './ResponseAssertions';
 import { ResponseHeaders } from './ResponseHeaders';
 import { ResponseInfo } from './ResponseInfo';
 import { AudioViewer } from './responseViewers/AudioViewer';
@@ -39,6 +41,7 @@ interface Props {
 const TAB_BODY = 'body';
 const TAB_HEADERS = 'headers';
 const TAB_INFO = 'info';
+const TAB_TESTS = 'tests';
 
 export function HttpResponsePane({ style, className, activeRequestId }: Props) {
   const { activeResponse, setPinnedResponseId, responses } = usePinnedHttpResponse(activeRequestId);
@@ -48,6 +51,8 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
     {},
   );
   const contentType = getContentTypeFromHeaders(activeResponse?.headers ?? null);
+  const assertionResults = activeResponse?.assertionResults ?? [];
+  const failedCount = assertionResults.filter((r) => !r.passed).length;
 
   const tabs = useMemo<TabItem[]>(
     () => [
@@ -72,12 +77,29 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
           />
         ),
       },
+      {
+        value: TAB_TESTS,
+        label: 'Tests',
+        rightSlot: (
+          <CountBadge
+            count={failedCount || assertionResults.length}
+            className={failedCount > 0 ? 'text-danger' : 'text-success'}
+          />
+        ),
+      },
       {
         value: TAB_INFO,
         label: 'Info',
       },
     ],
-    [activeResponse?.headers, contentType, setViewMode, viewMode],
+    [
+      activeResponse?.headers,
+      assertionResults.length,
+      contentType,
+      failedCount,
+      setViewMode,
+      viewMode,
+    ],
   );
   const activeTab = activeTabs?.[activeRequestId];
   const setActiveTab = useCallback(
@@ -122,6 +144,7 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
               >
                 {activeResponse.state !== 'closed' && <LoadingIcon size="sm" />}
                 <StatusTag showReason response={activeResponse} />
+                <UploadProgressTag response={activeResponse} />
                 <span>&bull;</span>
                 <DurationTag
                   headers={activeResponse.elapsedHeaders}
@@ -191,6 +214,9 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
               <TabContent value={TAB_HEADERS}>
                 <ResponseHeaders response={activeResponse} />
               </TabContent>
+              <TabContent value={TAB_TESTS}>
+                <ResponseAssertions response={activeResponse} />
+              </TabContent>
               <TabContent value={TAB_INFO}>
                 <ResponseInfo response={activeResponse} />
               </TabContent>
@@ -0,0 +1,75 @@
+import type { HttpRequest } from '@yaakapp-internal/models';
+import React from 'react';
+import { useActiveWorkspace } from '../hooks/useActiveWorkspace';
+import { useUpdateAnyHttpRequest } from '../hooks/useUpdateAnyHttpRequest';
+import { PlainInput } from './core/PlainInput';
+
+const MB = 1024 * 1024;
+
+interface Props {
+  request: HttpRequest;
+  forceUpdateKey: string;
+}
+
+/** Overrides for the workspace's response history settings. Empty inputs use the workspace's. */
+export function RequestHistorySettings({ request, forceUpdateKey }: Props) {
+  const workspace = useActiveWorkspace();
+  const updateRequest = useUpdateAnyHttpRequest();
+  if (workspace == null) {
+    return null;
+  }
+
+  const update = (update: Partial<HttpRequest>) => updateRequest.mutate({ id: request.id, update });
+
+  return (
+    <div className="grid grid-cols-3 gap-2">
+      <PlainInput
+        size="sm"
+        name="historyMaxCount"
+        label="Responses to Keep"
+        forceUpdateKey={forceUpdateKey}
+        placeholder={`${workspace.settingHistoryMaxCount || 'No limit'}`}
+        defaultValue={request.historyMaxCount == null ? '' : `${request.historyMaxCount}`}
+        validate={isEmptyOrPositive}
+        onChange={(v) => update({ historyMaxCount: parseOverride(v) })}
+        type="number"
+      />
+      <PlainInput
+        size="sm"
+        name="historyMaxAgeDays"
+        label="Max Age (days)"
+        forceUpdateKey={forceUpdateKey}
+        placeholder={`${workspace.settingHistoryMaxAgeDays || 'No limit'}`}
+        defaultValue={request.historyMaxAgeDays == null ? '' : `${request.historyMaxAgeDays}`}
+        validate={isEmptyOrPositive}
+        onChange={(v) => update({ historyMaxAgeDays: parseOverride(v) })}
+        type="number"
+      />
+      <PlainInput
+        size="sm"
+        name="historyMaxBytes"
+        label="Max Size (MB)"
+        forceUpdateKey={forceUpdateKey}
+        placeholder={`${Math.round(workspace.settingHistoryMaxBytes / MB) || 'No limit'}`}
+        defaultValue={
+          request.historyMaxBytes == null ? '' : `${Math.round(request.historyMaxBytes / MB)}`
+        }
+        validate={isEmptyOrPositive}
+        onChange={(v) => {
+          const mb = parseOverride(v);
+          update({ historyMaxBytes: mb == null ? null : mb * MB });
+        }}
+        type="number"
+      />
+    </div>
+  );
+}
+
+function isEmptyOrPositive(value: string) {
+  return value === '' || parseInt(value) >= 0;
+}
+
+function parseOverride(value: string): number | null {
+  const n = parseInt(value);
+  return Number.isNaN(n) ? null : n;
+}
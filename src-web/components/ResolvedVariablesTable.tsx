@@ -0,0 +1,47 @@
+import { useResolvedVariables } from '../hooks/useResolvedVariables';
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeaderCell,
+  TableRow,
+  TruncatedWideTableCell,
+} from './core/Table';
+import { EmptyStateText } from './EmptyStateText';
+
+interface Props {
+  folderId: string | null;
+}
+
+/** Preview the variables requests in a folder are rendered with, and where each comes from */
+export function ResolvedVariablesTable({ folderId }: Props) {
+  const variables = useResolvedVariables(folderId);
+
+  if (variables.data == null || variables.data.length === 0) {
+    return <EmptyStateText>No variables</EmptyStateText>;
+  }
+
+  return (
+    <Table>
+      <TableHead>
+        <TableRow>
+          <TableHeaderCell>Name</TableHeaderCell>
+          <TableHeaderCell>Value</TableHeaderCell>
+          <TableHeaderCell>Source</TableHeaderCell>
+        </TableRow>
+      </TableHead>
+      <TableBody>
+        {variables.data.map((v) => (
+          <TableRow key={v.name}>
+            <TableCell className="font-mono">{v.name}</TableCell>
+            <TruncatedWideTableCell className="font-mono select-text cursor-text">
+              {v.value}
+            </TruncatedWideTableCell>
+            <TableCell className="text-text-subtle">{v.source}</TableCell>
+          </TableRow>
+        ))}
+      </TableBody>
+    </Table>
+  );
+}
@@ -0,0 +1,43 @@
+import type { HttpResponse } from '@yaakapp-internal/models';
+import classNames from 'classnames';
+import React from 'react';
+import { describeAssertion } from '../lib/httpAssertions';
+import { EmptyStateText } from './EmptyStateText';
+import { Icon } from './core/Icon';
+
+interface Props {
+  response: HttpResponse;
+}
+
+export function ResponseAssertions({ response }: Props) {
+  if (response.assertionResults.length === 0) {
+    return <EmptyStateText>No assertions were checked</EmptyStateText>;
+  }
+
+  return (
+    <div className="overflow-auto h-full pb-4 grid grid-cols-[auto_minmax(0,1fr)] gap-x-2 gap-y-1.5 content-start">
+      {response.assertionResults.map((r, i) => (
+        <React.Fragment key={r.assertion.id ?? i}>
+          <Icon
+            className="mt-0.5"
+            icon={r.passed ? 'check_circle' : 'circle_alert'}
+            color={r.passed ? 'success' : 'danger'}
+          />
+          <div className="select-text cursor-text">
+            <div>{describeAssertion(r.assertion)}</div>
+            {(r.error ?? r.actual) != null && (
+              <div
+                className={classNames(
+                  'font-mono text-editor whitespace-pre-wrap break-all',
+                  r.error ? 'text-danger' : 'text-text-subtle',
+                )}
+              >
+                {r.error ?? `Got ${r.actual}`}
+              </div>
+            )}
+          </div>
+        </React.Fragment>
+      ))}
+    </div>
+  );
+}
@@ -0,0 +1,47 @@
+import type { HttpRequest } from '@yaakapp-internal/models';
+import React from 'react';
+import { useUpdateAnyHttpRequest } from '../hooks/useUpdateAnyHttpRequest';
+import type { RadioDropdownItem } from './core/RadioDropdown';
+import { Select } from './core/Select';
+
+const AUTO = 'auto';
+
+const charsets: RadioDropdownItem<string>[] = [
+  { label: 'Detect Automatically', value: AUTO },
+  { type: 'separator' },
+  { label: 'UTF-8', value: 'utf-8' },
+  { label: 'UTF-16LE', value: 'utf-16le' },
+  { label: 'UTF-16BE', value: 'utf-16be' },
+  { label: 'Western (Windows-1252)', value: 'windows-1252' },
+  { label: 'Central European (ISO-8859-2)', value: 'iso-8859-2' },
+  { label: 'Cyrillic (Windows-1251)', value: 'windows-1251' },
+  { label: 'Cyrillic (KOI8-R)', value: 'koi8-r' },
+  { label: 'Greek (ISO-8859-7)', value: 'iso-8859-7' },
+  { label: 'Japanese (Shift_JIS)', value: 'shift_jis' },
+  { label: 'Japanese (EUC-JP)', value: 'euc-jp' },
+  { label: 'Japanese (ISO-2022-JP)', value: 'iso-2022-jp' },
+  { label: 'Chinese Simplified (GB18030)', value: 'gb18030' },
+  { label: 'Chinese Traditional (Big5)', value: 'big5' },
+  { label: 'Korean (EUC-KR)', value: 'euc-kr' },
+];
+
+export function ResponseCharsetSelect({ request }: { request: HttpRequest }) {
+  const updateRequest = useUpdateAnyHttpRequest();
+  return (
+    <Select
+      size="sm"
+      name="responseCharset"
+      label="Response Charset"
+      labelPosition="left"
+      labelClassName="w-[10rem]"
+      value={request.responseCharset ?? AUTO}
+      options={charsets}
+      onChange={(v) =>
+        updateRequest.mutate({
+          id: request.id,
+          update: { responseCharset: v === AUTO ? null : v },
+        })
+      }
+    />
+  );
+}
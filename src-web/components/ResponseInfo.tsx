@@ -2,6 +2,8 @@ import { openUrl } from '@tauri-apps/plugin-opener';
 import type { HttpResponse } from '@yaakapp-internal/models';
 import { IconButton } from './core/IconButton';
 import { KeyValueRow, KeyValueRows } from './core/KeyValueRow';
+import { ResponseTimingRows } from './ResponseTimingRows';
+import { TlsInfoRows } from './TlsInfoRows';
 
 interface Props {
   response: HttpResponse;
@@ -38,7 +40,22 @@ export function ResponseInfo({ response }: Props) {
             </div>
           }
         </KeyValueRow>
+        {response.extractedValues.map((v, i) => (
+          <KeyValueRow
+            key={v.extractor.id ?? i}
+            labelColor="info"
+            label={`Set ${v.extractor.variable}`}
+          >
+            {v.value == null ? (
+              <span className="text-danger">{v.error}</span>
+            ) : (
+              <span className="select-text cursor-text">{v.value}</span>
+            )}
+          </KeyValueRow>
+        ))}
       </KeyValueRows>
+      {response.timing && <ResponseTimingRows timing={response.timing} />}
+      {response.tls && <TlsInfoRows tls={response.tls} />}
     </div>
   );
 }
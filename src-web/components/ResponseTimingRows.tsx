@@ -0,0 +1,34 @@
+import type { HttpResponseTiming } from '@yaakapp-internal/models';
+import { formatMillis } from './core/DurationTag';
+import { KeyValueRow, KeyValueRows } from './core/KeyValueRow';
+
+interface Props {
+  timing: HttpResponseTiming;
+}
+
+export function ResponseTimingRows({ timing }: Props) {
+  // Connection phases are null when an existing connection was reused
+  const phase = (millis: number | null) => (millis == null ? '—' : formatMillis(millis));
+  return (
+    <KeyValueRows>
+      <KeyValueRow labelColor="info" label="Redirects">
+        {formatMillis(timing.redirect)}
+      </KeyValueRow>
+      <KeyValueRow labelColor="info" label="DNS Lookup">
+        {phase(timing.dns)}
+      </KeyValueRow>
+      <KeyValueRow labelColor="info" label="TCP Connect">
+        {phase(timing.connect)}
+      </KeyValueRow>
+      <KeyValueRow labelColor="info" label="TLS Handshake">
+        {phase(timing.tls)}
+      </KeyValueRow>
+      <KeyValueRow labelColor="info" label="Time to First Byte">
+        {formatMillis(timing.ttfb)}
+      </KeyValueRow>
+      <KeyValueRow labelColor="info" label="Download">
+        {phase(timing.download)}
+      </KeyValueRow>
+    </KeyValueRows>
+  );
+}
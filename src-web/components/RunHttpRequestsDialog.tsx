@@ -0,0 +1,113 @@
+import type { HttpRunReport } from '@yaakapp-internal/models';
+import React, { useState } from 'react';
+import { runHttpRequests, saveRunReport } from '../commands/runHttpRequests';
+import { useActiveCookieJar } from '../hooks/useActiveCookieJar';
+import { useActiveEnvironment } from '../hooks/useActiveEnvironment';
+import { pluralizeCount } from '../lib/pluralize';
+import { Button } from './core/Button';
+import { Checkbox } from './core/Checkbox';
+import { Icon } from './core/Icon';
+import { PlainInput } from './core/PlainInput';
+import { HStack, VStack } from './core/Stacks';
+import { EmptyStateText } from './EmptyStateText';
+import { SelectFile } from './SelectFile';
+
+interface Props {
+  workspaceId: string;
+  folderId: string | null;
+}
+
+/** Send every request of a folder or workspace in sidebar order, and show how each went */
+export function RunHttpRequestsDialog({ workspaceId, folderId }: Props) {
+  const environment = useActiveEnvironment();
+  const cookieJar = useActiveCookieJar();
+  const [stopOnFailure, setStopOnFailure] = useState<boolean>(false);
+  const [delay, setDelay] = useState<number>(0);
+  const [dataFile, setDataFile] = useState<string | null>(null);
+  const [running, setRunning] = useState<boolean>(false);
+  const [report, setReport] = useState<HttpRunReport | null>(null);
+
+  const run = async () => {
+    setRunning(true);
+    setReport(null);
+    const report = await runHttpRequests.mutateAsync({
+      workspaceId,
+      folderId,
+      environmentId: environment?.id ?? null,
+      cookieJarId: cookieJar?.id ?? null,
+      stopOnFailure,
+      delay,
+      dataFile,
+    });
+    setReport(report);
+    setRunning(false);
+  };
+
+  const failed = report?.results.filter((r) => !r.passed && r.response != null).length ?? 0;
+  const skipped = report?.results.filter((r) => r.response == null).length ?? 0;
+
+  return (
+    <VStack space={3} className="pb-3">
+      <HStack space={3} alignItems="end">
+        <Checkbox title="Stop on failure" checked={stopOnFailure} onChange={setStopOnFailure} />
+        <PlainInput
+          size="sm"
+          type="number"
+          name="delay"
+          label="Delay (ms)"
+          className="!w-28"
+          defaultValue={`${delay}`}
+          onChange={(v) => setDelay(Math.max(0, parseInt(v) || 0))}
+        />
+        <SelectFile
+          size="sm"
+          noun="Data File"
+          filePath={dataFile}
+          title="CSV or JSON rows to send every request with, setting each column as a variable"
+          onChange={({ filePath }) => setDataFile(filePath)}
+        />
+        <Button color="primary" size="sm" isLoading={running} onClick={run}>
+          Run
+        </Button>
+      </HStack>
+
+      {report != null &&
+        (report.results.length === 0 ? (
+          <EmptyStateText>No requests to run</EmptyStateText>
+        ) : (
+          <>
+            <HStack space={2} justifyContent="between">
+              <div className="text-text-subtle">
+                {pluralizeCount('request', report.results.length)}, {failed} failed
+                {skipped > 0 && `, ${skipped} skipped`} ({report.elapsed} ms)
+              </div>
+              <Button size="xs" variant="border" onClick={() => saveRunReport.mutate(report)}>
+                Save Report
+              </Button>
+            </HStack>
+            <div className="grid grid-cols-[auto_minmax(0,1fr)_auto] gap-x-2 gap-y-1 items-center">
+              {report.results.map((r, i) => (
+                <React.Fragment key={`${r.requestId}.${i}`}>
+                  <Icon
+                    icon={r.response == null ? 'empty' : r.passed ? 'check_circle' : 'circle_alert'}
+                    color={r.passed ? 'success' : 'danger'}
+                  />
+                  <div className="truncate">
+                    {r.name}
+                    {r.iteration != null && (
+                      <span className="text-text-subtle"> [row {r.iteration + 1}]</span>
+                    )}
+                  </div>
+                  <div className="font-mono text-sm text-text-subtle">
+                    {r.response == null
+                      ? 'skipped'
+                      : (r.response.error ?? `${r.response.status} (${r.response.elapsed} ms)`)}
+                  </div>
+                </React.Fragment>
+              ))}
+            </div>
+          </>
+        ))}
+    </VStack>
+  );
+}
@@ -0,0 +1,44 @@
+import type { Folder, HttpRequest, Workspace } from '@yaakapp-internal/models';
+import { Editor } from './core/Editor/Editor';
+import { VStack } from './core/Stacks';
+
+type Scripts = Pick<HttpRequest | Folder | Workspace, 'preRequestScript' | 'postResponseScript'>;
+
+interface Props {
+  model: Scripts & { id: string };
+  forceUpdateKey?: string;
+  onChange: (update: Partial<Scripts>) => void;
+}
+
+/**
+ * Edit the JavaScript to run before a request is rendered and after its response is stored. The
+ * scripts of a workspace and its folders run around every request inside them.
+ */
+export function ScriptsEditor({ model, forceUpdateKey, onChange }: Props) {
+  return (
+    <VStack space={2} className="pb-4 min-h-[20rem]">
+      <div className="text-text-subtle text-sm">Pre-request Script</div>
+      <Editor
+        heightMode="auto"
+        language="javascript"
+        className="min-h-[6rem] border border-border"
+        forceUpdateKey={forceUpdateKey}
+        placeholder="yaak.request.headers.push({ name: 'X-Time', value: `${Date.now()}` });"
+        defaultValue={model.preRequestScript}
+        onChange={(preRequestScript) => onChange({ preRequestScript })}
+        stateKey={`pre_request_script.${model.id}`}
+      />
+      <div className="text-text-subtle text-sm">Post-response Script</div>
+      <Editor
+        heightMode="auto"
+        language="javascript"
+        className="min-h-[6rem] border border-border"
+        forceUpdateKey={forceUpdateKey}
+        placeholder="yaak.test('is ok', () => yaak.response.status === 200);"
+        defaultValue={model.postResponseScript}
+        onChange={(postResponseScript) => onChange({ postResponseScript })}
+        stateKey={`post_response_script.${model.id}`}
+      />
+    </VStack>
+  );
+}
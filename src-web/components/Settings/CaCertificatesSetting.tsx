@@ -0,0 +1,56 @@
+import type { CaCertificate, Workspace } from '@yaakapp-internal/models';
+import { upsertWorkspace } from '../../commands/upsertWorkspace';
+import { Button } from '../core/Button';
+import { Checkbox } from '../core/Checkbox';
+import { IconButton } from '../core/IconButton';
+import { HStack, VStack } from '../core/Stacks';
+import { SelectFile } from '../SelectFile';
+
+interface Props {
+  workspace: Workspace;
+}
+
+export function CaCertificatesSetting({ workspace }: Props) {
+  const certificates = workspace.settingCaCertificates;
+
+  const update = (settingCaCertificates: CaCertificate[]) =>
+    upsertWorkspace.mutate({ ...workspace, settingCaCertificates });
+
+  const updateAt = (index: number, patch: Partial<CaCertificate>) =>
+    update(certificates.map((c, i) => (i === index ? { ...c, ...patch } : c)));
+
+  return (
+    <VStack space={2}>
+      {certificates.map((c, i) => (
+        <HStack key={`${i}::${certificates.length}`} space={2}>
+          <Checkbox
+            hideLabel
+            title="Enabled"
+            checked={c.enabled}
+            onChange={(enabled) => updateAt(i, { enabled })}
+          />
+          <SelectFile
+            inline
+            noun="CA Certificate (PEM or DER)"
+            filePath={c.path || null}
+            onChange={({ filePath }) => updateAt(i, { path: filePath ?? '' })}
+          />
+          <IconButton
+            size="sm"
+            icon="trash"
+            title="Delete CA certificate"
+            onClick={() => update(certificates.filter((_, j) => j !== i))}
+          />
+        </HStack>
+      ))}
+      <Button
+        size="xs"
+        variant="border"
+        className="mr-auto"
+        onClick={() => update([...certificates, { enabled: true, path: '' }])}
+      >
+        Add CA Certificate
+      </Button>
+    </VStack>
+  );
+}
@@ -0,0 +1,68 @@
+import type { CertificatePin, Workspace } from '@yaakapp-internal/models';
+import { upsertWorkspace } from '../../commands/upsertWorkspace';
+import { Button } from '../core/Button';
+import { Checkbox } from '../core/Checkbox';
+import { IconButton } from '../core/IconButton';
+import { PlainInput } from '../core/PlainInput';
+import { HStack, VStack } from '../core/Stacks';
+
+interface Props {
+  workspace: Workspace;
+}
+
+export function CertificatePinsSetting({ workspace }: Props) {
+  const pins = workspace.settingCertificatePins;
+
+  const update = (settingCertificatePins: CertificatePin[]) =>
+    upsertWorkspace.mutate({ ...workspace, settingCertificatePins });
+
+  const updateAt = (index: number, patch: Partial<CertificatePin>) =>
+    update(pins.map((p, i) => (i === index ? { ...p, ...patch } : p)));
+
+  return (
+    <VStack space={2}>
+      {pins.map((p, i) => (
+        <HStack key={`${i}::${pins.length}`} space={2}>
+          <Checkbox
+            hideLabel
+            title="Enabled"
+            checked={p.enabled}
+            onChange={(enabled) => updateAt(i, { enabled })}
+          />
+          <PlainInput
+            hideLabel
+            size="sm"
+            name={`certificatePinHost[${i}]`}
+            label="Host"
+            placeholder="api.example.com"
+            defaultValue={p.host}
+            onChange={(host) => updateAt(i, { host })}
+          />
+          <PlainInput
+            hideLabel
+            size="sm"
+            name={`certificatePin[${i}]`}
+            label="Pin"
+            placeholder="sha256/base64-of-public-key-hash="
+            defaultValue={p.pin}
+            onChange={(pin) => updateAt(i, { pin })}
+          />
+          <IconButton
+            size="sm"
+            icon="trash"
+            title="Delete certificate pin"
+            onClick={() => update(pins.filter((_, j) => j !== i))}
+          />
+        </HStack>
+      ))}
+      <Button
+        size="xs"
+        variant="border"
+        className="mr-auto"
+        onClick={() => update([...pins, { enabled: true, host: '', pin: '' }])}
+      >
+        Add Certificate Pin
+      </Button>
+    </VStack>
+  );
+}
@@ -0,0 +1,125 @@
+import type { ClientCertificate, Workspace } from '@yaakapp-internal/models';
+import { upsertWorkspace } from '../../commands/upsertWorkspace';
+import { upsertWorkspaceMeta } from '../../commands/upsertWorkspaceMeta';
+import { useWorkspaceMeta } from '../../hooks/useWorkspaceMeta';
+import { Button } from '../core/Button';
+import { Checkbox } from '../core/Checkbox';
+import { IconButton } from '../core/IconButton';
+import { PlainInput } from '../core/PlainInput';
+import { HStack, VStack } from '../core/Stacks';
+import { SelectFile } from '../SelectFile';
+
+interface Props {
+  workspace: Workspace;
+}
+
+export function ClientCertificatesSetting({ workspace }: Props) {
+  const certificates = workspace.settingClientCertificates;
+  const workspaceMeta = useWorkspaceMeta();
+  const passphrases = workspaceMeta?.settingClientCertificatePassphrases ?? {};
+
+  const update = (settingClientCertificates: ClientCertificate[]) =>
+    upsertWorkspace.mutate({ ...workspace, settingClientCertificates });
+
+  const updateAt = (index: number, patch: Partial<ClientCertificate>) =>
+    update(certificates.map((c, i) => (i === index ? { ...c, ...patch } : c)));
+
+  // Passphrases are kept in the workspace meta so they're never synced to disk
+  const updatePassphrase = (key: string, passphrase: string) => {
+    if (workspaceMeta == null) return;
+    const settingClientCertificatePassphrases = { ...passphrases, [key]: passphrase };
+    if (!passphrase) delete settingClientCertificatePassphrases[key];
+    upsertWorkspaceMeta.mutate({ ...workspaceMeta, settingClientCertificatePassphrases });
+  };
+
+  const handleAdd = () =>
+    update([
+      ...certificates,
+      {
+        enabled: true,
+        host: '',
+        certPath: null,
+        keyPath: null,
+        pfxPath: null,
+      },
+    ]);
+
+  return (
+    <VStack space={2}>
+      {certificates.map((c, i) => {
+        const key = passphraseKey(c);
+        return (
+          <VStack
+            // There's no stable ID, and certificates are only added or removed one at a time
+            key={`${i}::${certificates.length}`}
+            space={1.5}
+            className="border border-border-subtle rounded-md p-2"
+          >
+            <HStack space={2}>
+              <Checkbox
+                hideLabel
+                title="Enabled"
+                checked={c.enabled}
+                onChange={(enabled) => updateAt(i, { enabled })}
+              />
+              <PlainInput
+                hideLabel
+                size="sm"
+                name={`clientCertificateHost[${i}]`}
+                label="Host"
+                placeholder="api.example.com, *.example.com, or localhost:8443"
+                defaultValue={c.host}
+                onChange={(host) => updateAt(i, { host })}
+              />
+              <IconButton
+                size="sm"
+                icon="trash"
+                title="Delete client certificate"
+                onClick={() => update(certificates.filter((_, j) => j !== i))}
+              />
+            </HStack>
+            <SelectFile
+              inline
+              noun="Certificate (PEM)"
+              filePath={c.certPath}
+              onChange={({ filePath }) => updateAt(i, { certPath: filePath })}
+            />
+            <SelectFile
+              inline
+              noun="Private Key (PEM)"
+              filePath={c.keyPath}
+              onChange={({ filePath }) => updateAt(i, { keyPath: filePath })}
+            />
+            <SelectFile
+              inline
+              noun="PKCS#12 Bundle (PFX)"
+              filePath={c.pfxPath}
+              onChange={({ filePath }) => updateAt(i, { pfxPath: filePath })}
+            />
+            {key != null && (
+              <PlainInput
+                size="sm"
+                type="password"
+                name={`clientCertificatePassphrase[${i}]`}
+                label="Passphrase"
+                labelPosition="left"
+                labelClassName="w-[8rem]"
+                forceUpdateKey={key}
+                defaultValue={passphrases[key] ?? ''}
+                onChange={(passphrase) => updatePassphrase(key, passphrase)}
+              />
+            )}
+          </VStack>
+        );
+      })}
+      <Button size="xs" variant="border" className="mr-auto" onClick={handleAdd}>
+        Add Client Certificate
+      </Button>
+    </VStack>
+  );
+}
+
+/** The file the passphrase decrypts, which the passphrase is stored under */
+function passphraseKey(c: ClientCertificate) {
+  return [c.pfxPath, c.keyPath, c.certPath].find((p) => p != null && p !== '') ?? null;
+}
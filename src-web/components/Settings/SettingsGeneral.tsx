@@ -1,5 +1,6 @@
 import { revealItemInDir } from '@tauri-apps/plugin-opener';
 import React from 'react';
+import { collectGarbage } from '../../commands/collectGarbage';
 import { upsertWorkspace } from '../../commands/upsertWorkspace';
 import { useActiveWorkspace } from '../../hooks/useActiveWorkspace';
 import { useAppInfo } from '../../hooks/useAppInfo';
@@ -7,6 +8,7 @@ import { useCheckForUpdates } from '../../hooks/useCheckForUpdates';
 import { useSettings } from '../../hooks/useSettings';
 import { useUpdateSettings } from '../../hooks/useUpdateSettings';
 import { revealInFinderText } from '../../lib/reveal';
+import { Button } from '../core/Button';
 import { Checkbox } from '../core/Checkbox';
 import { Heading } from '../core/Heading';
 import { IconButton } from '../core/IconButton';
@@ -15,6 +17,11 @@ import { PlainInput } from '../core/PlainInput';
 import { Select } from '../core/Select';
 import { Separator } from '../core/Separator';
 import { VStack } from '../core/Stacks';
+import { CaCertificatesSetting } from './CaCertificatesSetting';
+import { CertificatePinsSetting } from './CertificatePinsSetting';
+import { ClientCertificatesSetting } from './ClientCertificatesSetting';
+
+const MB = 1024 * 1024;
 
 export function SettingsGeneral() {
   const workspace = useActiveWorkspace();
@@ -118,6 +125,15 @@ export function SettingsGeneral() {
           }
         />
 
+        <Heading level={3}>CA Certificates</Heading>
+        <CaCertificatesSetting workspace={workspace} />
+
+        <Heading level={3}>Certificate Pins</Heading>
+        <CertificatePinsSetting workspace={workspace} />
+
+        <Heading level={3}>Client Certificates</Heading>
+        <ClientCertificatesSetting workspace={workspace} />
+
         <Checkbox
           checked={workspace.settingFollowRedirects}
           title="Follow Redirects"
@@ -128,6 +144,94 @@ export function SettingsGeneral() {
             })
           }
         />
+
+        {workspace.settingFollowRedirects && (
+          <PlainInput
+            required
+            size="sm"
+            name="maxRedirects"
+            label="Max Redirects"
+            labelClassName="w-[14rem]"
+            placeholder="10"
+            labelPosition="left"
+            defaultValue={`${workspace.settingMaxRedirects}`}
+            validate={(value) => parseInt(value) >= 0}
+            onChange={(v) =>
+              upsertWorkspace.mutate({ ...workspace, settingMaxRedirects: parseInt(v) || 0 })
+            }
+            type="number"
+          />
+        )}
+
+        <Checkbox
+          checked={workspace.settingReconnectEventStreams}
+          title="Reconnect Event Streams"
+          onChange={(settingReconnectEventStreams) =>
+            upsertWorkspace.mutate({ ...workspace, settingReconnectEventStreams })
+          }
+        />
+
+        <Heading level={3}>Response History</Heading>
+        <p className="text-sm text-text-subtle">
+          HTTP requests can override these in their settings. gRPC and WebSocket connections always
+          use them, and aren&apos;t limited by size.
+        </p>
+        <PlainInput
+          required
+          size="sm"
+          name="historyMaxCount"
+          label="Responses Per Request"
+          labelClassName="w-[14rem]"
+          placeholder="0 for no limit"
+          labelPosition="left"
+          defaultValue={`${workspace.settingHistoryMaxCount}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) =>
+            upsertWorkspace.mutate({ ...workspace, settingHistoryMaxCount: parseInt(v) || 0 })
+          }
+          type="number"
+        />
+        <PlainInput
+          required
+          size="sm"
+          name="historyMaxAgeDays"
+          label="Max Age (days)"
+          labelClassName="w-[14rem]"
+          placeholder="0 for no limit"
+          labelPosition="left"
+          defaultValue={`${workspace.settingHistoryMaxAgeDays}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) =>
+            upsertWorkspace.mutate({ ...workspace, settingHistoryMaxAgeDays: parseInt(v) || 0 })
+          }
+          type="number"
+        />
+        <PlainInput
+          required
+          size="sm"
+          name="historyMaxBytes"
+          label="Max Size Per Request (MB)"
+          labelClassName="w-[14rem]"
+          placeholder="0 for no limit"
+          labelPosition="left"
+          defaultValue={`${Math.round(workspace.settingHistoryMaxBytes / MB)}`}
+          validate={(value) => parseInt(value) >= 0}
+          onChange={(v) =>
+            upsertWorkspace.mutate({
+              ...workspace,
+              settingHistoryMaxBytes: (parseInt(v) || 0) * MB,
+            })
+          }
+          type="number"
+        />
+        <Button
+          className="mr-auto"
+          size="xs"
+          variant="border"
+          onClick={() => collectGarbage.mutate()}
+        >
+          Clean Up Now
+        </Button>
       </VStack>
 
       <Separator className="my-4" />
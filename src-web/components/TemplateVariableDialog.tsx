@@ -1,7 +1,9 @@
 import type { Tokens } from '@yaakapp-internal/templates';
 import { useCallback, useMemo, useState } from 'react';
 import { useActiveEnvironmentVariables } from '../hooks/useActiveEnvironmentVariables';
+import { useActiveRequest } from '../hooks/useActiveRequest';
 import { useRenderTemplate } from '../hooks/useRenderTemplate';
+import { useResolvedVariables } from '../hooks/useResolvedVariables';
 import { useTemplateTokensToString } from '../hooks/useTemplateTokensToString';
 import { Button } from './core/Button';
 import { InlineCode } from './core/InlineCode';
@@ -46,6 +48,9 @@ export function TemplateVariableDialog({ hide, onChange, initialTokens }: Props)
   }, [hide, onChange, tagText.data]);
 
   const rendered = useRenderTemplate(tagText.data ?? '');
+  const activeRequest = useActiveRequest();
+  const resolved = useResolvedVariables(activeRequest?.folderId ?? null);
+  const source = resolved.data?.find((v) => v.name === selectedVariableName)?.source;
 
   return (
     <VStack className="pb-3" space={4}>
@@ -61,6 +66,7 @@ export function TemplateVariableDialog({ hide, onChange, initialTokens }: Props)
       <VStack>
         <div className="text-sm text-text-subtle">Preview</div>
         <InlineCode className="select-text cursor-text">{rendered.data}</InlineCode>
+        {source && <div className="text-sm text-text-subtlest">From {source}</div>}
       </VStack>
       <Button color="primary" onClick={handleDone}>
         Done
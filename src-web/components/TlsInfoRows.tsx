@@ -0,0 +1,46 @@
+import type { TlsInfo } from '@yaakapp-internal/models';
+import { KeyValueRow, KeyValueRows } from './core/KeyValueRow';
+
+interface Props {
+  tls: TlsInfo;
+}
+
+export function TlsInfoRows({ tls }: Props) {
+  const certificateRows = tls.certificates.flatMap((c, i) => [
+    <KeyValueRow key={`subject-${i}`} labelColor="info" label={`Certificate ${i + 1}`}>
+      {c.subject}
+    </KeyValueRow>,
+    <KeyValueRow key={`issuer-${i}`} label="Issuer">
+      {c.issuer}
+    </KeyValueRow>,
+    <KeyValueRow key={`sans-${i}`} label="Alt Names">
+      {c.subjectAltNames.join(', ') || 'None'}
+    </KeyValueRow>,
+    <KeyValueRow key={`validity-${i}`} label="Valid">
+      {c.notBefore ?? '?'} – {c.notAfter ?? '?'}
+    </KeyValueRow>,
+    <KeyValueRow key={`serial-${i}`} label="Serial">
+      {c.serialNumber}
+    </KeyValueRow>,
+    <KeyValueRow key={`fingerprint-${i}`} label="SHA-256">
+      {c.fingerprintSha256}
+    </KeyValueRow>,
+  ]);
+
+  return (
+    <KeyValueRows>
+      {[
+        <KeyValueRow key="version" labelColor="info" label="TLS Version">
+          {tls.version ?? 'Unknown'}
+        </KeyValueRow>,
+        <KeyValueRow key="cipher" labelColor="info" label="Cipher Suite">
+          {tls.cipherSuite ?? 'Unknown'}
+        </KeyValueRow>,
+        <KeyValueRow key="alpn" labelColor="info" label="ALPN">
+          {tls.alpnProtocol ?? 'None'}
+        </KeyValueRow>,
+        ...certificateRows,
+      ]}
+    </KeyValueRows>
+  );
+}
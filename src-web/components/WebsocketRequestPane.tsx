@@ -32,7 +32,7 @@ import { PlainInput } from './core/PlainInput';
 import type { TabItem } from './core/Tabs/Tabs';
 import { TabContent, Tabs } from './core/Tabs/Tabs';
 import { HeadersEditor } from './HeadersEditor';
-import { HttpAuthenticationEditor } from './HttpAuthenticationEditor';
+import { HttpAuthenticationEditor, INHERIT_AUTHENTICATION } from './HttpAuthenticationEditor';
 import { MarkdownEditor } from './MarkdownEditor';
 import { UrlBar } from './UrlBar';
 import { UrlParametersEditor } from './UrlParameterEditor';
@@ -125,6 +125,7 @@ export function WebsocketRequestPane({ style, fullHeight, className, activeReque
               value: a.name,
             })),
             { type: 'separator' },
+            { label: 'Inherit from Parent', shortLabel: 'Inherited', value: INHERIT_AUTHENTICATION },
             { label: 'No Authentication', shortLabel: 'Auth', value: null },
           ],
           onChange: async (authenticationType) => {
@@ -5,11 +5,14 @@ import { useWorkspaceMeta } from '../hooks/useWorkspaceMeta';
 import { useWorkspaces } from '../hooks/useWorkspaces';
 import { Banner } from './core/Banner';
 import { Button } from './core/Button';
+import { Checkbox } from './core/Checkbox';
 import { InlineCode } from './core/InlineCode';
 import { Input } from './core/Input';
 import { Separator } from './core/Separator';
 import { VStack } from './core/Stacks';
+import { HeadersAndAuthenticationEditor } from './HeadersAndAuthenticationEditor';
 import { MarkdownEditor } from './MarkdownEditor';
+import { ScriptsEditor } from './ScriptsEditor';
 import { SyncToFilesystemSetting } from './SyncToFilesystemSetting';
 
 interface Props {
@@ -59,6 +62,28 @@ export function WorkspaceSettingsDialog({ workspaceId, hide, openSyncMenu }: Pro
         heightMode="auto"
       />
 
+      <div className="w-full">
+        <HeadersAndAuthenticationEditor
+          model={workspace}
+          onChange={(update) => upsertWorkspace.mutate({ ...workspace, ...update })}
+        />
+      </div>
+
+      <div className="w-full">
+        <ScriptsEditor
+          model={workspace}
+          onChange={(update) => upsertWorkspace.mutate({ ...workspace, ...update })}
+        />
+        <Checkbox
+          className="mt-3"
+          checked={workspaceMeta.settingTrustScripts}
+          title="Trust scripts in this workspace on this machine"
+          onChange={(settingTrustScripts) =>
+            upsertWorkspaceMeta.mutate({ ...workspaceMeta, settingTrustScripts })
+          }
+        />
+      </div>
+
       <VStack space={6} className="mt-3 w-full" alignItems="start">
         <SyncToFilesystemSetting
           value={{ filePath: workspaceMeta.settingSyncDir }}
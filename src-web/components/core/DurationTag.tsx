@@ -14,7 +14,7 @@ export function DurationTag({ total, headers }: Props) {
   );
 }
 
-function formatMillis(millis: number) {
+export function formatMillis(millis: number) {
   let num;
   let unit;
 
@@ -0,0 +1,24 @@
+import { formatSize } from '@yaakapp-internal/lib/formatSize';
+import type { HttpResponse } from '@yaakapp-internal/models';
+
+interface Props {
+  response: HttpResponse;
+}
+
+export function UploadProgressTag({ response }: Props) {
+  const { requestBytesSent: sent, requestContentLength: total, state } = response;
+
+  // Only shown until the response starts, since the upload is done by then
+  if (state !== 'initialized' || sent == null || !total) {
+    return null;
+  }
+
+  return (
+    <>
+      <span>&bull;</span>
+      <span className="font-mono" title={`${sent} of ${total} bytes sent`}>
+        {formatSize(sent)} / {formatSize(total)} ({Math.floor((sent / total) * 100)}%)
+      </span>
+    </>
+  );
+}
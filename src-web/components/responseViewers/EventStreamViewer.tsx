@@ -1,12 +1,10 @@
-import type { HttpResponse } from '@yaakapp-internal/models';
-import type { ServerSentEvent } from '@yaakapp-internal/sse';
+import type { HttpResponse, HttpResponseEvent } from '@yaakapp-internal/models';
 import classNames from 'classnames';
 import React, { Fragment, useMemo, useState } from 'react';
 import { useFormatText } from '../../hooks/useFormatText';
-import { useResponseBodyEventSource } from '../../hooks/useResponseBodyEventSource';
+import { useHttpResponseEvents } from '../../hooks/useHttpResponseEvents';
 import { isJSON } from '../../lib/contentType';
 import { AutoScroller } from '../core/AutoScroller';
-import { Banner } from '../core/Banner';
 import { Button } from '../core/Button';
 import type { EditorProps } from '../core/Editor/Editor';
 import { Editor } from '../core/Editor/Editor';
@@ -34,9 +32,9 @@ function ActualEventStreamViewer({ response }: Props) {
   const [showLarge, setShowLarge] = useState<boolean>(false);
   const [showingLarge, setShowingLarge] = useState<boolean>(false);
   const [activeEventIndex, setActiveEventIndex] = useState<number | null>(null);
-  const events = useResponseBodyEventSource(response);
+  const events = useHttpResponseEvents(response.id);
   const activeEvent = useMemo(
-    () => (activeEventIndex == null ? null : events.data?.[activeEventIndex]),
+    () => (activeEventIndex == null ? null : events[activeEventIndex]),
     [activeEventIndex, events],
   );
 
@@ -53,14 +51,7 @@ function ActualEventStreamViewer({ response }: Props) {
       minHeightPx={20}
       firstSlot={() => (
         <AutoScroller
-          data={events.data ?? []}
-          header={
-            events.error && (
-              <Banner color="danger" className="m-3">
-                {String(events.error)}
-              </Banner>
-            )
-          }
+          data={events}
           render={(event, i) => (
             <EventRow
               event={event}
@@ -138,7 +129,7 @@ function EventRow({
 }: {
   onClick: () => void;
   isActive: boolean;
-  event: ServerSentEvent;
+  event: HttpResponseEvent;
   className?: string;
   index: number;
 }) {
@@ -166,7 +157,7 @@ function EventLabels({
   index,
   isActive,
 }: {
-  event: ServerSentEvent;
+  event: HttpResponseEvent;
   index: number;
   className: string;
   isActive?: boolean;
@@ -174,7 +165,7 @@ function EventLabels({
   return (
     <HStack space={1.5} alignItems="center" className={className}>
       <InlineCode className={classNames('py-0', isActive && 'bg-text-subtlest text-text')}>
-        {event.id ?? index}
+        {event.eventId ?? index}
       </InlineCode>
       {event.eventType && (
         <InlineCode className={classNames('py-0', isActive && 'bg-text-subtlest text-text')}>
@@ -63,7 +63,7 @@ export function TextViewer({ language, text, responseId, requestId, pretty, clas
             autoFocus
             containerClassName="bg-surface"
             size="sm"
-            placeholder={language === 'json' ? 'JSONPath expression' : 'XPath expression'}
+            placeholder={language === 'json' ? 'JSONPath or jq expression' : 'XPath expression'}
             label="Filter expression"
             name="filter"
             defaultValue={filterText}
@@ -1,5 +1,6 @@
 import React, { useMemo } from 'react';
 import { duplicateWebsocketRequest } from '../../commands/duplicateWebsocketRequest';
+import { getActiveWorkspaceId } from '../../hooks/useActiveWorkspace';
 import { useCreateDropdownItems } from '../../hooks/useCreateDropdownItems';
 import { useDeleteAnyRequest } from '../../hooks/useDeleteAnyRequest';
 import { useDeleteFolder } from '../../hooks/useDeleteFolder';
@@ -19,6 +20,7 @@ import type { DropdownItem } from '../core/Dropdown';
 import { ContextMenu } from '../core/Dropdown';
 import { Icon } from '../core/Icon';
 import { FolderSettingsDialog } from '../FolderSettingsDialog';
+import { RunHttpRequestsDialog } from '../RunHttpRequestsDialog';
 import type { SidebarTreeNode } from './Sidebar';
 
 interface Props {
@@ -51,6 +53,22 @@ export function SidebarItemContextMenu({ child, show, close }: Props) {
           leftSlot: <Icon icon="send_horizontal" />,
           onSelect: () => sendManyRequests.mutate(child.children.map((c) => c.id)),
         },
+        {
+          label: 'Run Tests',
+          leftSlot: <Icon icon="flask" />,
+          onSelect: () => {
+            const workspaceId = getActiveWorkspaceId();
+            if (workspaceId == null) return;
+            showDialog({
+              id: 'run-http-requests',
+              title: `Run ${child.name}`,
+              size: 'md',
+              render: () => (
+                <RunHttpRequestsDialog workspaceId={workspaceId} folderId={child.id} />
+              ),
+            });
+          },
+        },
         {
           label: 'Settings',
           leftSlot: <Icon icon="settings" />,
@@ -1,16 +1,29 @@
-import type { EnvironmentVariable } from '@yaakapp-internal/models';
+import type { EnvironmentVariable, Folder } from '@yaakapp-internal/models';
 import { atom, useAtomValue } from 'jotai';
 import { activeEnvironmentAtom } from './useActiveEnvironment';
+import { activeRequestAtom } from './useActiveRequest';
 import { environmentsBreakdownAtom } from './useEnvironments';
+import { foldersAtom } from './useFolders';
 
 const activeEnvironmentVariablesAtom = atom((get) => {
   const { baseEnvironment } = get(environmentsBreakdownAtom);
   const activeEnvironment = get(activeEnvironmentAtom);
 
+  // Folders of the active request override the environment, outermost first
+  const folders = get(foldersAtom);
+  const requestFolders: Folder[] = [];
+  let folderId = get(activeRequestAtom)?.folderId ?? null;
+  let folder: Folder | undefined;
+  while ((folder = folders.find((f) => f.id === folderId)) != null) {
+    requestFolders.unshift(folder);
+    folderId = folder.folderId;
+  }
+
   const varMap: Record<string, EnvironmentVariable> = {};
   const allVariables = [
     ...(baseEnvironment?.variables ?? []),
     ...(activeEnvironment?.variables ?? []),
+    ...requestFolders.flatMap((f) => f.variables),
   ];
 
   for (const v of allVariables) {
@@ -0,0 +1,22 @@
+import { useQuery } from '@tanstack/react-query';
+import type { HttpResponseEvent } from '@yaakapp-internal/models';
+import { invokeCmd } from '../lib/tauri';
+
+export function httpResponseEventsQueryKey({ responseId }: { responseId: string }) {
+  return ['http_response_events', { responseId }];
+}
+
+export function useHttpResponseEvents(responseId: string | null) {
+  return (
+    useQuery<HttpResponseEvent[]>({
+      enabled: responseId !== null,
+      initialData: [],
+      queryKey: httpResponseEventsQueryKey({ responseId: responseId ?? 'n/a' }),
+      queryFn: async () => {
+        return (await invokeCmd('cmd_list_http_response_events', {
+          responseId,
+        })) as HttpResponseEvent[];
+      },
+    }).data ?? []
+  );
+}
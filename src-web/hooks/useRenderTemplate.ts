@@ -1,27 +1,32 @@
 import { useQuery } from '@tanstack/react-query';
 import { invokeCmd } from '../lib/tauri';
 import { useActiveEnvironment } from './useActiveEnvironment';
+import { useActiveRequest } from './useActiveRequest';
 import { useActiveWorkspace } from './useActiveWorkspace';
 
 export function useRenderTemplate(template: string) {
   const workspaceId = useActiveWorkspace()?.id ?? 'n/a';
   const environmentId = useActiveEnvironment()?.id ?? null;
+  // Variables of the active request's folders are used too
+  const folderId = useActiveRequest()?.folderId ?? null;
   return useQuery<string>({
     placeholderData: (prev) => prev, // Keep previous data on refetch
     refetchOnWindowFocus: false,
-    queryKey: ['render_template', template],
-    queryFn: () => renderTemplate({ template, workspaceId, environmentId }),
+    queryKey: ['render_template', template, folderId],
+    queryFn: () => renderTemplate({ template, workspaceId, folderId, environmentId }),
   });
 }
 
 export async function renderTemplate({
   template,
   workspaceId,
+  folderId,
   environmentId,
 }: {
   template: string;
   workspaceId: string;
+  folderId: string | null;
   environmentId: string | null;
 }): Promise<string> {
-  return invokeCmd('cmd_render_template', { template, workspaceId, environmentId });
+  return invokeCmd('cmd_render_template', { template, workspaceId, folderId, environmentId });
 }
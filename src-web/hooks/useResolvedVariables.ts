@@ -0,0 +1,22 @@
+import { useQuery } from '@tanstack/react-query';
+import type { ResolvedVariable } from '@yaakapp-internal/models';
+import { invokeCmd } from '../lib/tauri';
+import { useActiveEnvironment } from './useActiveEnvironment';
+import { useActiveWorkspace } from './useActiveWorkspace';
+import { useEnvironments } from './useEnvironments';
+import { useFolders } from './useFolders';
+
+/** Rendered variables for requests in the given folder, with the layer each value came from */
+export function useResolvedVariables(folderId: string | null) {
+  const workspaceId = useActiveWorkspace()?.id ?? 'n/a';
+  const environmentId = useActiveEnvironment()?.id ?? null;
+  // Re-render when any variables change
+  const { allEnvironments } = useEnvironments();
+  const folders = useFolders();
+  return useQuery<ResolvedVariable[]>({
+    placeholderData: (prev) => prev, // Keep previous data on refetch
+    refetchOnWindowFocus: false,
+    queryKey: ['render_variables', workspaceId, folderId, environmentId, allEnvironments, folders],
+    queryFn: () => invokeCmd('cmd_render_variables', { workspaceId, folderId, environmentId }),
+  });
+}
@@ -1,11 +1,20 @@
 import { useQuery } from '@tanstack/react-query';
 import type { HttpResponse } from '@yaakapp-internal/models';
 import { getResponseBodyText } from '../lib/responseBody';
+import { useHttpRequest } from './useHttpRequest';
 
 export function useResponseBodyText(response: HttpResponse) {
+  // Re-decode when the request's charset override changes
+  const responseCharset = useHttpRequest(response.requestId)?.responseCharset ?? null;
   return useQuery<string | null>({
     placeholderData: (prev) => prev, // Keep previous data on refetch
-    queryKey: ['response-body-text', response.id, response.updatedAt, response.contentLength],
+    queryKey: [
+      'response-body-text',
+      response.id,
+      response.updatedAt,
+      response.contentLength,
+      responseCharset,
+    ],
     queryFn: () => getResponseBodyText(response),
   });
 }
@@ -13,6 +13,7 @@ import { grpcConnectionsAtom } from './useGrpcConnections';
 import { grpcEventsQueryKey } from './useGrpcEvents';
 import { grpcRequestsAtom } from './useGrpcRequests';
 import { httpRequestsAtom } from './useHttpRequests';
+import { httpResponseEventsQueryKey } from './useHttpResponseEvents';
 import { httpResponsesAtom } from './useHttpResponses';
 import { keyValueQueryKey, keyValuesAtom } from './useKeyValue';
 import { useListenToTauriEvent } from './useListenToTauriEvent';
@@ -36,9 +37,11 @@ export function useSyncModelStores() {
         ? grpcEventsQueryKey(payload.model)
         : payload.model.model === 'websocket_event'
           ? websocketEventsQueryKey(payload.model)
-          : payload.model.model === 'key_value'
-            ? keyValueQueryKey(payload.model)
-            : null;
+          : payload.model.model === 'http_response_event'
+            ? httpResponseEventsQueryKey(payload.model)
+            : payload.model.model === 'key_value'
+              ? keyValueQueryKey(payload.model)
+              : null;
 
     // TODO: Move this logic to useRequestEditor() hook
     if (
@@ -127,6 +130,11 @@ export function useSyncModelStores() {
       jotaiStore.set(grpcConnectionsAtom, removeModelById(payload.model));
     } else if (payload.model.model === 'grpc_event') {
       queryClient.setQueryData(grpcEventsQueryKey(payload.model), removeModelById(payload.model));
+    } else if (payload.model.model === 'http_response_event') {
+      queryClient.setQueryData(
+        httpResponseEventsQueryKey(payload.model),
+        removeModelById(payload.model),
+      );
     } else if (payload.model.model === 'key_value') {
       queryClient.setQueryData(keyValueQueryKey(payload.model), removeModelByKv(payload.model));
     } else if (payload.model.model === 'cookie_jar') {
@@ -0,0 +1,59 @@
+import type { HttpAssertion, HttpAssertionType } from '@yaakapp-internal/models';
+
+interface AssertionTypeInfo {
+  label: string;
+  targetPlaceholder: string | null;
+  valuePlaceholder: string;
+}
+
+export const assertionTypes: Record<HttpAssertionType, AssertionTypeInfo> = {
+  status: { label: 'Status Code', targetPlaceholder: null, valuePlaceholder: '200 or 2xx' },
+  header: {
+    label: 'Header',
+    targetPlaceholder: 'Content-Type',
+    valuePlaceholder: 'Value (empty to check it exists)',
+  },
+  json_path_equals: {
+    label: 'JSONPath Equals',
+    targetPlaceholder: '$.data.id',
+    valuePlaceholder: 'Expected JSON value',
+  },
+  json_path_exists: {
+    label: 'JSONPath Exists',
+    targetPlaceholder: '$.data.id',
+    valuePlaceholder: '',
+  },
+  body_matches: { label: 'Body Matches', targetPlaceholder: null, valuePlaceholder: 'Regex' },
+  response_time_under: {
+    label: 'Response Time Under',
+    targetPlaceholder: null,
+    valuePlaceholder: 'Milliseconds',
+  },
+  json_schema: { label: 'JSON Schema', targetPlaceholder: null, valuePlaceholder: 'Schema JSON' },
+  // Only added by post-response scripts, so it's left out of the editor
+  script: { label: 'Script', targetPlaceholder: 'Test name', valuePlaceholder: '' },
+};
+
+/** A short description of what an assertion checks, like `Status Code is 2xx` */
+export function describeAssertion(assertion: HttpAssertion): string {
+  const { label } = assertionTypes[assertion.assertionType];
+  const { target, value } = assertion;
+  switch (assertion.assertionType) {
+    case 'status':
+      return `${label} is ${value}`;
+    case 'header':
+      return value === '' ? `${label} ${target} exists` : `${label} ${target} is ${value}`;
+    case 'json_path_equals':
+      return `${target} equals ${value}`;
+    case 'json_path_exists':
+      return `${target} exists`;
+    case 'body_matches':
+      return `Body matches /${value}/`;
+    case 'response_time_under':
+      return `${label} ${value} ms`;
+    case 'json_schema':
+      return 'Body matches JSON Schema';
+    case 'script':
+      return target;
+  }
+}
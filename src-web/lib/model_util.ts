@@ -1,5 +1,4 @@
-import type { AnyModel, Cookie, HttpResponseHeader } from '@yaakapp-internal/models';
-import { getMimeTypeFromContentType } from './contentType';
+import type { AnyModel, HttpResponseHeader } from '@yaakapp-internal/models';
 
 export const BODY_TYPE_NONE = null;
 export const BODY_TYPE_GRAPHQL = 'graphql';
@@ -10,19 +9,6 @@ export const BODY_TYPE_FORM_URLENCODED = 'application/x-www-form-urlencoded';
 export const BODY_TYPE_FORM_MULTIPART = 'multipart/form-data';
 export const BODY_TYPE_XML = 'text/xml';
 
-export function cookieDomain(cookie: Cookie): string {
-  if (cookie.domain === 'NotPresent' || cookie.domain === 'Empty') {
-    return 'n/a';
-  }
-  if ('HostOnly' in cookie.domain) {
-    return cookie.domain.HostOnly;
-  }
-  if ('Suffix' in cookie.domain) {
-    return cookie.domain.Suffix;
-  }
-  return 'unknown';
-}
-
 export function modelsEq(a: AnyModel, b: AnyModel) {
   if (a.model != b.model) {
     return false;
@@ -39,11 +25,3 @@ export function modelsEq(a: AnyModel, b: AnyModel) {
 export function getContentTypeFromHeaders(headers: HttpResponseHeader[] | null): string | null {
   return headers?.find((h) => h.name.toLowerCase() === 'content-type')?.value ?? null;
 }
-
-export function getCharsetFromContentType(headers: HttpResponseHeader[]): string | null {
-  const contentType = getContentTypeFromHeaders(headers);
-  if (contentType == null) return null;
-
-  const mimeType = getMimeTypeFromContentType(contentType);
-  return mimeType.parameters.get('charset') ?? null;
-}
@@ -2,9 +2,11 @@ import type { InvokeArgs } from '@tauri-apps/api/core';
 import { invoke } from '@tauri-apps/api/core';
 
 type TauriCmd =
+  | 'cmd_add_cookie'
   | 'cmd_call_http_authentication_action'
   | 'cmd_call_http_request_action'
   | 'cmd_check_for_updates'
+  | 'cmd_collect_garbage'
   | 'cmd_create_cookie_jar'
   | 'cmd_create_environment'
   | 'cmd_create_grpc_request'
@@ -12,6 +14,7 @@ type TauriCmd =
   | 'cmd_curl_to_request'
   | 'cmd_delete_all_grpc_connections'
   | 'cmd_delete_all_http_responses'
+  | 'cmd_delete_cookie'
   | 'cmd_delete_cookie_jar'
   | 'cmd_delete_environment'
   | 'cmd_delete_folder'
@@ -25,6 +28,7 @@ type TauriCmd =
   | 'cmd_duplicate_folder'
   | 'cmd_duplicate_grpc_request'
   | 'cmd_duplicate_http_request'
+  | 'cmd_export_cookies'
   | 'cmd_export_data'
   | 'cmd_filter_response'
   | 'cmd_format_json'
@@ -37,12 +41,13 @@ type TauriCmd =
   | 'cmd_get_http_request'
   | 'cmd_get_key_value'
   | 'cmd_get_settings'
-  | 'cmd_get_sse_events'
   | 'cmd_get_workspace'
   | 'cmd_get_workspace_meta'
   | 'cmd_grpc_go'
   | 'cmd_grpc_reflect'
+  | 'cmd_hexdump_response_body'
   | 'cmd_http_request_actions'
+  | 'cmd_import_cookies'
   | 'cmd_import_data'
   | 'cmd_install_plugin'
   | 'cmd_list_cookie_jars'
@@ -52,6 +57,7 @@ type TauriCmd =
   | 'cmd_list_grpc_events'
   | 'cmd_list_grpc_requests'
   | 'cmd_list_http_requests'
+  | 'cmd_list_http_response_events'
   | 'cmd_list_http_responses'
   | 'cmd_list_key_values'
   | 'cmd_list_plugins'
@@ -61,9 +67,16 @@ type TauriCmd =
   | 'cmd_new_main_window'
   | 'cmd_parse_template'
   | 'cmd_plugin_info'
+  | 'cmd_read_response_body'
+  | 'cmd_read_response_body_chunk'
+  | 'cmd_read_response_body_lines'
   | 'cmd_reload_plugins'
   | 'cmd_render_template'
+  | 'cmd_render_variables'
+  | 'cmd_run_http_requests'
   | 'cmd_save_response'
+  | 'cmd_save_run_report'
+  | 'cmd_search_response_body'
   | 'cmd_send_ephemeral_request'
   | 'cmd_send_http_request'
   | 'cmd_set_key_value'
@@ -71,6 +84,7 @@ type TauriCmd =
   | 'cmd_template_functions'
   | 'cmd_template_tokens_to_string'
   | 'cmd_uninstall_plugin'
+  | 'cmd_update_cookie'
   | 'cmd_update_cookie_jar'
   | 'cmd_update_environment'
   | 'cmd_update_folder'